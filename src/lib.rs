@@ -13,6 +13,12 @@ const STATE_ATTACK: u8 = 6;
 const STATE_HURT: u8 = 7;
 const STATE_DEAD: u8 = 8;
 const STATE_CROUCH: u8 = 9;
+const STATE_MELEE: u8 = 10;
+const STATE_WALL_SLIDE: u8 = 11;
+const STATE_WALL_JUMP: u8 = 12;
+const STATE_DASH: u8 = 13;
+const STATE_SLIDE: u8 = 14;  // Low dash along the floor, fits under low obstacles
+const STATE_HANG: u8 = 15;   // Hanging from a rope / monkey bars (LEVEL_ROPES)
 
 // ============================================================================
 // CONSTANTS - PHYSICS
@@ -21,41 +27,63 @@ const GRAVITY: f32 = 0.5;  // Normal gravity (keeps jump height the same)
 const TERMINAL_VEL: f32 = 6.0;
 const JUMP_VEL: f32 = -6.5;  // Reduced for lower/same jump height
 const WALK_SPEED: f32 = 1.5;  // Increased for more horizontal momentum
-const RUN_SPEED: f32 = 1.5;  // Increased from 2.5 for faster running
-const ACCEL: f32 = 0.2;
-const DECEL: f32 = 0.4;
-const AIR_CONTROL: f32 = 0.5;  // Significantly increased for longer horizontal jumps
-const JUMP_LENGTH_MULTIPLIER: f32 = 1.8;  // Control jump distance (1.0 = normal, 2.0 = double)
+const RUN_SPEED: f32 = 2.4;  // Hold A (scaled with the volume's walk_speed)
 const CLIMB_SPEED: f32 = 1.5;
+const LADDER_GRAB_HALF_WIDTH: f32 = 6.0;  // How close to the rails Santa must be to grab on
+const LADDER_TOP_HALF_WIDTH: f32 = 8.0;   // Ladder tops are one-way platforms this wide each side
 const CROUCH_SPEED: f32 = 0.75;
 
 const COYOTE_TIME: u8 = 4;
 const INPUT_BUFFER: u8 = 3;
 
+// Dash (D) and slide (Down+D). Both grant i-frames through player_invuln_timer
+const DASH_SPEED: f32 = 4.0;
+const DASH_FRAMES: u8 = 12;
+const SLIDE_FRAMES: u8 = 22;
+const SLIDE_FRICTION: f32 = 0.95;   // Slides lose speed, dashes don't
+const DASH_COOLDOWN: u8 = 45;
+const DASH_INVULN: u8 = 14;          // Enough to pass through Evil Santa's dash
+const CROUCH_TOP: f32 = 1.0;         // Hitbox top below center while crouched/sliding (standing: 19)
+
+// Wall-slide / wall-jump (needs the wall boots upgrade, has_wall_jump)
+const WALL_SLIDE_SPEED: f32 = 1.2;  // Fall speed cap while sliding
+const WALL_JUMP_LOCK: u8 = 10;      // Frames air control is ignored after a kick-off
+const WALL_COYOTE_TIME: u8 = 6;     // Wall jumps still work this long after leaving the wall
+
+// Keys the movement code reads this frame (handle_input fills it from the keyboard)
+#[derive(Clone, Copy, Default)]
+struct PlayerInput {
+    left: bool,
+    right: bool,
+    up: bool,
+    down: bool,
+    jump_pressed: bool,  // X
+    jump_held: bool,
+    attack: bool,        // Z
+    attack_held: bool,   // Charge shot
+    place_bomb: bool,    // C
+    melee_held: bool,    // Shift+Z forces a bonk
+    run_held: bool,      // A
+    dash_pressed: bool,  // D
+}
+
 // Animation frame counts
 const IDLE_FRAMES: u8 = 4;
 const RUN_FRAMES: u8 = 10;
 const JUMP_FRAMES: u8 = 5;
 const ATTACK_FRAMES: u8 = 5;
-// Boss animation constants
-const EVIL_IDLE_FRAMES: u8 = 4;
-const EVIL_WALK_FRAMES: u8 = 10;
-const EVIL_JUMP_FRAMES: u8 = 6;
-const EVIL_ATTACK_FRAMES: u8 = 6;
+// Boss animation frames live in each boss's BossSprites (see BOSS_DEFS)
 
 // Boss tuning
 const EVIL_WALK_SPEED: f32 = 1.2;
 const EVIL_JUMP_VEL: f32 = -5.5;
 const EVIL_ATTACK_RANGE: f32 = 40.0;
 
-// Boss fight arena settings (Level 3)
-const BOSS_TRIGGER_X: f32 = 1860.0;
-const BOSS_TRIGGER_Y: f32 = 164.0;
-const BOSS_ARENA_WIDTH: f32 = 360.0; // matches bossfight background width
+// Evil Santa arena spawn (Level 3)
 const BOSS_EVIL_SANTA_X: f32 = 280.0; // Adjusted for new arena width
 const BOSS_EVIL_SANTA_Y: f32 = 190.0; // Adjusted for platform at y=217
 
-// Boss entity state (flattened into GameState to avoid serialization derives)
+// Boss FSM states (shared by every boss, see BOSS_DEFS)
 const BOSS_STATE_IDLE: u8 = 10;
 const BOSS_STATE_SELECT: u8 = 11;
 const BOSS_STATE_ATTACK: u8 = 12;
@@ -64,14 +92,84 @@ const BOSS_STATE_DEAD: u8 = 14;
 const BOSS_STATE_JUMP: u8 = 15;
 const BOSS_STATE_FALL: u8 = 16;
 const BOSS_STATE_WALK: u8 = 17; // Start walk
+const BOSS_STATE_ROAR: u8 = 18; // Invulnerable phase-transition roar
 
 const ATTACK_DASH: u8 = 0;
 const ATTACK_SLAM: u8 = 1;
 const ATTACK_PROJECTILE: u8 = 2;
+const ATTACK_SACK_SUMMON: u8 = 3;  // Pull minions out of the toy sack
+const ATTACK_COAL_RAIN: u8 = 4;    // Coal falls from the chimney, shadows telegraph it
+const ATTACK_SHOCKWAVE: u8 = 5;    // Ground-pound wave that travels along the floor
 
 const PHASE_WINDUP: u8 = 0;
 const PHASE_ACTIVE: u8 = 1;
 const PHASE_RECOVERY: u8 = 2;
+
+// Boss fight phases (separate from the per-attack PHASE_* steps above)
+const BOSS_FIGHT_PHASE_1: u8 = 1;
+const BOSS_FIGHT_PHASE_2: u8 = 2; // Extra ledges + falling icicles
+const BOSS_FIGHT_PHASE_3: u8 = 3; // Floor crumbles from the edges
+const BOSS_PHASE_2_HP_RATIO: f32 = 0.5;
+const BOSS_PHASE_3_HP_RATIO: f32 = 0.25;
+
+// Attack pools per fight phase live in data/boss_patterns.json
+
+const BOSS_ROAR_TIME: u32 = 90;     // 1.5 seconds of invulnerable roar
+const BOSS_SCREEN_FLASH: u8 = 30;   // White flash at the start of the roar
+
+// Boss arena geometry (360x240 frame, floor platform at y=217)
+const BOSS_ARENA_LEFT: f32 = 10.0;
+const BOSS_ARENA_RIGHT: f32 = 350.0;
+const BOSS_ARENA_FLOOR_Y: f32 = 217.0;
+const BOSS_FLOOR_MIN_LEFT: f32 = 70.0;   // Phase 3 floor never shrinks past these
+const BOSS_FLOOR_MIN_RIGHT: f32 = 290.0;
+const BOSS_FLOOR_SHRINK_INTERVAL: u16 = 240;
+const BOSS_FLOOR_SHRINK_STEP: f32 = 10.0;
+
+// Falling hazards: icicles (phase 2+) and chimney coal (ATTACK_COAL_RAIN)
+const HAZARD_ICICLE: u8 = 0;
+const HAZARD_COAL: u8 = 1;
+const ICICLE_TELEGRAPH_TIME: u8 = 45;
+const ICICLE_CEILING_Y: f32 = 32.0;
+const COAL_TELEGRAPH_TIME: u8 = 40;
+const COAL_START_Y: f32 = -8.0;  // Just above the screen, only the shadow shows at first
+
+#[turbo::serialize]
+#[derive(Copy)]
+struct FallingHazard {
+    active: bool,
+    x: f32,
+    y: f32,
+    vy: f32,
+    telegraph: u8,  // Frames of warning shadow left before it drops
+    kind: u8,       // HAZARD_*
+}
+
+impl FallingHazard {
+    const EMPTY: FallingHazard = FallingHazard { active: false, x: 0.0, y: 0.0, vy: 0.0, telegraph: 0, kind: HAZARD_ICICLE };
+}
+
+// Ground-pound wave sliding along the arena floor
+#[turbo::serialize]
+#[derive(Copy)]
+struct Shockwave {
+    active: bool,
+    x: f32,
+    vx: f32,
+}
+
+impl Shockwave {
+    const EMPTY: Shockwave = Shockwave { active: false, x: 0.0, vx: 0.0 };
+
+    const fn new(x: f32, vx: f32) -> Self {
+        Self { active: true, x, vx }
+    }
+}
+
+// Sack summon: minions reuse the regular enemy kinds (1=mouse, 2=kickmouse, 3=penguin)
+const BOSS_MAX_MINIONS: usize = 3;
+const BOSS_MINION_KINDS: [u8; 3] = [1, 2, 3];
+
 // ============================================================================
 // CONSTANTS - GAME
 // ============================================================================
@@ -82,2046 +180,4569 @@ const HUD_HEIGHT: f32 = 16.0;
 const LEVEL_TIME: u32 = 180 * 60; // 60 minutes (3600 seconds)
 
 // ============================================================================
-// MAIN GAME STATE
+// CONSTANTS - DROP TABLES
 // ============================================================================
-    #[turbo::game]
-    struct GameState {
-    frame: u32,
+const DROP_NONE: u8 = 0;
+const DROP_GIFT_BOMB: u8 = 1;
+const DROP_COIN: u8 = 2;
+const DROP_CANDY: u8 = 3;
+const DROP_WALL_BOOTS: u8 = 4;  // Upgrade, never despawns
+const DROP_COCOA: u8 = 5;       // DROP_COCOA..=DROP_BELL are consumables, see ITEMS
+const DROP_SHIELD: u8 = 6;
+const DROP_SNOW_GLOBE: u8 = 7;
+const DROP_BELL: u8 = 8;
+const UPGRADE_MESSAGE_FRAMES: u16 = 180;
+
+const DROP_DESPAWN_TIME: u16 = 600;  // 10 seconds at 60 FPS
+const DROP_NO_DESPAWN: u16 = u16::MAX;  // Despawn timer for upgrades and placed pickups
+const DROP_BLINK_TIME: u16 = 120;    // Blink warning during the last 2 seconds
+const COIN_SCORE: u32 = 250;
+
+// Weighted outcomes: (drop kind, weight). Weights are relative, they don't need to sum to 100.
+// Gift bombs stay the most likely enemy drop since doors can only be opened with bombs.
+const DROP_TABLE_MOUSE: [(u8, u16); 6] = [(DROP_GIFT_BOMB, 60), (DROP_COIN, 25), (DROP_CANDY, 5), (DROP_COCOA, 4), (DROP_SNOW_GLOBE, 2), (DROP_NONE, 10)];
+const DROP_TABLE_KICKMOUSE: [(u8, u16); 6] = [(DROP_GIFT_BOMB, 55), (DROP_COIN, 25), (DROP_CANDY, 10), (DROP_SHIELD, 4), (DROP_COCOA, 3), (DROP_NONE, 10)];
+const DROP_TABLE_PENGUIN: [(u8, u16); 6] = [(DROP_GIFT_BOMB, 50), (DROP_COIN, 20), (DROP_CANDY, 15), (DROP_SNOW_GLOBE, 4), (DROP_BELL, 2), (DROP_NONE, 15)];
+// Doors already give a key/kid/powerup, this is a bonus roll on top
+const DROP_TABLE_DOOR: [(u8, u16); 7] = [(DROP_GIFT_BOMB, 15), (DROP_COIN, 35), (DROP_CANDY, 15), (DROP_BELL, 6), (DROP_SHIELD, 5), (DROP_COCOA, 5), (DROP_NONE, 35)];
+
+// Loot lying on the floor until Santa picks it up or it despawns
+#[turbo::serialize]
+#[derive(Copy)]
+struct DropItem {
+    x: f32,
+    y: f32,
+    active: bool,
+    despawn_timer: u16,  // DROP_NO_DESPAWN keeps it forever
+    kind: u8,            // DROP_*
+}
 
-    // Player state
-    player_x: f32,
-    player_y: f32,
-    player_vx: f32,
-    player_vy: f32,
-    player_state: u8,
-    player_facing_right: bool,
-    player_on_ground: bool,
-    player_hp: u8,
-    player_max_hp: u8,
-    player_invuln_timer: u8,
-    player_anim_frame: u8,
-    player_anim_timer: u8,
-    player_coyote_timer: u8,
-    player_jump_buffer: u8,
-    player_on_ladder: bool,
-    player_is_crouching: bool,
-    jump_anim_frame: u8,
-    jump_anim_timer: u8,
+impl DropItem {
+    const EMPTY: DropItem = DropItem { x: 0.0, y: 0.0, active: false, despawn_timer: 0, kind: DROP_NONE };
 
-    // Attack state
-    attack_cooldown: u8,
-    attack_frame: u8,
+    const fn new(x: f32, y: f32, despawn_timer: u16, kind: u8) -> Self {
+        Self { x, y, active: true, despawn_timer, kind }
+    }
+}
 
-    // Projectiles (up to 6 total)
-    projectiles: [(bool, f32, f32, f32, bool, f32); 6],
+// ============================================================================
+// CONSUMABLES
+// ============================================================================
+// Items Santa carries and uses on demand: E uses the selected one, Q cycles.
+// They come from the drop tables above and from LEVEL_ITEMS, carry over between
+// levels and are lost on game over.
+const ITEM_COCOA: u8 = 0;       // Heals COCOA_HEAL
+const ITEM_SHIELD: u8 = 1;      // Blocks all hits for SHIELD_FRAMES
+const ITEM_SNOW_GLOBE: u8 = 2;  // Freezes every enemy for FREEZE_FRAMES
+const ITEM_BELL: u8 = 3;        // Reveals the level's hidden kids
+
+const COCOA_HEAL: u8 = 4;
+const SHIELD_FRAMES: u16 = 480;     // 8 seconds
+const FREEZE_FRAMES: u16 = 300;     // 5 seconds
+const ITEM_MESSAGE_FRAMES: u16 = 90;
+const ITEM_MSG_PICKUP: u8 = 0;  // item_message kinds
+const ITEM_MSG_USED: u8 = 1;
+const ITEM_MSG_NOT_NOW: u8 = 2;  // Would do nothing (full HP, nothing left to reveal)
+const HIDDEN_KID_SCORE: u32 = 1000;
+
+struct ItemDef {
+    name: &'static str,
+    color: u32,          // HUD slot and message color
+    max_carried: u8,     // Pickups stay on the ground past this
+    use_text: &'static str,
+}
 
-    // Enemies (10 max for now)
-    // Format: (type, x, y, vx, vy, direction, anim_frame, patrol_start_x, active, anim_timer, is_attacking, respawn_timer)
-    // respawn_timer: 0 = alive, >0 = dead and counting down to respawn (600 frames = 10 seconds)
-    // cloud animation frame calculated from respawn_timer when needed
-    enemies: [(u8, f32, f32, f32, f32, u8, u8, u16, bool, u8, bool, u16); 10],
+// Indexed by ITEM_*, the drop kind is DROP_COCOA + index
+const ITEMS: [ItemDef; 4] = [
+    ItemDef { name: "COCOA", color: 0xc87533ff, max_carried: 3, use_text: "WARM COCOA!" },
+    ItemDef { name: "SHIELD", color: 0xff4060ff, max_carried: 2, use_text: "SHIELD UP!" },
+    ItemDef { name: "GLOBE", color: 0x9fe8ffff, max_carried: 2, use_text: "FROZEN!" },
+    ItemDef { name: "BELL", color: 0xffd700ff, max_carried: 1, use_text: "JINGLE!" },
+];
 
-    // Penguin snowball projectiles (up to 5)
-    // Format: (active, x, y, vx, vy)
-    snowballs: [(bool, f32, f32, f32, f32); 5],
+struct PlacedItem {
+    level: u8,
+    x: f32,
+    y: f32,
+    kind: u8,  // DROP_*
+}
 
-    // Gift Bombs
-    gift_bombs: u8,  // Number of gift bombs Santa has
-    gift_bomb_items: [(f32, f32, bool, u16); 5],  // Dropped gift bombs to pick up (x, y, active, despawn_timer)
-    placed_bombs: [(f32, f32, bool, u8, u8); 3],  // Placed bombs (x, y, active, anim_frame, anim_timer)
+// Placed pickups, they don't despawn
+const LEVEL_ITEMS: [PlacedItem; 4] = [
+    PlacedItem { level: 1, x: 150.0, y: 135.0, kind: DROP_COCOA },
+    PlacedItem { level: 1, x: 1000.0, y: 67.0, kind: DROP_BELL },
+    PlacedItem { level: 2, x: 705.0, y: 136.0, kind: DROP_SHIELD },
+    PlacedItem { level: 2, x: 1100.0, y: 69.0, kind: DROP_SNOW_GLOBE },
+];
+
+// Bonus kids that only show up once the reindeer bell rings. Not needed to finish the level
+// Format: (level, x, y)
+const LEVEL_HIDDEN_KIDS: [(u8, f32, f32); 3] = [
+    (1, 120.0, 130.0),
+    (2, 1380.0, 64.0),
+    (2, 600.0, 111.0),
+];
 
-    // Doors (4 doors that can be destroyed by bombs)
-    // Format: (x, y, intact)
-    // x, y are top-left corner coordinates
-    // intact: true = normal door, false = exploded
-    doors: [(f32, f32, bool); 4],
+// ============================================================================
+// GIFT BOMBS
+// ============================================================================
+const GIFT_BOMB_START_CAPACITY: u8 = 3;  // Bombs Santa can carry (gift_bomb_capacity)
+const GIFT_BOMB_RADIUS: f32 = 50.0;      // Blast reach in x and y from the bomb
+const GIFT_BOMB_THROW_VX: f32 = 3.0;     // Up+C lob
+const GIFT_BOMB_THROW_VY: f32 = -4.5;
+const GIFT_BOMB_DAMAGE: u8 = 3;         // Boss and block damage
+const GIFT_BOMB_CHAIN_DELAY: u8 = 8;     // Frames before a bomb caught in a blast goes off
+
+// A placed or thrown bomb
+#[turbo::serialize]
+#[derive(Copy)]
+struct PlacedBomb {
+    x: f32,
+    y: f32,
+    active: bool,
+    anim_frame: u8,   // 0 fuse, 1-8 warning blinks, 9 flash, 10-11 explosion
+    anim_timer: u8,
+    vx: f32,
+    vy: f32,
+    airborne: bool,   // Still flying from a throw
+}
 
-    // Keys dropped from exploded doors
-    // Format: (x, y, active, anim_frame, anim_timer)
-    keys: [(f32, f32, bool, u8, u8); 4],
-    keys_collected: u8,
-    key_pickup_flash: u8,
+impl PlacedBomb {
+    const EMPTY: PlacedBomb = PlacedBomb::new(0.0, 0.0, 0.0, 0.0, false);
 
-    // Game state
-    score: u32,
-    lives: u8,
-    level: u8,
-    timer: u32,
-    camera_x: f32,
+    const fn new(x: f32, y: f32, vx: f32, vy: f32, airborne: bool) -> Self {
+        Self { x, y, active: true, anim_frame: 0, anim_timer: 0, vx, vy, airborne }
+    }
+}
 
-    // Developer options
-    dev_mode: bool,
-    // UI: Controls panel overlay
-    show_controls_panel: bool,
-    
-    // Game state management
-    in_menu: bool,  // true = in start menu, false = playing
-    show_controls: bool,  // true = showing controls page after starting page
-    game_over_timer: u16,  // Timer for game over screen (10 seconds)
-    game_won_timer: u16,  // Timer for victory screen (3 seconds)
-    time_up_timer: u16,  // Timer for time up screen (3 seconds)
-    show_game_over: bool,
-    show_victory: bool,
-    show_time_up: bool,  // true = showing time up screen
+// ============================================================================
+// DESTRUCTIBLE BLOCKS
+// ============================================================================
+// Solid blocks that stop Santa, enemies and shots until broken. destroyed_by
+// is a mask of BREAK_BY_* flags, anything else just bounces off.
+const BREAK_BY_BOMB: u8 = 1;
+const BREAK_BY_SHOT: u8 = 2;     // Any shot, charged or not
+const BREAK_BY_CHARGED: u8 = 4;  // Charged shots only
+const BREAK_BY_MELEE: u8 = 8;
+
+const BLOCK_HIT_FLASH: u8 = 8;   // Also limits a melee swing to one hit
+const BLOCK_SCORE: u32 = 100;
+
+// Runtime state of a block, also used as the level data below
+#[turbo::serialize]
+#[derive(Copy)]
+struct Block {
+    x: f32,
+    y: f32,
+    w: f32,
+    h: f32,
+    hp: u8,            // 0 = broken (or empty slot)
+    destroyed_by: u8,  // BREAK_BY_* mask
+    drop: u8,          // DROP_* spawned when it breaks
+    flash: u8,         // Hit flash frames left
+}
 
-    // Level completion
-    level_complete: bool,
-    level_transition_timer: u8,
-    completion_trigger: (f32, f32, f32, f32), // x, y, width, height
+impl Block {
+    const EMPTY: Block = Block::new(0.0, 0.0, 0.0, 0.0, 0, 0, DROP_NONE);
 
-    // Level layout - 3 floors with multiple platforms
-    // Using Vec to support variable number of platforms (boss level has 52)
-    platforms: Vec<(f32, f32, f32)>, // x1, x2, y for each platform
-    ladders: [(f32, f32, f32); 6],    // x, y_top, y_bottom for each ladder
-    walls: [(f32, f32, f32, f32); 22], // x, y, width, height for each wall
+    const fn new(x: f32, y: f32, w: f32, h: f32, hp: u8, destroyed_by: u8, drop: u8) -> Self {
+        Self { x, y, w, h, hp, destroyed_by, drop, flash: 0 }
+    }
+}
 
-    // Kids to rescue (up to 3 per level)
-    // Format: (x, y, active, collected, anim_frame, anim_timer, spawned_from_door_idx)
-    kids: [(f32, f32, bool, bool, u8, u8, i8); 3],
-    kids_collected: u8,
-    
-    // Level timer (in frames, 60 fps)
-    level_timer: u32,  // Current time elapsed
-    level_time_limit: u32,  // Time limit for current level
-    total_kids_in_level: u8,
-    kid_pickup_flash: u8,
-    kid_door_index: usize,  // Which door (0-3) spawns a kid instead of a key
+// Level data. Format: (level, block)
+const LEVEL_BLOCKS: [(u8, Block); 3] = [
+    // Ice wall splitting the middle floor, the ladders go around it
+    (1, Block::new(470.0, 124.0, 14.0, 30.0, 3, BREAK_BY_BOMB, DROP_COIN)),
+    // Crate on the ground floor
+    (1, Block::new(330.0, 208.0, 16.0, 16.0, 2, BREAK_BY_SHOT | BREAK_BY_MELEE | BREAK_BY_BOMB, DROP_CANDY)),
+    // Steel block on the top-right floor with a spare gift bomb inside
+    (1, Block::new(880.0, 56.0, 14.0, 30.0, 4, BREAK_BY_CHARGED | BREAK_BY_BOMB, DROP_GIFT_BOMB)),
+];
 
-    // Life powerup (spawns from specific door in boss level)
-    life_position: (f32, f32),
-    life_active: bool,
-    life_collected: bool,
-    life_door_index: usize,  // Which door spawns life powerup (door #3 in level 3)
+// ============================================================================
+// KEYS & GATES
+// ============================================================================
+// Keys are colored, a gate opens once Santa holds every key it asks for.
+// keys_held is a bitmask of (1 << KEY_*).
+const KEY_RED: u8 = 0;
+const KEY_GREEN: u8 = 1;
+const KEY_BLUE: u8 = 2;
+
+// Format: (name, color), indexed by KEY_*
+const KEY_COLORS: [(&str, u32); 3] = [
+    ("RED", 0xff4040ff),
+    ("GREEN", 0x40e060ff),
+    ("BLUE", 0x40a0ffff),
+];
+
+// A key dropped from an exploded door
+#[turbo::serialize]
+#[derive(Copy)]
+struct Key {
+    x: f32,
+    y: f32,
+    active: bool,
+    anim_frame: u8,
+    anim_timer: u8,
+    color: u8,  // KEY_*
+}
 
-    // PowerUp1 (spawns from door #4 in level 3)
-    powerup1_position: (f32, f32),
-    powerup1_active: bool,
-    powerup1_collected: bool,
-    has_firepower: bool,  // Granted by powerUp1
+impl Key {
+    const EMPTY: Key = Key { x: 0.0, y: 0.0, active: false, anim_frame: 0, anim_timer: 0, color: KEY_RED };
 
-    // Boss fight state
-        boss_active: bool,
-        boss_defeated: bool,  // Prevents boss from re-triggering after victory
-        use_boss_santa: bool,
-        evil_santa_x: f32,
-        evil_santa_y: f32,
-        evil_santa_facing_right: bool,
-        evil_santa_state: u8,
-        evil_santa_anim_frame: u8,
-        evil_santa_anim_timer: u8,
-        // Boss combat/physics
-        evil_santa_hp: u8,
-        evil_santa_max_hp: u8,
-        evil_santa_vx: f32,
-        evil_santa_vy: f32,
-        evil_santa_on_ground: bool,
-        evil_santa_attack_cooldown: u8,
-        evil_santa_attack_frame: u8,
-        evil_santa_jump_cooldown: u8,
-        // AI FSM
-        boss_state_timer: u32,
-        boss_pattern_index: u8,
-        boss_phase: u8,
-        boss_phase_timer: u32,
-        boss_attack_type: u8,
-        evil_santa_flash_timer: u8,
-        // Player idle tracking for boss aggression
-        player_idle_x: f32,
-        player_idle_timer: u32,
-        // Boss death handling
-        boss_death_timer: u16,
+    const fn new(x: f32, y: f32, color: u8) -> Self {
+        Self { x, y, active: true, anim_frame: 0, anim_timer: 0, color }
     }
+}
 
-impl GameState {
-    pub fn new() -> Self {
-        let mut game = Self {
-            frame: 0,
+const GATE_OPEN_FRAMES: u8 = 40;       // Gate slides up over this many frames
+const GATE_MESSAGE_FRAMES: u16 = 120;  // "<NAME> OPEN!" stays up this long
+
+// What each door holds once bombed: DOOR_KID frees a kid, KEY_* drops that key.
+// Level 3's doors 2 and 3 also release the life and weapon powerups next to their key.
+// Format: (level, [door 0, door 1, door 2, door 3])
+const DOOR_KID: u8 = u8::MAX;
+const LEVEL_DOOR_CONTENTS: [(u8, [u8; 4]); 3] = [
+    (1, [KEY_RED, DOOR_KID, KEY_GREEN, KEY_BLUE]),
+    (2, [KEY_BLUE, KEY_RED, DOOR_KID, KEY_GREEN]),
+    (3, [DOOR_KID, DOOR_KID, KEY_RED, KEY_GREEN]),
+];
+
+// Gates are walls entries that open with the right keys
+struct Gate {
+    level: u8,
+    wall: usize,            // walls index
+    name: &'static str,
+    required: u8,           // Keys mask
+    sprite: &'static str,   // "" draws bars
+}
 
-            player_x: 50.0,
-            player_y: 100.0,
-            player_vx: 0.0,
-            player_vy: 0.0,
-            player_state: STATE_IDLE,
-            player_facing_right: true,
-            player_on_ground: false,
-            player_hp: 6,
-            player_max_hp: 6,
-            player_invuln_timer: 0,
-            player_anim_frame: 0,
-            player_anim_timer: 0,
-            player_coyote_timer: 0,
-            player_jump_buffer: 0,
-            player_on_ladder: false,
-            player_is_crouching: false,
-            jump_anim_frame: 0,
-            jump_anim_timer: 0,
+const LEVEL_GATES: [Gate; 4] = [
+    Gate { level: 1, wall: 2, name: "NORTH GATE", required: 1 << KEY_RED | 1 << KEY_GREEN | 1 << KEY_BLUE, sprite: "gatelevel" },
+    Gate { level: 2, wall: 2, name: "NORTH GATE", required: 1 << KEY_RED | 1 << KEY_GREEN | 1 << KEY_BLUE, sprite: "gatelevel" },
+    Gate { level: 3, wall: 0, name: "ICE GATE", required: 1 << KEY_RED, sprite: "" },
+    Gate { level: 3, wall: 1, name: "COAL GATE", required: 1 << KEY_RED | 1 << KEY_GREEN, sprite: "" },
+];
 
-            attack_cooldown: 0,
-            attack_frame: 0,
+// ============================================================================
+// INTERACTIVE OBJECTS
+// ============================================================================
+// Triggers drive numbered links, targets listen to a link. A link is on while
+// any of its triggers is on.
+const TRIGGER_SWITCH: u8 = 0;  // Wall-mounted, flipped by Santa's shots
+const TRIGGER_PLATE: u8 = 1;   // Floor plate, held down by Santa, enemies or resting bombs
+const TRIGGER_TIMER: u8 = 2;   // Flips itself every `duration` frames
+
+const TARGET_GATE: u8 = 0;      // index = walls entry, open while the link is on
+const TARGET_PLATFORM: u8 = 1;  // index = platforms entry, slid by (a, b) while the link is on
+const TARGET_SPAWNER: u8 = 2;   // index = enemy type, spawned at (a, b) when the link turns on, patrols from patrol_x
+
+const SWITCH_SIZE: (f32, f32) = (8.0, 12.0);
+const PLATE_HALF_WIDTH: f32 = 12.0;
+const MOVING_PLATFORM_SPEED: f32 = 1.0;  // Pixels per frame
+
+#[turbo::serialize]
+#[derive(Copy)]
+struct Trigger {
+    kind: u8,       // TRIGGER_*
+    x: f32,         // Switches use top-left, plates the floor center
+    y: f32,
+    link: u8,       // 0 = empty slot
+    duration: u16,  // A hit switch / released plate stays on this many frames
+                    // (0 = switch toggles, plate is on only while held)
+    on: bool,
+    timer: u16,
+}
 
-            projectiles: [(false, 0.0, 0.0, 0.0, false, 0.0); 6],
-            enemies: [(0, 0.0, 0.0, 0.0, 0.0, 0, 0, 0, false, 0, false, 0); 10],
-            snowballs: [(false, 0.0, 0.0, 0.0, 0.0); 5],  // Penguin snowball projectiles
+impl Trigger {
+    const EMPTY: Trigger = Trigger::new(TRIGGER_SWITCH, 0.0, 0.0, 0, 0);
 
-            gift_bombs: 0,
-            gift_bomb_items: [(0.0, 0.0, false, 0); 5],
-            placed_bombs: [(0.0, 0.0, false, 0, 0); 3],
-            doors: [(0.0, 0.0, false); 4],  // Will be set in load_level
+    const fn new(kind: u8, x: f32, y: f32, link: u8, duration: u16) -> Self {
+        Self { kind, x, y, link, duration, on: false, timer: 0 }
+    }
+}
 
-            score: 0,
-            lives: 3,
-            level: 1,
-            timer: LEVEL_TIME,
-            camera_x: 0.0,
+#[turbo::serialize]
+#[derive(Copy)]
+struct LinkTarget {
+    link: u8,                // 0 = empty slot
+    kind: u8,                // TARGET_*
+    index: usize,
+    a: f32,
+    b: f32,
+    patrol_x: u16,           // Spawners: left end of the released enemy's patrol
+    progress: f32,           // Platforms: 0 = parked, 1 = fully slid
+    spawned: Option<usize>,  // Spawners: enemies slot of the released enemy
+}
 
-            // Developer options
-            dev_mode: false,
-            show_controls_panel: false,
-            
-            in_menu: true,  // Start with menu screen
-            show_controls: false,  // Controls page shown after starting page
-            show_game_over: false,
-            show_victory: false,
-            show_time_up: false,
-            game_over_timer: 0,
-            game_won_timer: 0,
-            time_up_timer: 0,
+impl LinkTarget {
+    const EMPTY: LinkTarget = LinkTarget::new(0, TARGET_GATE, usize::MAX, 0.0, 0.0);
 
-            level_complete: false,
-            level_transition_timer: 0,
-            completion_trigger: (0.0, 0.0, 0.0, 0.0),
+    const fn new(link: u8, kind: u8, index: usize, a: f32, b: f32) -> Self {
+        Self { link, kind, index, a, b, patrol_x: 0, progress: 0.0, spawned: None }
+    }
 
-            platforms: Vec::new(), // Will be populated in load_level
-            ladders: [
-                // Initialize empty, will be set in load_level
-                (0.0, 0.0, 0.0); 6
-            ],
-            walls: [
-                // Initialize empty, will be set in load_level
-                (0.0, 0.0, 0.0, 0.0); 22
-            ],
+    const fn spawner(link: u8, enemy_type: u8, x: f32, y: f32, patrol_x: u16) -> Self {
+        Self { patrol_x, ..Self::new(link, TARGET_SPAWNER, enemy_type as usize, x, y) }
+    }
+}
 
-            // Keys state
-            keys: [
-                (0.0, 0.0, false, 0, 0); 4
-            ],
-            keys_collected: 0,
-            key_pickup_flash: 0,
+// Level data. Format: (level, trigger)
+const LEVEL_TRIGGERS: [(u8, Trigger); 3] = [
+    // Ground floor plate, holds the side gate open a few seconds (a resting bomb works too)
+    (1, Trigger::new(TRIGGER_PLATE, 900.0, 224.0, 1, 180)),
+    // Switch across the right-hand ground gap, shoot it to bring the bridge over
+    (2, Trigger::new(TRIGGER_SWITCH, 962.0, 200.0, 2, 600)),
+    // Shuttle over the middle ground gap
+    (2, Trigger::new(TRIGGER_TIMER, 0.0, 0.0, 3, 150)),
+];
+
+// Format: (level, target)
+const LEVEL_TARGETS: [(u8, LinkTarget); 4] = [
+    (1, LinkTarget::new(1, TARGET_GATE, 8, 0.0, 0.0)),
+    // Kickmouse waiting behind the side gate
+    (1, LinkTarget::spawner(1, 2, 1030.0, 205.0, 975)),
+    (2, LinkTarget::new(2, TARGET_PLATFORM, 29, 57.0, 0.0)),
+    (2, LinkTarget::new(3, TARGET_PLATFORM, 30, 36.0, 0.0)),
+];
 
-            // Kids state
-            kids: [(0.0, 0.0, false, false, 0, 0, -1); 3],
-            kids_collected: 0,
-            level_timer: 0,
-            level_time_limit: 180 * 60,  // Default 3 minutes
-            total_kids_in_level: 0,
-            kid_pickup_flash: 0,
-            kid_door_index: 0,  // Will be randomized per level
+// ============================================================================
+// HAZARDS
+// ============================================================================
+// Level regions that hurt or push. Floor hazards (spikes, ice, conveyors) are a
+// strip sitting on the floor: the floor surface is y + height.
+const HAZARD_SPIKES: u8 = 0;
+const HAZARD_ICE: u8 = 1;
+const HAZARD_CONVEYOR: u8 = 2;  // param = push in px/frame (negative = left)
+const HAZARD_FIRE: u8 = 3;      // param = cycle length in frames
+
+const HAZARD_DAMAGE: u8 = 1;
+const HAZARD_KNOCKBACK: (f32, f32) = (2.5, -4.0);  // (vx away from the hazard, vy)
+const HAZARD_INVULN: u8 = 60;
+const ICE_FRICTION: f32 = 0.15;  // Scales ground accel/decel (and enemy turning) on ice
+
+// Fire jets: dormant, then sputter (telegraph), then burn at the end of each cycle
+const FIRE_TELEGRAPH_FRAMES: u32 = 45;
+const FIRE_BURN_FRAMES: u32 = 60;
+
+struct HazardRegion {
+    level: u8,
+    kind: u8,  // HAZARD_*
+    x: f32,
+    y: f32,
+    w: f32,
+    h: f32,
+    param: f32,
+}
 
-            // Life powerup state
-            life_position: (0.0, 0.0),
-            life_active: false,
-            life_collected: false,
-            life_door_index: 2,  // Door #3 (index 2) in level 3
+const LEVEL_HAZARDS: [HazardRegion; 5] = [
+    // Spike strip on the ground floor, right of the center patrols
+    HazardRegion { level: 1, kind: HAZARD_SPIKES, x: 780.0, y: 218.0, w: 24.0, h: 6.0, param: 0.0 },
+    // Frozen middle floor on the right
+    HazardRegion { level: 1, kind: HAZARD_ICE, x: 820.0, y: 152.0, w: 180.0, h: 2.0, param: 0.0 },
+    // Conveyor on the big top platform, pushes left
+    HazardRegion { level: 1, kind: HAZARD_CONVEYOR, x: 560.0, y: 64.0, w: 120.0, h: 3.0, param: -0.6 },
+    // Chimney fire on the rooftop
+    HazardRegion { level: 2, kind: HAZARD_FIRE, x: 770.0, y: 40.0, w: 12.0, h: 48.0, param: 180.0 },
+    HazardRegion { level: 2, kind: HAZARD_FIRE, x: 1100.0, y: 40.0, w: 12.0, h: 48.0, param: 240.0 },
+];
 
-            // PowerUp1 state
-            powerup1_position: (0.0, 0.0),
-            powerup1_active: false,
-            powerup1_collected: false,
-            has_firepower: false,
+// ============================================================================
+// PHYSICS VOLUMES
+// ============================================================================
+// Regions that swap Santa's movement constants (enemies use walk_speed and push_x).
+// Where volumes overlap the last one wins, wind adds its push on top of the rest.
+const VOLUME_SNOWDRIFT: u8 = 0;
+const VOLUME_WATER: u8 = 1;
+const VOLUME_WIND: u8 = 2;  // param = push in px/frame (negative = left)
+
+const BREATH_FRAMES: u16 = 480;       // 8 seconds under water
+const BREATH_REFILL: u16 = 4;         // Per frame with Santa's head above water
+const DROWN_DAMAGE_INTERVAL: u16 = 60;
+
+#[derive(Clone, Copy)]
+struct Physics {
+    gravity: f32,
+    terminal_vel: f32,
+    walk_speed: f32,
+    jump_vel: f32,
+    push_x: f32,  // Added to x every frame
+    swim: bool,   // Jump works in mid-water as a stroke
+}
 
-        // Boss fight
-        boss_active: false,
-        boss_defeated: false,
-        use_boss_santa: false,
-        evil_santa_x: 0.0,
-        evil_santa_y: 0.0,
-        evil_santa_facing_right: false,
-        evil_santa_state: BOSS_STATE_IDLE,
-        evil_santa_anim_frame: 0,
-        evil_santa_anim_timer: 0,
-        evil_santa_hp: 0,
-        evil_santa_max_hp: 8,
-        evil_santa_vx: 0.0,
-        evil_santa_vy: 0.0,
-        evil_santa_on_ground: true,
-        evil_santa_attack_cooldown: 0,
-        evil_santa_attack_frame: 0,
-        evil_santa_jump_cooldown: 0,
-        // AI FSM init
-        boss_state_timer: 0,
-        boss_pattern_index: 0,
-        boss_phase: 0,
-        boss_phase_timer: 0,
-        boss_attack_type: 0,
-        evil_santa_flash_timer: 0,
-        player_idle_x: 0.0,
-        player_idle_timer: 0,
-        boss_death_timer: 0,
-        };
+const DEFAULT_PHYSICS: Physics = Physics {
+    gravity: GRAVITY,
+    terminal_vel: TERMINAL_VEL,
+    walk_speed: WALK_SPEED,
+    jump_vel: JUMP_VEL,
+    push_x: 0.0,
+    swim: false,
+};
+
+// Indexed by VOLUME_* (wind keeps the surrounding physics)
+const VOLUME_PHYSICS: [Physics; 3] = [
+    // Snowdrift: wading pace, short hops
+    Physics { walk_speed: 0.7, jump_vel: -4.0, ..DEFAULT_PHYSICS },
+    // Icy water: floaty sink, slow strokes
+    Physics { gravity: 0.12, terminal_vel: 1.2, walk_speed: 0.9, jump_vel: -2.5, push_x: 0.0, swim: true },
+    DEFAULT_PHYSICS,
+];
+
+struct Volume {
+    level: u8,
+    kind: u8,  // VOLUME_*
+    x: f32,
+    y: f32,
+    w: f32,
+    h: f32,
+    param: f32,
+}
 
-        game.load_level(1);
-        game
+const LEVEL_VOLUMES: [Volume; 3] = [
+    // Drift piled on the top-right floor
+    Volume { level: 1, kind: VOLUME_SNOWDRIFT, x: 940.0, y: 60.0, w: 95.0, h: 26.0, param: 0.0 },
+    // Flooded stretch of the ground floor around the plate
+    Volume { level: 1, kind: VOLUME_WATER, x: 850.0, y: 178.0, w: 100.0, h: 46.0, param: 0.0 },
+    // Headwind on the rooftop route
+    Volume { level: 2, kind: VOLUME_WIND, x: 1160.0, y: 0.0, w: 200.0, h: 88.0, param: -0.3 },
+];
+
+// ============================================================================
+// MOVEMENT PROFILES
+// ============================================================================
+// How Santa's run and jump feel. Volumes (above) decide the raw speeds, the
+// profile decides how he gets to them. Profiles are data (data/movement_profiles.json),
+// the first one is the default. Dev mode cycles them with M and tunes the current
+// one live: [ and ] pick a value, - and = change it.
+const MOVEMENT_PROFILES_JSON: &str = include_str!("../data/movement_profiles.json");
+
+#[turbo::serialize]
+struct MovementProfile {
+    name: String,
+    accel: f32,            // Ground speed gained per frame toward the target
+    decel: f32,            // Ground speed lost per frame with no input
+    air_accel: f32,
+    air_decel: f32,
+    air_speed_scale: f32,  // Airborne top speed relative to ground top speed
+    jump_scale: f32,       // Multiplies the volume's jump_vel
+    jump_cut: f32,         // vy multiplier per frame while rising with X released
+    apex_threshold: f32,   // |vy| below this counts as the top of the arc
+    apex_gravity: f32,     // Gravity scale at the apex (< 1 = hang time)
+    fall_gravity: f32,     // Gravity scale while falling
+    air_jumps: u8,         // Extra jumps before landing (1 = double jump)
+    air_jump_scale: f32,   // Air jump strength relative to a ground jump
+}
+
+#[turbo::serialize]
+struct MovementProfileFile {
+    profiles: Vec<MovementProfile>,
+}
+
+// Values dev mode can tune. Format: (label, step per key press), in MovementProfile field order
+const MOVEMENT_TUNE_FIELDS: [(&str, f32); 12] = [
+    ("accel", 0.02),
+    ("decel", 0.02),
+    ("air_accel", 0.05),
+    ("air_decel", 0.01),
+    ("air_speed_scale", 0.1),
+    ("jump_scale", 0.05),
+    ("jump_cut", 0.05),
+    ("apex_threshold", 0.1),
+    ("apex_gravity", 0.05),
+    ("fall_gravity", 0.05),
+    ("air_jumps", 1.0),
+    ("air_jump_scale", 0.05),
+];
+
+impl MovementProfile {
+    fn list_from_json(json: &str) -> Result<Vec<MovementProfile>, String> {
+        let file: MovementProfileFile = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        if file.profiles.is_empty() {
+            return Err("at least one movement profile is needed".to_string());
+        }
+        for (i, profile) in file.profiles.iter().enumerate() {
+            profile.validate().map_err(|e| format!("profile '{}': {}", profile.name, e))?;
+            if file.profiles[..i].iter().any(|other| other.name == profile.name) {
+                return Err(format!("profile '{}' is defined twice", profile.name));
+            }
+        }
+        Ok(file.profiles)
     }
 
-    pub fn update(&mut self) {
-        self.frame += 1;
-        
-        let kb = keyboard::get();
-        
-        // MENU STATE - two-step flow: starting_page -> controls -> game
-        if self.in_menu {
-            if kb.enter().just_pressed() {
-                // Show controls page
-                self.in_menu = false;
-                self.show_controls = true;
-                log!("Showing controls page");
-            }
-            self.render();  // Render the starting page
-            return;  // Don't process game logic while in menu
-        }
-        
-        // CONTROLS STATE - show controls page, press Enter to start game
-        if self.show_controls {
-            if kb.enter().just_pressed() {
-                self.show_controls = false;
-                self.load_level(1);
-                self.lives = 3;
-                self.score = 0;
-                self.player_hp = self.player_max_hp;
-                log!("Game started from controls page!");
-            }
-            self.render();  // Render the controls page
-            return;  // Don't process game logic while showing controls
-        }
-        
-        // GAME OVER STATE - show game over screen for 3 seconds then restart from level 1
-        if self.show_game_over {
-            self.game_over_timer += 1;
-            if self.game_over_timer >= 180 {  // 3 seconds
-                // Reset game completely and restart from level 1
-                self.show_game_over = false;
-                self.game_over_timer = 0;
-                self.lives = 3;
-                self.score = 0;
-                self.player_hp = self.player_max_hp;
-                self.player_state = STATE_IDLE;
-                self.keys_collected = 0;
-                self.kids_collected = 0;
-                self.gift_bombs = 0;
-                self.boss_active = false;
-                self.boss_defeated = false;
-                self.use_boss_santa = false;
-                self.load_level(1);
-                log!("Game restarted from level 1!");
-            }
-            self.render();  // Continuously render game over screen
-            return;  // Don't process game logic during game over
+    fn validate(&self) -> Result<(), String> {
+        let positive = [
+            ("accel", self.accel),
+            ("decel", self.decel),
+            ("air_accel", self.air_accel),
+            ("air_speed_scale", self.air_speed_scale),
+            ("jump_scale", self.jump_scale),
+            ("apex_gravity", self.apex_gravity),
+            ("fall_gravity", self.fall_gravity),
+        ];
+        if let Some((field, _)) = positive.iter().find(|(_, value)| *value <= 0.0) {
+            return Err(format!("{} must be above 0", field));
         }
-        
-        // VICTORY STATE - show victory screen for 5 seconds then restart from level 1
-        // Triggered when Santa collects kid from door 0 in level 3
-        if self.show_victory {
-            self.game_won_timer += 1;
-            if self.game_won_timer >= 300 {  // 5 seconds
-                // Reset game completely and restart from level 1
-                self.show_victory = false;
-                self.game_won_timer = 0;
-                self.lives = 3;
-                self.score = 0;
-                self.player_hp = self.player_max_hp;
-                self.player_state = STATE_IDLE;
-                self.keys_collected = 0;
-                self.kids_collected = 0;
-                self.gift_bombs = 0;
-                self.boss_active = false;
-                self.boss_defeated = false;
-                self.use_boss_santa = false;
-                self.load_level(1);
-                log!("Victory! Game restarted from level 1!");
-            }
-            self.render();  // Continuously render victory screen
-            return;  // Don't process game logic during victory
+        if self.air_decel < 0.0 || self.apex_threshold < 0.0 {
+            return Err("air_decel and apex_threshold can't be negative".to_string());
         }
-        
-        if !audio::is_playing("bgm") {
-            audio::play("bgm");
+        if self.jump_cut <= 0.0 || self.jump_cut > 1.0 {
+            return Err("jump_cut must be in (0, 1]".to_string());
         }
-        // TIME UP STATE - show time up screen for 3 seconds then restart from level 1
-        if self.show_time_up {
-            self.time_up_timer += 1;
-            if self.time_up_timer >= 180 {  // 3 seconds
-                // Reset game completely and restart from level 1
-                self.show_time_up = false;
-                self.time_up_timer = 0;
-                self.lives = 3;
-                self.score = 0;
-                self.player_hp = self.player_max_hp;
-                self.player_state = STATE_IDLE;
-                self.keys_collected = 0;
-                self.kids_collected = 0;
-                self.gift_bombs = 0;
-                self.boss_active = false;
-                self.boss_defeated = false;
-                self.use_boss_santa = false;
-                self.load_level(1);
-                log!("Game restarted from level 1 after time up!");
-            }
-            self.render();  // Continuously render time up screen
-            return;  // Don't process game logic during time up
+        if self.air_jumps > 0 && self.air_jump_scale <= 0.0 {
+            return Err("air_jump_scale must be above 0 when air_jumps is set".to_string());
         }
-        // Update level timer
-        if self.player_state != STATE_DEAD {
-            self.level_timer += 1;
-            if self.level_timer >= self.level_time_limit {
-                self.show_time_up = true;
-                self.time_up_timer = 0;
-                log!("Time's up! Level failed.");
-                self.render();
+        Ok(())
+    }
+
+    // Dev tuning: nudges field MOVEMENT_TUNE_FIELDS[field] by `steps` steps, keeping the profile valid
+    fn tune(&mut self, field: usize, steps: f32) {
+        let before = self.clone();
+        let delta = MOVEMENT_TUNE_FIELDS[field].1 * steps;
+        let value = match field {
+            0 => &mut self.accel,
+            1 => &mut self.decel,
+            2 => &mut self.air_accel,
+            3 => &mut self.air_decel,
+            4 => &mut self.air_speed_scale,
+            5 => &mut self.jump_scale,
+            6 => &mut self.jump_cut,
+            7 => &mut self.apex_threshold,
+            8 => &mut self.apex_gravity,
+            9 => &mut self.fall_gravity,
+            10 => {
+                self.air_jumps = (self.air_jumps as f32 + delta).max(0.0) as u8;
+                if self.air_jumps > 0 && self.air_jump_scale <= 0.0 {
+                    self.air_jump_scale = 0.85;
+                }
                 return;
-            }
+            },
+            _ => &mut self.air_jump_scale,
+        };
+        // Round away float drift so the logged values can be pasted back into the data file
+        *value = ((*value + delta) * 1000.0).round() / 1000.0;
+        if self.validate().is_err() {
+            *self = before;
         }
+    }
 
-        // Developer mode toggle and stage jump controls (Press . to toggle)
-        if kb.period().just_pressed() {
-            self.dev_mode = !self.dev_mode;
+    fn tune_value(&self, field: usize) -> f32 {
+        match field {
+            0 => self.accel,
+            1 => self.decel,
+            2 => self.air_accel,
+            3 => self.air_decel,
+            4 => self.air_speed_scale,
+            5 => self.jump_scale,
+            6 => self.jump_cut,
+            7 => self.apex_threshold,
+            8 => self.apex_gravity,
+            9 => self.fall_gravity,
+            10 => self.air_jumps as f32,
+            _ => self.air_jump_scale,
         }
-        if self.dev_mode {
-            // Direct stage selection
-            if kb.key_j().just_pressed() { self.load_level(1); }
-            if kb.key_k().just_pressed() { self.load_level(2); }
-            if kb.key_b().just_pressed() { self.load_level(3); }
+    }
+}
 
-            // Cycle stages
-            if kb.key_l().just_pressed() {
-                let next = if self.level >= 3 { 1 } else { self.level + 1 };
-                self.load_level(next);
-            }
-            if kb.key_h().just_pressed() {
-                let prev = if self.level <= 1 { 3 } else { self.level - 1 };
-                self.load_level(prev);
-            }
-        }
+// ============================================================================
+// ROPES / MONKEY BARS
+// ============================================================================
+// Horizontal lines Santa hangs from by his hands. Jump into one (or hold Up)
+// to grab on, Left/Right to move along it, X or Down to let go.
+const HANG_OFFSET: f32 = 17.0;       // Santa's center sits this far below the rope
+const HANG_SPEED: f32 = 1.0;
+const ROPE_GRAB_ABOVE: f32 = 4.0;    // Grab window around the hands (see rope_at)
+const ROPE_GRAB_BELOW: f32 = 6.0;
+
+struct Rope {
+    level: u8,
+    x1: f32,  // Santa can hang anywhere from x1 to x2
+    x2: f32,
+    y: f32,
+}
 
-        if self.player_state != STATE_DEAD {
-            self.handle_input();
-            self.apply_jump_velocity_boost();
-            self.update_player();
-            self.update_camera();
-        }
+const LEVEL_ROPES: [Rope; 2] = [
+    // Over the gap in the top floor on the left
+    Rope { level: 1, x1: 222.0, x2: 283.0, y: 44.0 },
+    // Monkey bars across the rooftop gap
+    Rope { level: 2, x1: 482.0, x2: 744.0, y: 44.0 },
+];
 
-        // Boss arena trigger and simple idle animation ticker
-        if self.level == 3 {
-            if !self.boss_active && !self.boss_defeated && self.player_x >= BOSS_TRIGGER_X && self.player_y >= BOSS_TRIGGER_Y {
-                self.boss_active = true;
-                self.use_boss_santa = true; // switch to bossfight/santa sprite set
+// ============================================================================
+// WEAPONS
+// ============================================================================
+// Santa's shot tiers. powerUp1 moves him up one tier. Like score and lives the
+// tier carries over between levels and deaths and resets on game over.
+const WEAPON_BASIC: u8 = 0;
+const WEAPON_FIREPOWER: u8 = 1;  // Longer range, pierces through enemies
+const WEAPON_SPREAD: u8 = 2;     // Three-way volley
+
+const CHARGE_SHOT_TIME: u16 = 45;         // Frames Z must be held for a charged shot
+const CHARGE_SHOT_GLOW_DELAY: u16 = 10;   // Taps don't flash the charge glow
+const CHARGE_SHOT_DAMAGE_BONUS: u8 = 2;
+const CHARGE_SHOT_PIERCE_BONUS: u8 = 2;
+const CHARGE_SHOT_RANGE_MULT: f32 = 1.5;
+const CHARGE_SHOT_SPEED_MULT: f32 = 1.25;
+
+struct WeaponTier {
+    name: &'static str,
+    speed: f32,
+    range: f32,                 // Distance travelled before the shot fizzles
+    damage: u8,                 // Boss HP per hit (regular enemies always die in one hit)
+    pierce: u8,                 // Enemies a shot passes through before it stops
+    spread_vy: &'static [f32],  // One shot per entry, with this vertical speed
+    max_on_screen: usize,       // A volley only fires if all of its shots fit
+    color: u32,
+}
 
-                // Setup boss fight arena (360x256 frame with single platform)
-                self.platforms.clear();
-                self.platforms.push((10.0, 350.0, 217.0)); // Single platform: x=10, width=340, y=217
+const WEAPON_TIERS: [WeaponTier; 3] = [
+    WeaponTier { name: "BASIC", speed: 4.0, range: 5.0, damage: 2, pierce: 0, spread_vy: &[0.0], max_on_screen: 2, color: 0xff0000ff },
+    WeaponTier { name: "FIRE", speed: 5.0, range: 160.0, damage: 2, pierce: 2, spread_vy: &[0.0], max_on_screen: 2, color: 0xff8800ff },
+    WeaponTier { name: "SPREAD", speed: 4.5, range: 110.0, damage: 1, pierce: 0, spread_vy: &[-0.9, 0.0, 0.9], max_on_screen: 3, color: 0x66ccffff },
+];
+
+// A shot in flight, Santa's or the boss's
+#[turbo::serialize]
+#[derive(Copy)]
+struct Projectile {
+    active: bool,
+    x: f32,
+    y: f32,
+    vx: f32,
+    vy: f32,
+    from_boss: bool,
+    distance: f32,      // Travelled so far
+    max_distance: f32,  // Fizzles past this
+    damage: u8,
+    pierce_left: u8,
+    charged: bool,
+    color: u32,         // Tier color at the time it was fired
+}
 
-                // Disable all enemies during boss fight
-                for enemy in self.enemies.iter_mut() {
-                    enemy.8 = false; // Set active to false
-                }
+impl Projectile {
+    const EMPTY: Projectile = Projectile {
+        active: false,
+        x: 0.0,
+        y: 0.0,
+        vx: 0.0,
+        vy: 0.0,
+        from_boss: false,
+        distance: 0.0,
+        max_distance: 0.0,
+        damage: 0,
+        pierce_left: 0,
+        charged: false,
+        color: 0xffffffff,
+    };
+}
 
-                // Add walls at corners to prevent falling off
-                self.walls[0] = (0.0, 0.0, 10.0, 256.0);    // Left wall
-                self.walls[1] = (350.0, 0.0, 10.0, 256.0);  // Right wall
-                // Clear other walls
-                for i in 2..self.walls.len() {
-                    self.walls[i] = (0.0, 0.0, 0.0, 0.0);
-                }
+// ============================================================================
+// MELEE (candy-cane bonk)
+// ============================================================================
+// Z swings instead of shooting when an enemy is close or Shift is held.
+// Reuses attack_frame/attack_cooldown and the Santa/attack sprites.
+const MELEE_FRAMES: u8 = 20;         // Whole swing (5 attack sprites x 4 frames)
+const MELEE_HIT_START: u8 = 6;       // Hitbox is live from this frame...
+const MELEE_HIT_END: u8 = 12;        // ...up to (not including) this one
+const MELEE_REACH: f32 = 34.0;       // Hitbox reaches this far in front of Santa
+const MELEE_HALF_HEIGHT: f32 = 18.0;
+const MELEE_AUTO_RANGE: f32 = 36.0;  // Enemies closer than this get bonked, contact damage starts at 20
+const MELEE_DAMAGE: u8 = 3;          // Boss damage, regular enemies die in one hit
+const MELEE_KNOCKBACK: f32 = 3.0;
+const MELEE_COOLDOWN: u8 = 16;
+const MELEE_SCORE: u32 = 120;
 
-                // Reset player position for boss fight arena
-                self.player_x = 50.0;
-                self.player_y = 169.0; // y=217-48 (48px sprite height)
-                self.player_vx = 0.0;
-                self.player_vy = 0.0;
-                self.player_on_ground = true;
+// ============================================================================
+// BOSS ATTACK PATTERNS (data/boss_patterns.json, data/giant_kickmouse.json)
+// ============================================================================
+const BOSS_PATTERNS_JSON: &str = include_str!("../data/boss_patterns.json");
+const GIANT_KICKMOUSE_PATTERNS_JSON: &str = include_str!("../data/giant_kickmouse.json");
+
+const PLAYER_IDLE_FRAMES: u32 = 90;  // "player_idle" rules match after 1.5s standing still
+
+// One attack's tuning. Durations are in frames and get shortened in later fight phases.
+#[turbo::serialize]
+struct BossAttackDef {
+    name: String,
+    #[serde(default)]
+    id: u8,             // Resolved from `name` when the pattern is loaded
+    windup: u32,
+    active: u32,
+    recovery: u32,
+    #[serde(default)]
+    damage: u8,
+    #[serde(default)]
+    damage_max_hp_ratio: f32, // Scales with Santa's max HP (workshop upgrades), damage is the floor
+    #[serde(default)]
+    hitbox_w: f32,      // Half-extents around the boss/hazard, like the old dx/dy checks
+    #[serde(default)]
+    hitbox_h: f32,
+    #[serde(default)]
+    velocity_x: f32,
+    #[serde(default)]
+    velocity_y: f32,
+    #[serde(default)]
+    knockback_x: f32,
+    #[serde(default)]
+    knockback_y: f32,
+    #[serde(default)]
+    spawn_frames: Vec<u32>,   // Projectile: active frames that fire a shot
+    #[serde(default)]
+    spawn_interval: u32,      // Coal rain: frames between lumps
+    #[serde(default)]
+    count: u8,                // Sack summon: minions per summon
+}
 
-                // Initialize boss entity
-                self.evil_santa_x = BOSS_EVIL_SANTA_X;
-                self.evil_santa_y = BOSS_EVIL_SANTA_Y;
-                self.evil_santa_state = BOSS_STATE_IDLE;
-                self.evil_santa_anim_frame = 0;
-                self.evil_santa_anim_timer = 0;
-                self.evil_santa_facing_right = false;
-                // Initialize boss combat state
-                self.evil_santa_hp = self.evil_santa_max_hp;
-                self.evil_santa_vx = 0.0;
-                self.evil_santa_vy = 0.0;
-                self.evil_santa_on_ground = true;
-                self.evil_santa_attack_cooldown = 60; // short delay before first attack
-                self.evil_santa_attack_frame = 0;
-                self.evil_santa_jump_cooldown = 90;
-            }
+// Weighted selection rule. Every rule whose conditions hold adds its weight to `attack`.
+// dx is horizontal distance, dy is player_y - boss_y (negative when Santa is above).
+#[turbo::serialize]
+struct BossSelectRule {
+    attack: String,
+    #[serde(default)]
+    id: u8,
+    weight: u32,
+    min_dx: Option<f32>,
+    max_dx: Option<f32>,
+    min_dy: Option<f32>,
+    max_dy: Option<f32>,
+    min_hp: Option<f32>,   // Boss HP ratio 0.0-1.0
+    max_hp: Option<f32>,
+    near_wall: Option<bool>,
+    player_idle: Option<bool>,
+}
 
-        if self.boss_active {
-            self.update_boss();
-        }
-        }
+#[turbo::serialize]
+struct BossPattern {
+    phase_attacks: Vec<Vec<String>>,  // Attack names unlocked in fight phase 1, 2, 3
+    attacks: Vec<BossAttackDef>,
+    rules: Vec<BossSelectRule>,
+    #[serde(default)]
+    phase_ids: Vec<Vec<u8>>,
+}
 
-        self.update_enemies();
-        self.check_enemy_collisions();  // Check for damage from enemies
-        self.update_projectiles();
-        self.update_snowballs();  // Update penguin snowball projectiles
-        self.update_placed_bombs();
+impl BossPattern {
+    fn from_json(json: &str) -> Result<BossPattern, String> {
+        let mut pattern: BossPattern = serde_json::from_str(json).map_err(|e| e.to_string())?;
 
-        if self.timer > 0 {
-            self.timer -= 1;
-        } else {
-            self.player_hp = 0;
+        for attack in pattern.attacks.iter_mut() {
+            attack.id = Self::attack_id(&attack.name)?;
         }
-        
-        // Check for death and handle respawn/game over
-        if self.player_hp == 0 && self.player_state != STATE_DEAD {
-            self.handle_death();
+        for rule in pattern.rules.iter_mut() {
+            rule.id = Self::attack_id(&rule.attack)?;
+        }
+        pattern.phase_ids = Vec::new();
+        for names in pattern.phase_attacks.iter() {
+            let ids = names.iter().map(|n| Self::attack_id(n)).collect::<Result<Vec<u8>, String>>()?;
+            if ids.is_empty() {
+                return Err("every fight phase needs at least one attack".to_string());
+            }
+            pattern.phase_ids.push(ids);
+        }
+        if pattern.phase_ids.len() != BOSS_FIGHT_PHASE_3 as usize {
+            return Err(format!("expected {} fight phases, got {}", BOSS_FIGHT_PHASE_3, pattern.phase_ids.len()));
+        }
+        for ids in pattern.phase_ids.iter() {
+            for id in ids {
+                if !pattern.attacks.iter().any(|a| a.id == *id) {
+                    return Err(format!("attack {} is used but has no definition", id));
+                }
+            }
         }
+        Ok(pattern)
+    }
 
-        self.update_keys_animation();
-        self.check_keys();
-        self.check_gift_bomb_pickups();
+    fn attack_id(name: &str) -> Result<u8, String> {
+        match name {
+            "dash" => Ok(ATTACK_DASH),
+            "slam" => Ok(ATTACK_SLAM),
+            "projectile" => Ok(ATTACK_PROJECTILE),
+            "sack_summon" => Ok(ATTACK_SACK_SUMMON),
+            "coal_rain" => Ok(ATTACK_COAL_RAIN),
+            "shockwave" => Ok(ATTACK_SHOCKWAVE),
+            _ => Err(format!("unknown boss attack '{}'", name)),
+        }
+    }
 
-        // Update and check kids
-        self.update_kids_animation();
-        self.check_kid_collection();
-        self.check_life_collection();  // Check life powerup collection
-        self.check_powerup1_collection();  // Check powerup1 collection
+    fn attack(&self, id: u8) -> BossAttackDef {
+        self.attacks.iter().find(|a| a.id == id).cloned().unwrap_or_else(|| self.attacks[0].clone())
+    }
 
-        // Check for level completion
-        if !self.level_complete {
-            self.check_level_completion();
+    fn phase_pool(&self, fight_phase: u8) -> &[u8] {
+        let idx = (fight_phase.max(1) as usize - 1).min(self.phase_ids.len() - 1);
+        &self.phase_ids[idx]
+    }
+
+    // Sums rule weights per attack, only for attacks unlocked in this fight phase,
+    // then picks one with `roll` (any u32, taken modulo the total weight)
+    fn pick_attack(&self, fight_phase: u8, situation: &BossSituation, roll: u32) -> u8 {
+        let pool = self.phase_pool(fight_phase);
+        let mut weights: Vec<(u8, u32)> = pool.iter().map(|id| (*id, 0)).collect();
+        for rule in self.rules.iter() {
+            if rule.id == ATTACK_SACK_SUMMON && situation.minions_full { continue; }
+            if !rule.matches(situation) { continue; }
+            if let Some(entry) = weights.iter_mut().find(|(id, _)| *id == rule.id) {
+                entry.1 += rule.weight;
+            }
         }
 
-        // Handle level transition
-        if self.level_complete {
-            if self.level_transition_timer > 0 {
-                self.level_transition_timer -= 1;
-            } else {
-                // Transition to next level
-                let next_level = self.level + 1;
-                self.load_level(next_level);
+        let total: u32 = weights.iter().map(|(_, w)| *w).sum();
+        if total == 0 {
+            // No rule applies here - fall back to the first attack of the phase
+            return pool[0];
+        }
+        let mut roll = roll % total;
+        for (id, weight) in weights.iter() {
+            if roll < *weight {
+                return *id;
             }
+            roll -= *weight;
         }
+        pool[0]
+    }
+}
 
-        self.render();
+impl BossAttackDef {
+    fn damage_for(&self, player_max_hp: u8) -> u8 {
+        let scaled = (player_max_hp as f32 * self.damage_max_hp_ratio).ceil() as u8;
+        self.damage.max(scaled)
     }
+}
 
-    fn restart_level(&mut self) {
-        // Reset player health
-        self.player_hp = self.player_max_hp;
-        self.player_state = STATE_IDLE;
-        self.player_invuln_timer = 0;
+// What the selection rules look at when the boss picks its next attack
+struct BossSituation {
+    dx: f32,
+    dy: f32,
+    hp_ratio: f32,
+    near_wall: bool,
+    player_idle: bool,
+    minions_full: bool,  // Sack summon rules are skipped at BOSS_MAX_MINIONS
+}
 
-        // Reload current level
-        self.load_level(self.level);
+impl BossSelectRule {
+    fn matches(&self, at: &BossSituation) -> bool {
+        self.min_dx.is_none_or(|v| at.dx >= v)
+            && self.max_dx.is_none_or(|v| at.dx < v)
+            && self.min_dy.is_none_or(|v| at.dy >= v)
+            && self.max_dy.is_none_or(|v| at.dy < v)
+            && self.min_hp.is_none_or(|v| at.hp_ratio >= v)
+            && self.max_hp.is_none_or(|v| at.hp_ratio <= v)
+            && self.near_wall.is_none_or(|v| at.near_wall == v)
+            && self.player_idle.is_none_or(|v| at.player_idle == v)
     }
+}
 
-    fn handle_death(&mut self) {
-        // Check if player has lives remaining
-        if self.lives > 0 {
-            // Lose a life
-            self.lives -= 1;
+// ============================================================================
+// BOSSES
+// ============================================================================
+// Every boss runs the same FSM (BOSS_STATE_*) and attack runner (BossPattern).
+// A BossDef describes where the fight happens and how the boss looks; the
+// per-boss hooks (boss_on_intro/phase/defeat/exit) cover everything else.
+const BOSS_EVIL_SANTA: u8 = 1;
+const BOSS_GIANT_KICKMOUSE: u8 = 2;
+
+struct BossSprites {
+    idle: &'static [&'static str],
+    walk: &'static [&'static str],
+    jump: &'static [&'static str],
+    attack: &'static [&'static str],
+    fall: &'static str,
+    defeated: &'static str,
+    offset: (i32, i32),  // Draw offset from the boss position
+    scale: f32,
+}
 
-            // Respawn at starting position based on current level
-            match self.level {
-                1 => {
-                    self.player_x = 145.0;  // Level 1 starting X
-                    self.player_y = 72.0;   // Level 1 starting Y
-                },
-                2 => {
-                    self.player_x = 60.0;   // Level 2 starting X
-                    self.player_y = 58.0;   // Level 2 starting Y (86 - 28)
-                },
-                3 => {
-                    // If boss fight has started, respawn in boss arena
-                    if self.boss_active {
-                        self.player_x = 50.0;  // Boss arena starting X
-                        self.player_y = 169.0; // Boss arena starting Y (y=217-48)
-                        // Keep using 48x48 boss sprites while in boss fight
-                        self.use_boss_santa = true;
-                    } else {
-                        self.player_x = 150.0;  // Boss level starting X
-                        self.player_y = 194.0;  // Boss level starting Y (222 - 28)
-                    }
-                },
-                _ => {
-                    self.player_x = 145.0;
-                    self.player_y = 72.0;
-                }
-            }
+struct BossDef {
+    kind: u8,
+    name: &'static str,
+    level: u8,
+    trigger: (f32, f32, f32, f32),  // x, y, width, height in level coordinates
+    own_screen: bool,               // Arena replaces the level (own background, floor and walls)
+    background: &'static str,       // Only drawn for own_screen arenas
+    arena: (f32, f32, f32),         // left, right, floor_y
+    camera_x: f32,                  // Camera is locked here for the whole fight
+    player_start: (f32, f32),       // Teleport target for own_screen arenas, respawn point for all
+    boss_start: (f32, f32),         // boss_start.1 is also the boss ground line
+    big_santa: bool,                // Swap Santa to the 48x48 bossfight/santa sprites
+    max_hp: u8,
+    hitbox: (f32, f32),             // Half extents used for Santa's shots
+    walk_speed: f32,
+    score: u32,                     // Awarded on defeat
+    roar: &'static str,             // Shown during phase transitions
+    intro_cutscene: u8,             // CUTSCENE_* played when the fight starts
+    defeat_cutscene: u8,            // CUTSCENE_* played on defeat, must end with CUTSCENE_ACTION_EXIT_BOSS
+    pattern_json: &'static str,
+    sprites: BossSprites,
+    hurt_cue: Option<u8>,           // CUE_* voice lines, None = silent
+    warning_cue: Option<u8>,
+    roar_cue: Option<u8>,
+}
 
-            self.player_vx = 0.0;
-            self.player_vy = 0.0;
+const EVIL_SANTA_SPRITES: BossSprites = BossSprites {
+    idle: &["bossfight/evilsanta/idle/1", "bossfight/evilsanta/idle/2", "bossfight/evilsanta/idle/3", "bossfight/evilsanta/idle/4"],
+    walk: &[
+        "bossfight/evilsanta/walk/1", "bossfight/evilsanta/walk/2", "bossfight/evilsanta/walk/3", "bossfight/evilsanta/walk/4",
+        "bossfight/evilsanta/walk/5", "bossfight/evilsanta/walk/6", "bossfight/evilsanta/walk/7", "bossfight/evilsanta/walk/8",
+        "bossfight/evilsanta/walk/9", "bossfight/evilsanta/walk/10",
+    ],
+    jump: &[
+        "bossfight/evilsanta/jump/1", "bossfight/evilsanta/jump/2", "bossfight/evilsanta/jump/3",
+        "bossfight/evilsanta/jump/4", "bossfight/evilsanta/jump/5", "bossfight/evilsanta/jump/6",
+    ],
+    attack: &[
+        "bossfight/evilsanta/attack/1", "bossfight/evilsanta/attack/2", "bossfight/evilsanta/attack/3",
+        "bossfight/evilsanta/attack/4", "bossfight/evilsanta/attack/5", "bossfight/evilsanta/attack/6",
+    ],
+    fall: "bossfight/evilsanta/evilsantafall",
+    defeated: "bossfight/evilsanta/defeated",
+    offset: (-16, -16),
+    scale: 1.0,
+};
+
+// Kickmouse sprites drawn at double size
+const GIANT_KICKMOUSE_SPRITES: BossSprites = BossSprites {
+    idle: &["enemy/kickmouse/1", "enemy/kickmouse/2"],
+    walk: &[
+        "enemy/kickmouse/1", "enemy/kickmouse/2", "enemy/kickmouse/3", "enemy/kickmouse/4",
+        "enemy/kickmouse/5", "enemy/kickmouse/6", "enemy/kickmouse/7", "enemy/kickmouse/8",
+    ],
+    jump: &["enemy/kickmouse/3"],
+    attack: &["enemy/kickmouse/attack/1", "enemy/kickmouse/attack/2", "enemy/kickmouse/attack/3"],
+    fall: "enemy/kickmouse/5",
+    defeated: "cloud/cloud5",
+    offset: (-32, -32),
+    scale: 2.0,
+};
+
+const BOSS_DEFS: [BossDef; 3] = [
+    BossDef {
+        kind: BOSS_EVIL_SANTA,
+        name: "EVIL SANTA",
+        level: 3,
+        trigger: (1860.0, 164.0, 300.0, 100.0),
+        own_screen: true,
+        background: "bossfight/background",
+        arena: (BOSS_ARENA_LEFT, BOSS_ARENA_RIGHT, BOSS_ARENA_FLOOR_Y),
+        camera_x: 0.0,
+        player_start: (50.0, 169.0),  // y=217-48 (48px sprite height)
+        boss_start: (BOSS_EVIL_SANTA_X, BOSS_EVIL_SANTA_Y),
+        big_santa: true,
+        max_hp: 8,
+        hitbox: (20.0, 20.0),
+        walk_speed: EVIL_WALK_SPEED,
+        score: 5000,
+        roar: "ROAR!",
+        intro_cutscene: CUTSCENE_EVIL_SANTA_INTRO,
+        defeat_cutscene: CUTSCENE_EVIL_SANTA_DEFEAT,
+        pattern_json: BOSS_PATTERNS_JSON,
+        sprites: EVIL_SANTA_SPRITES,
+        hurt_cue: Some(CUE_EVIL_SANTA_HURT),
+        warning_cue: Some(CUE_EVIL_SANTA_WARNING),
+        roar_cue: Some(CUE_EVIL_SANTA_ROAR),
+    },
+    // Mid-boss on the level 1 ground floor, between the center wall and the plate gate
+    // (walls[8] at x=960 stays shut during the fight, so the arena ends in front of it)
+    BossDef {
+        kind: BOSS_GIANT_KICKMOUSE,
+        name: "GIANT KICKMOUSE",
+        level: 1,
+        trigger: (700.0, 180.0, 250.0, 60.0),
+        own_screen: false,
+        background: "",
+        arena: (690.0, 950.0, 224.0),
+        camera_x: 690.0,
+        player_start: (720.0, 205.0),
+        boss_start: (910.0, 192.0),
+        big_santa: false,
+        max_hp: 6,
+        hitbox: (24.0, 28.0),
+        walk_speed: 1.0,
+        score: 1500,
+        roar: "SQUEAK!",
+        intro_cutscene: CUTSCENE_KICKMOUSE_INTRO,
+        defeat_cutscene: CUTSCENE_KICKMOUSE_DEFEAT,
+        pattern_json: GIANT_KICKMOUSE_PATTERNS_JSON,
+        sprites: GIANT_KICKMOUSE_SPRITES,
+        hurt_cue: None,
+        warning_cue: None,
+        roar_cue: None,
+    },
+    // Mid-boss on the level 2 ground floor, right section
+    BossDef {
+        kind: BOSS_GIANT_KICKMOUSE,
+        name: "GIANT KICKMOUSE",
+        level: 2,
+        trigger: (1040.0, 180.0, 345.0, 60.0),
+        own_screen: false,
+        background: "",
+        arena: (1040.0, 1390.0, 225.0),
+        camera_x: 1040.0,
+        player_start: (1060.0, 206.0),
+        boss_start: (1320.0, 193.0),
+        big_santa: false,
+        max_hp: 8,
+        hitbox: (24.0, 28.0),
+        walk_speed: 1.2,
+        score: 2000,
+        roar: "SQUEAK!",
+        intro_cutscene: CUTSCENE_KICKMOUSE_INTRO,
+        defeat_cutscene: CUTSCENE_KICKMOUSE_DEFEAT,
+        pattern_json: GIANT_KICKMOUSE_PATTERNS_JSON,
+        sprites: GIANT_KICKMOUSE_SPRITES,
+        hurt_cue: None,
+        warning_cue: None,
+        roar_cue: None,
+    },
+];
 
-            // Reset health and state
-            self.player_hp = self.player_max_hp;
-            self.player_state = STATE_IDLE;
-            self.player_on_ground = true;
+// ============================================================================
+// CUTSCENES
+// ============================================================================
+// Scripted sequences run one step at a time. Input is locked while a script
+// runs (X/Z advances dialogue, ENTER skips the rest).
+const CUTSCENE_NONE: u8 = 0;
+const CUTSCENE_EVIL_SANTA_INTRO: u8 = 1;
+const CUTSCENE_EVIL_SANTA_DEFEAT: u8 = 2;
+const CUTSCENE_KICKMOUSE_INTRO: u8 = 3;
+const CUTSCENE_KICKMOUSE_DEFEAT: u8 = 4;
+const CUTSCENE_LEVEL_1_INTRO: u8 = 5;
+const CUTSCENE_LEVEL_2_INTRO: u8 = 6;
+const CUTSCENE_LEVEL_3_INTRO: u8 = 7;
+const CUTSCENE_ENDING: u8 = 8;
+
+const ACTOR_SANTA: u8 = 0;
+const ACTOR_BOSS: u8 = 1;
+
+const CUTSCENE_ACTION_BOSS_ROAR: u8 = 0;  // Boss roars, the fight starts when the roar ends
+const CUTSCENE_ACTION_EXIT_BOSS: u8 = 1;  // Leave the arena (exit_boss_fight)
+const CUTSCENE_ACTION_VICTORY: u8 = 2;    // Show the game won screen
+
+const CUTSCENE_TEXT_SPEED: u16 = 2;       // Frames per typed character
+const CUTSCENE_LINE_CHARS: usize = 44;    // Dialogue box wraps at this width
+const CUTSCENE_SKIP_DELAY: u16 = 30;      // ENTER that just confirmed a menu doesn't also skip
+
+// Portraits: (sprite, scale to fill the 48x48 frame)
+const PORTRAIT_SANTA: (&str, f32) = ("Santa/idle/1_32", 1.5);
+const PORTRAIT_EVIL_SANTA: (&str, f32) = ("bossfight/evilsanta/idle/1", 1.0);
+const PORTRAIT_KICKMOUSE: (&str, f32) = ("enemy/kickmouse/1", 1.5);
+const PORTRAIT_KID: (&str, f32) = ("kid/1", 2.4);
+
+#[derive(Clone, Copy)]
+enum CutsceneStep {
+    Wait(u16),
+    FadeOut(u16),
+    FadeIn(u16),
+    CameraTo(f32, u16),   // Pan to a camera_x over N frames
+    CameraArena(u16),     // Pan to the current boss arena
+    CameraFollow(u16),    // Pan back to Santa and hand the camera back
+    Walk(u8, f32),        // ACTOR_* walks to x
+    Say(&'static str, (&'static str, f32), &'static str),  // speaker, portrait, text
+    Action(u8),           // CUTSCENE_ACTION_*
+}
 
-            // Grant 3 seconds of invulnerability after respawn
-            self.player_invuln_timer = 180;
+const EVIL_SANTA_INTRO_SCRIPT: &[CutsceneStep] = &[
+    CutsceneStep::FadeIn(40),
+    CutsceneStep::Walk(ACTOR_SANTA, 50.0),
+    CutsceneStep::Walk(ACTOR_BOSS, BOSS_EVIL_SANTA_X),
+    CutsceneStep::Wait(20),
+    CutsceneStep::Say("EVIL SANTA", PORTRAIT_EVIL_SANTA, "So the real Santa finally shows up. Those kids are staying in my workshop!"),
+    CutsceneStep::Say("SANTA", PORTRAIT_SANTA, "Ho ho... no. They're going home for Christmas."),
+    CutsceneStep::Action(CUTSCENE_ACTION_BOSS_ROAR),
+];
+
+const EVIL_SANTA_DEFEAT_SCRIPT: &[CutsceneStep] = &[
+    CutsceneStep::Wait(60),
+    CutsceneStep::Say("EVIL SANTA", PORTRAIT_EVIL_SANTA, "Nooo! My sack of stolen toys..."),
+    CutsceneStep::Say("SANTA", PORTRAIT_SANTA, "Those toys belong under the trees. Now, where are the kids?"),
+    CutsceneStep::FadeOut(40),
+    CutsceneStep::Action(CUTSCENE_ACTION_EXIT_BOSS),
+    CutsceneStep::FadeIn(40),
+];
+
+const KICKMOUSE_INTRO_SCRIPT: &[CutsceneStep] = &[
+    CutsceneStep::CameraArena(45),
+    CutsceneStep::Wait(30),
+    CutsceneStep::Say("GIANT KICKMOUSE", PORTRAIT_KICKMOUSE, "SQUEAK! Nobody gets past my floor!"),
+    CutsceneStep::Action(CUTSCENE_ACTION_BOSS_ROAR),
+];
+
+const KICKMOUSE_DEFEAT_SCRIPT: &[CutsceneStep] = &[
+    CutsceneStep::Wait(50),
+    CutsceneStep::Say("GIANT KICKMOUSE", PORTRAIT_KICKMOUSE, "Squeak... my tail..."),
+    CutsceneStep::Action(CUTSCENE_ACTION_EXIT_BOSS),
+];
+
+const LEVEL_1_INTRO_SCRIPT: &[CutsceneStep] = &[
+    CutsceneStep::Say("SANTA", PORTRAIT_SANTA, "Those mice locked the kids behind the doors! Gift bombs should open them up."),
+    CutsceneStep::CameraTo(720.0, 150),
+    CutsceneStep::Wait(30),
+    CutsceneStep::CameraFollow(90),
+];
+
+const LEVEL_2_INTRO_SCRIPT: &[CutsceneStep] = &[
+    CutsceneStep::Say("SANTA", PORTRAIT_SANTA, "More doors, more kids. Keep going, old man!"),
+    CutsceneStep::CameraTo(1080.0, 180),
+    CutsceneStep::Wait(30),
+    CutsceneStep::CameraFollow(120),
+];
+
+const LEVEL_3_INTRO_SCRIPT: &[CutsceneStep] = &[
+    CutsceneStep::Say("SANTA", PORTRAIT_SANTA, "This is it, the evil workshop. Whoever is behind this is waiting at the end."),
+];
+
+const ENDING_SCRIPT: &[CutsceneStep] = &[
+    CutsceneStep::Say("KID", PORTRAIT_KID, "Santa! You found us! Is it Christmas yet?"),
+    CutsceneStep::Say("SANTA", PORTRAIT_SANTA, "Ho ho ho! It is now. Merry Christmas, everyone!"),
+    CutsceneStep::FadeOut(60),
+    CutsceneStep::Action(CUTSCENE_ACTION_VICTORY),
+];
+
+fn cutscene_script(id: u8) -> &'static [CutsceneStep] {
+    match id {
+        CUTSCENE_EVIL_SANTA_INTRO => EVIL_SANTA_INTRO_SCRIPT,
+        CUTSCENE_EVIL_SANTA_DEFEAT => EVIL_SANTA_DEFEAT_SCRIPT,
+        CUTSCENE_KICKMOUSE_INTRO => KICKMOUSE_INTRO_SCRIPT,
+        CUTSCENE_KICKMOUSE_DEFEAT => KICKMOUSE_DEFEAT_SCRIPT,
+        CUTSCENE_LEVEL_1_INTRO => LEVEL_1_INTRO_SCRIPT,
+        CUTSCENE_LEVEL_2_INTRO => LEVEL_2_INTRO_SCRIPT,
+        CUTSCENE_LEVEL_3_INTRO => LEVEL_3_INTRO_SCRIPT,
+        CUTSCENE_ENDING => ENDING_SCRIPT,
+        _ => &[],
+    }
+}
 
-            // Reset animation
-            self.player_anim_frame = 0;
-            self.player_anim_timer = 0;
-        } else {
-            // No lives left AND health is 0 - Game Over!
-            // Trigger game over: lives <= 0 and player_hp <= 0
-            self.show_game_over = true;
-            self.game_over_timer = 0;
-            self.player_state = STATE_DEAD;
-            audio::play("santa_death");
-            log!("Game Over! Lives: {}, HP: {}", self.lives, self.player_hp);
+// Runtime state of whichever boss is currently being fought
+#[turbo::serialize]
+struct BossEntity {
+    x: f32,
+    y: f32,
+    vx: f32,
+    vy: f32,
+    facing_right: bool,
+    on_ground: bool,
+    state: u8,
+    anim_frame: u8,
+    anim_timer: u8,
+    hp: u8,
+    max_hp: u8,
+    flash_timer: u8,
+    // AI FSM
+    state_timer: u32,
+    attack_type: u8,
+    phase: u8,        // PHASE_WINDUP / PHASE_ACTIVE / PHASE_RECOVERY of the current attack
+    phase_timer: u32,
+}
+
+impl BossEntity {
+    fn new() -> Self {
+        Self {
+            x: 0.0,
+            y: 0.0,
+            vx: 0.0,
+            vy: 0.0,
+            facing_right: false,
+            on_ground: true,
+            state: BOSS_STATE_IDLE,
+            anim_frame: 0,
+            anim_timer: 0,
+            hp: 0,
+            max_hp: 0,
+            flash_timer: 0,
+            state_timer: 0,
+            attack_type: 0,
+            phase: 0,
+            phase_timer: 0,
         }
     }
+}
 
-    fn handle_input(&mut self) {
-        let kb = keyboard::get();
+// ============================================================================
+// SAVE DATA
+// ============================================================================
+// Kept in the host's local storage (turbo::local). Bump SAVE_VERSION when the
+// layout changes, older saves are then ignored.
+const SAVE_VERSION: u8 = 4;
+
+// Best result on one level. Same borsh layout as the (stars, score, kids) tuple it replaced
+#[turbo::serialize]
+#[derive(Copy, PartialEq)]
+struct LevelBest {
+    stars: u8,  // 0 = never cleared
+    score: u32,
+    kids: u8,   // Kids rescued
+}
 
-        // Toggle controls panel with 'S'
-        if kb.key_s().just_pressed() {
-            self.show_controls_panel = !self.show_controls_panel;
-        }
+impl LevelBest {
+    const NONE: LevelBest = LevelBest { stars: 0, score: 0, kids: 0 };
+}
 
-        // Don't allow input during attack or hurt states
-        if self.player_state == STATE_ATTACK || self.player_state == STATE_HURT {
-            return;
+#[turbo::serialize]
+struct SaveData {
+    version: u8,
+    tokens: u32,        // Unspent toy tokens
+    upgrades: [u8; 4],  // Times each SHOP_ITEMS entry was bought
+    best: [LevelBest; 3],  // Per level
+    high_scores: [u32; HIGH_SCORE_SLOTS],  // Best finished runs, highest first
+    settings: Settings,
+}
+
+impl SaveData {
+    fn new() -> Self {
+        Self {
+            version: SAVE_VERSION,
+            tokens: 0,
+            upgrades: [0; 4],
+            best: [LevelBest::NONE; 3],
+            high_scores: [0; HIGH_SCORE_SLOTS],
+            settings: Settings::new(),
         }
+    }
+}
 
-        // Get key states - NEW KEY BINDINGS
-        let left = kb.arrow_left().pressed();
-        let right = kb.arrow_right().pressed();
-        let up = kb.arrow_up().pressed();
-        let down = kb.arrow_down().pressed();
-        let jump_pressed = kb.key_x().just_pressed();
-        let jump_held = kb.key_x().pressed();
-        let attack = kb.key_z().just_pressed();  // Z key for attack
-        let place_bomb = kb.key_c().just_pressed();  // C key to place bomb
+// Tallies for the level being played, reset by load_level. The workshop summary reads them.
+#[turbo::serialize]
+struct LevelStats {
+    score_at_start: u32,
+    enemies_defeated: u16,
+    kill_score: u32,
+    door_score: u32,
+    tokens_earned: u32,
+    damage_taken: u16,
+    bombs_used: u16,
+    hp_seen: u8,  // HP at the end of last frame, drops from it count as damage
+}
 
-        // Check if player is near a ladder
-        let on_ladder = self.check_ladder_collision();
+impl LevelStats {
+    fn new(score_at_start: u32, hp: u8) -> Self {
+        Self {
+            score_at_start,
+            enemies_defeated: 0,
+            kill_score: 0,
+            door_score: 0,
+            tokens_earned: 0,
+            damage_taken: 0,
+            bombs_used: 0,
+            hp_seen: hp,
+        }
+    }
+}
 
-        // ==================== CLIMBING LOGIC ====================
-        if on_ladder {
-            // Press UP to start climbing up, or DOWN to start climbing down
-            if (up || down) && !self.player_on_ladder {
-                self.player_on_ladder = true;
-                self.player_state = STATE_CLIMB;
-                self.player_vy = 0.0;
-                self.player_vx = 0.0;
-            }
-        }
-
-        // Handle ladder climbing movement
-        if self.player_on_ladder {
-            // Force exit ladder if standing on solid ground and not pressing up/down
-            if self.player_on_ground && !up && !down {
-                self.player_on_ladder = false;
-                self.player_state = STATE_IDLE;
-                self.player_vy = 0.0;
-                self.player_anim_frame = 0;
-                self.player_anim_timer = 0;
-                // Don't return, let normal movement take over
-            } else {
-                self.player_vx = 0.0;  // No horizontal movement while climbing
+// ============================================================================
+// LEVEL RESULTS
+// ============================================================================
+// One star for clearing a level, one for beating its par time, one for reaching its target score.
+// Format: (level, par time in seconds, target score)
+const LEVEL_STAR_TARGETS: [(u8, u32, u32); 3] = [
+    (1, 120, 4000),
+    (2, 300, 6000),
+    (3, 420, 15000),
+];
 
-                if up {
-                    self.player_vy = -CLIMB_SPEED;
-                    self.player_state = STATE_CLIMB;
-                } else if down {
-                    self.player_vy = CLIMB_SPEED;
-                    self.player_state = STATE_CLIMB;
-                } else {
-                    self.player_vy = 0.0;
-                }
+// Shown on the level select map. A level unlocks once the one before it has been cleared.
+const LEVEL_NAMES: [&str; 3] = ["CHRISTMAS HOUSE", "ROOFTOPS", "BOSS LAIR"];
 
-                // Jump off ladder with X key + left/right
-                if jump_pressed && (left || right) {
-                    self.player_on_ladder = false;
-                    self.player_vy = JUMP_VEL * 0.7;  // Smaller jump when jumping off ladder
-                    self.player_state = STATE_JUMP;
-                    self.jump_anim_frame = 0;
-                    self.jump_anim_timer = 0;
-                    audio::play("jump");
-                    if left {
-                        self.player_facing_right = false;
-                        self.player_vx = -WALK_SPEED;
-                    } else {
-                        self.player_facing_right = true;
-                        self.player_vx = WALK_SPEED;
-                    }
-                }
+// ============================================================================
+// GIFT WORKSHOP
+// ============================================================================
+// Intermission between levels. Toy tokens from kills, doors and bosses buy
+// permanent upgrades: they are saved and applied again at the start of every run.
+const SHOP_MAX_HP: u8 = 0;
+const SHOP_BOMB_SACK: u8 = 1;
+const SHOP_WEAPON: u8 = 2;
+const SHOP_EXTRA_LIFE: u8 = 3;
+
+const TOKENS_PER_KILL: u32 = 1;
+const TOKENS_PER_DOOR: u32 = 3;
+const TOKENS_PER_BOSS: u32 = 25;
+const SHOP_HP_STEP: u8 = 2;
+const PLAYER_START_MAX_HP: u8 = 6;
+const START_LIVES: u8 = 3;
+
+struct ShopItem {
+    name: &'static str,
+    effect: &'static str,
+    base_cost: u32,
+    cost_step: u32,  // Extra cost per purchase
+    max: u8,         // Purchases before it shows MAXED
+}
 
-                // Exit ladder if no longer overlapping
-                if !on_ladder {
-                    self.player_on_ladder = false;
-                }
+// Indexed by SHOP_*
+const SHOP_ITEMS: [ShopItem; 4] = [
+    ShopItem { name: "WARM COAT", effect: "+2 MAX HP", base_cost: 20, cost_step: 15, max: 3 },
+    ShopItem { name: "BIGGER SACK", effect: "+1 BOMB CAPACITY", base_cost: 15, cost_step: 10, max: 3 },
+    ShopItem { name: "TOY BLASTER", effect: "+1 WEAPON TIER", base_cost: 40, cost_step: 40, max: 2 },
+    ShopItem { name: "LUCKY STOCKING", effect: "+1 LIFE", base_cost: 30, cost_step: 30, max: 2 },
+];
 
-                return;  // Skip normal movement while on ladder
-            }
-        }
+// ============================================================================
+// SETTINGS
+// ============================================================================
+// Player options from the settings menu, kept in the save. Volumes go from 0 to
+// VOLUME_STEPS (steps of 10%), music and SFX are scaled by the master volume.
+const VOLUME_STEPS: u8 = 10;
+
+const HUD_TOP: u8 = 0;
+const HUD_BOTTOM: u8 = 1;
+const HUD_COMPACT: u8 = 2;      // Top bar only, see-through, no weapon/item labels
+const HUD_LAYOUT_NAMES: [&str; 3] = ["TOP", "BOTTOM", "COMPACT"];
+
+#[turbo::serialize]
+struct Settings {
+    master_volume: u8,
+    music_volume: u8,
+    sfx_volume: u8,
+    reduced_motion: bool,  // Tones down full-screen flashes, strobing and text shake
+    hud_layout: u8,        // HUD_*
+}
 
-        // ==================== CROUCHING LOGIC ====================
-        if down && self.player_on_ground && !self.player_on_ladder {
-            self.player_is_crouching = true;
-            self.player_state = STATE_CROUCH;
-            // Can still move slowly while crouching
-            let crouch_target = if left && !right {
-                -CROUCH_SPEED
-            } else if right && !left {
-                CROUCH_SPEED
-            } else {
-                0.0
-            };
-            self.player_vx = crouch_target;
-            
-            if left && !right {
-                self.player_facing_right = false;
-            } else if right && !left {
-                self.player_facing_right = true;
-            }
-            return;
-        } else {
-            self.player_is_crouching = false;
+impl Settings {
+    fn new() -> Self {
+        Self {
+            master_volume: VOLUME_STEPS,
+            music_volume: 8,
+            sfx_volume: VOLUME_STEPS,
+            reduced_motion: false,
+            hud_layout: HUD_TOP,
         }
+    }
+}
 
-        // ==================== HORIZONTAL MOVEMENT ====================
-        if left && !right {
-            self.player_facing_right = false;
-        } else if right && !left {
-            self.player_facing_right = true;
-        }
+// ============================================================================
+// AUDIO
+// ============================================================================
+// Every sound goes through a named cue (CUE_*). A cue picks one of its variants
+// at random (not the same one twice in a row), won't play again until its
+// cooldown (frames) runs out, and its category decides volume and mixing:
+//   music   - one looping track at a time, switched by GameState::music_cue
+//   stinger - one-shot jingle that stops the music (level clear, victory, game over)
+//   sfx     - one-shots that may overlap
+//   voice   - Santa, boss and kid lines, a new line cuts off the one still playing
+const CATEGORY_MUSIC: u8 = 0;
+const CATEGORY_STINGER: u8 = 1;
+const CATEGORY_SFX: u8 = 2;
+const CATEGORY_VOICE: u8 = 3;
+
+struct SoundCue {
+    variants: &'static [&'static str],
+    category: u8,
+    cooldown: u16,
+}
 
-        let target_speed = if left && !right {
-            -WALK_SPEED
-        } else if right && !left {
-            WALK_SPEED
-        } else {
-            0.0
-        };
+const CUE_MUSIC_MENU: u8 = 0;
+const CUE_MUSIC_LEVEL_1: u8 = 1;
+const CUE_MUSIC_LEVEL_2: u8 = 2;
+const CUE_MUSIC_LEVEL_3: u8 = 3;
+const CUE_MUSIC_BOSS: u8 = 4;
+const CUE_LEVEL_CLEAR: u8 = 5;
+const CUE_VICTORY: u8 = 6;
+const CUE_GAME_OVER: u8 = 7;
+const CUE_JUMP: u8 = 8;
+const CUE_PICKUP: u8 = 9;
+const CUE_EXPLOSION: u8 = 10;
+const CUE_SANTA_HURT: u8 = 11;
+const CUE_SANTA_KILL: u8 = 12;
+const CUE_KID_RESCUED: u8 = 13;
+// Boss voices, picked per boss in BOSS_DEFS
+const CUE_EVIL_SANTA_HURT: u8 = 14;
+const CUE_EVIL_SANTA_WARNING: u8 = 15;  // Before each attack
+const CUE_EVIL_SANTA_ROAR: u8 = 16;     // Phase changes
+
+// Indexed by CUE_*. The level tracks share one asset until each level gets its own file,
+// the boss fight uses the original arrangement.
+const SOUND_CUES: [SoundCue; 17] = [
+    SoundCue { variants: &["bgm_original"], category: CATEGORY_MUSIC, cooldown: 0 },
+    SoundCue { variants: &["bgm"], category: CATEGORY_MUSIC, cooldown: 0 },
+    SoundCue { variants: &["bgm"], category: CATEGORY_MUSIC, cooldown: 0 },
+    SoundCue { variants: &["bgm"], category: CATEGORY_MUSIC, cooldown: 0 },
+    SoundCue { variants: &["bgm_original"], category: CATEGORY_MUSIC, cooldown: 0 },
+    SoundCue { variants: &["completion"], category: CATEGORY_STINGER, cooldown: 0 },
+    // Victory: the rescued kids cheer
+    SoundCue { variants: &["meeting_kid", "meeting_kid_2"], category: CATEGORY_STINGER, cooldown: 0 },
+    SoundCue { variants: &["santa_death"], category: CATEGORY_STINGER, cooldown: 0 },
+    SoundCue { variants: &["jump"], category: CATEGORY_SFX, cooldown: 0 },
+    SoundCue { variants: &["collection"], category: CATEGORY_SFX, cooldown: 3 },
+    SoundCue { variants: &["explosion"], category: CATEGORY_SFX, cooldown: 4 },
+    SoundCue { variants: &["santa_hurt_1", "santa_hurt_2"], category: CATEGORY_VOICE, cooldown: 10 },
+    SoundCue { variants: &["santa_kill", "santa_kill_2"], category: CATEGORY_VOICE, cooldown: 6 },
+    SoundCue { variants: &["meeting_kid", "meeting_kid_2"], category: CATEGORY_VOICE, cooldown: 0 },
+    SoundCue { variants: &["evilSanta_hurt"], category: CATEGORY_VOICE, cooldown: 6 },
+    SoundCue {
+        variants: &["evilsanta_before_attack", "evilsanta_bfore_attak1", "evilsanta_bfore_attack2"],
+        category: CATEGORY_VOICE,
+        cooldown: 0,
+    },
+    SoundCue { variants: &["evilsanta_before_attack"], category: CATEGORY_VOICE, cooldown: 30 },
+];
+
+// Level music, indexed by level - 1
+const LEVEL_MUSIC: [u8; 3] = [CUE_MUSIC_LEVEL_1, CUE_MUSIC_LEVEL_2, CUE_MUSIC_LEVEL_3];
+
+#[turbo::serialize]
+struct AudioManager {
+    cooldowns: Vec<u16>,      // Frames left per cue
+    last_variant: Vec<u8>,    // Per cue, so a variant doesn't repeat back to back
+    music: Option<(u8, u8)>,  // Looping (cue, variant)
+    voice: Option<(u8, u8)>,  // Last voice line, stopped when the next one starts
+    music_volume: f32,        // From the settings, see GameState::apply_audio_settings
+    sfx_volume: f32,
+}
 
-        // Apply acceleration/deceleration
-        if self.player_on_ground {
-            if target_speed.abs() > 0.01 {
-                if (target_speed > 0.0 && self.player_vx < target_speed) ||
-                   (target_speed < 0.0 && self.player_vx > target_speed) {
-                    self.player_vx += target_speed.signum() * ACCEL;
-                }
-            } else {
-                if self.player_vx.abs() > DECEL {
-                    self.player_vx -= self.player_vx.signum() * DECEL;
-                } else {
-                    self.player_vx = 0.0;
-                }
-            }
-        } else {
-            // Air control
-            if target_speed.abs() > 0.01 {
-                if (target_speed > 0.0 && self.player_vx < target_speed) ||
-                   (target_speed < 0.0 && self.player_vx > target_speed) {
-                    self.player_vx += target_speed.signum() * AIR_CONTROL;
-                }
-            }
+impl AudioManager {
+    fn new() -> Self {
+        Self {
+            cooldowns: vec![0; SOUND_CUES.len()],
+            last_variant: vec![0; SOUND_CUES.len()],
+            music: None,
+            voice: None,
+            music_volume: 1.0,
+            sfx_volume: 1.0,
         }
+    }
 
-        self.player_vx = self.player_vx.clamp(-WALK_SPEED, WALK_SPEED);
+    fn sound_name(cue: u8, variant: u8) -> &'static str {
+        SOUND_CUES[cue as usize].variants[variant as usize]
+    }
 
-        // ==================== JUMPING LOGIC ====================
-        // Input buffering for jump
-        if jump_pressed {
-            self.player_jump_buffer = INPUT_BUFFER;
-        } else if self.player_jump_buffer > 0 {
-            self.player_jump_buffer -= 1;
+    fn volume(&self, category: u8) -> f32 {
+        match category {
+            CATEGORY_MUSIC | CATEGORY_STINGER => self.music_volume,
+            _ => self.sfx_volume,
         }
+    }
 
-        // Execute jump if buffer valid and grounded (or coyote time)
-        if self.player_jump_buffer > 0 && (self.player_on_ground || self.player_coyote_timer > 0) {
-            self.player_vy = JUMP_VEL;
-            self.player_state = STATE_JUMP;
-            self.player_jump_buffer = 0;
-            self.player_on_ground = false;
-            self.player_coyote_timer = 0;
-            // Reset jump animation
-            self.jump_anim_frame = 0;
-            self.jump_anim_timer = 0;
-            audio::play("jump");
+    fn pick_variant(&self, cue: u8) -> u8 {
+        let count = SOUND_CUES[cue as usize].variants.len() as u32;
+        if count == 1 {
+            return 0;
+        }
+        let variant = (random::u32() % count) as u8;
+        if variant == self.last_variant[cue as usize] {
+            (variant + 1) % count as u8
+        } else {
+            variant
         }
+    }
 
-        // Variable jump height - release early for shorter jump
-        if !jump_held && self.player_vy < 0.0 {
-            self.player_vy *= 0.5;
+    fn set_volumes(&mut self, music: f32, sfx: f32) {
+        self.music_volume = music;
+        self.sfx_volume = sfx;
+        if let Some((cue, variant)) = self.music {
+            audio::set_volume(Self::sound_name(cue, variant), music);
         }
+    }
 
-        // ==================== ATTACK LOGIC ====================
-        // Place gift bomb with C key
-        if place_bomb && self.gift_bombs > 0 && self.player_on_ground {
-            self.place_gift_bomb();
-        } else if attack && self.attack_cooldown == 0 {
-            self.player_state = STATE_ATTACK;
-            self.attack_frame = 0;
+    // Once per frame: cooldowns tick down and the music loops
+    fn update(&mut self) {
+        for cooldown in self.cooldowns.iter_mut() {
+            *cooldown = cooldown.saturating_sub(1);
+        }
+        if let Some((cue, variant)) = self.music {
+            let name = Self::sound_name(cue, variant);
+            if !audio::is_playing(name) {
+                audio::play(name);
+            }
         }
     }
 
-    // Check if player is overlapping with any ladder
-    fn check_ladder_collision(&self) -> bool {
-        let player_center_x = self.player_x;
-        let player_left = self.player_x - 7.0;
-        let player_right = self.player_x + 7.0;
-        let player_top = self.player_y - 19.0;  // Full player height
-        let player_bottom = self.player_y + 19.0;
+    // Plays a cue, false if it is still cooling down. Music cues switch the track instead
+    fn play(&mut self, cue: u8) -> bool {
+        let def = &SOUND_CUES[cue as usize];
+        if def.category == CATEGORY_MUSIC {
+            self.play_music(Some(cue));
+            return true;
+        }
+        if self.cooldowns[cue as usize] > 0 {
+            return false;
+        }
+        match def.category {
+            CATEGORY_STINGER => self.play_music(None),
+            CATEGORY_VOICE => {
+                if let Some((voice_cue, variant)) = self.voice.take() {
+                    audio::stop(Self::sound_name(voice_cue, variant));
+                }
+            },
+            _ => {},
+        }
 
-        for (lx, ly_top, ly_bottom) in self.ladders.iter() {
-            if *lx > 0.0 {  // Valid ladder
-                let ladder_left = *lx - 4.0;  // Narrower collision (4px each side)
-                let ladder_right = *lx + 4.0;
+        let variant = self.pick_variant(cue);
+        let name = Self::sound_name(cue, variant);
+        audio::set_volume(name, self.volume(def.category));
+        audio::play(name);
+        if def.category == CATEGORY_VOICE {
+            self.voice = Some((cue, variant));
+        }
+        self.cooldowns[cue as usize] = def.cooldown;
+        self.last_variant[cue as usize] = variant;
+        true
+    }
 
-                // Check if player overlaps ladder horizontally and vertically
-                // More forgiving: check if ANY part of player overlaps, not just center
-                if player_right > ladder_left && player_left < ladder_right {
-                    if player_bottom >= *ly_top && player_top <= *ly_bottom {
-                        return true;
-                    }
-                }
-            }
+    // Stops every variant of a cue
+    fn stop(&mut self, cue: u8) {
+        for name in SOUND_CUES[cue as usize].variants {
+            audio::stop(name);
+        }
+        if self.music.is_some_and(|(music_cue, _)| music_cue == cue) {
+            self.music = None;
+        }
+        if self.voice.is_some_and(|(voice_cue, _)| voice_cue == cue) {
+            self.voice = None;
         }
-        false
     }
 
-    fn apply_jump_velocity_boost(&mut self) {
-        if self.player_state == STATE_JUMP || self.player_state == STATE_FALL {
-            // Only boost horizontal velocity during jump, don't change ground speed
-            let kb = keyboard::get();
-            
-            // Check for horizontal input during jump
-            if kb.arrow_left().pressed() || kb.arrow_right().pressed() {
-                // Apply velocity boost only during jump state
-                let current_boost = if kb.arrow_left().pressed() { 
-                    -WALK_SPEED * (JUMP_LENGTH_MULTIPLIER - 1.0) 
-                } else { 
-                    WALK_SPEED * (JUMP_LENGTH_MULTIPLIER - 1.0) 
-                };
-                
-                // Add boost to existing velocity (don't replace it)
-                // This only affects Santa's player_vx, not camera or background
-                self.player_vx += current_boost;
-            }
+    // Switches the looping track, None stops the music. Asking for the current track keeps it going
+    fn play_music(&mut self, cue: Option<u8>) {
+        if self.music.map(|(music_cue, _)| music_cue) == cue {
+            return;
+        }
+        if let Some((music_cue, _)) = self.music {
+            self.stop(music_cue);
+        }
+        if let Some(cue) = cue {
+            let variant = self.pick_variant(cue);
+            let name = Self::sound_name(cue, variant);
+            audio::set_volume(name, self.music_volume);
+            audio::play(name);
+            self.music = Some((cue, variant));
+            self.last_variant[cue as usize] = variant;
         }
     }
+}
 
-    fn update_player(&mut self) {
-        // ==================== ATTACK STATE ====================
-        if self.player_state == STATE_ATTACK {
-            self.attack_frame += 1;
+// ============================================================================
+// MENUS
+// ============================================================================
+// One menu widget drives the title, pause, settings and info pages. Open menus
+// are kept on a stack of (menu, cursor): confirm may push another menu, back
+// (Esc) pops to the one underneath. Up/Down move the cursor past disabled items.
+const MENU_TITLE: u8 = 0;
+const MENU_PAUSE: u8 = 1;
+const MENU_SETTINGS: u8 = 2;
+const MENU_HIGH_SCORES: u8 = 3;
+const MENU_CREDITS: u8 = 4;
+const MENU_CONTROLS: u8 = 5;
+
+const ACTION_START: u8 = 0;
+const ACTION_CONTINUE: u8 = 1;      // Fresh run from the furthest unlocked level
+const ACTION_LEVEL_SELECT: u8 = 2;
+const ACTION_SETTINGS: u8 = 3;
+const ACTION_HIGH_SCORES: u8 = 4;
+const ACTION_CREDITS: u8 = 5;
+const ACTION_RESUME: u8 = 6;
+const ACTION_CONTROLS: u8 = 7;
+const ACTION_QUIT: u8 = 8;          // Back to the title, the run's score goes on the high score list
+const ACTION_BACK: u8 = 9;
+// Settings items: Left/Right change the value, confirm steps it forward (wrapping)
+const ACTION_MASTER_VOLUME: u8 = 10;
+const ACTION_MUSIC_VOLUME: u8 = 11;
+const ACTION_SFX_VOLUME: u8 = 12;
+const ACTION_REDUCED_MOTION: u8 = 13;
+const ACTION_HUD_LAYOUT: u8 = 14;
+
+const MENU_ROW_HEIGHT: i32 = 14;
+
+// Format: (heading, items x, items y, width), indexed by MENU_*. The title has no heading, the start page art is its backdrop
+const MENU_LAYOUT: [(&str, i32, i32, u32); 6] = [
+    ("", 124, 142, 124),
+    ("PAUSED", 124, 90, 124),
+    ("SETTINGS", 92, 64, 188),
+    ("HIGH SCORES", 124, 204, 124),
+    ("CREDITS", 124, 204, 124),
+    ("", 226, 220, 124),
+];
+
+// Format: (label, action)
+const TITLE_MENU: [(&str, u8); 6] = [
+    ("START", ACTION_START),
+    ("CONTINUE", ACTION_CONTINUE),
+    ("LEVEL SELECT", ACTION_LEVEL_SELECT),
+    ("SETTINGS", ACTION_SETTINGS),
+    ("HIGH SCORES", ACTION_HIGH_SCORES),
+    ("CREDITS", ACTION_CREDITS),
+];
+const PAUSE_MENU: [(&str, u8); 4] = [
+    ("RESUME", ACTION_RESUME),
+    ("CONTROLS", ACTION_CONTROLS),
+    ("SETTINGS", ACTION_SETTINGS),
+    ("QUIT TO TITLE", ACTION_QUIT),
+];
+const SETTINGS_MENU: [(&str, u8); 7] = [
+    ("MASTER VOLUME", ACTION_MASTER_VOLUME),
+    ("MUSIC VOLUME", ACTION_MUSIC_VOLUME),
+    ("SFX VOLUME", ACTION_SFX_VOLUME),
+    ("REDUCED MOTION", ACTION_REDUCED_MOTION),
+    ("HUD LAYOUT", ACTION_HUD_LAYOUT),
+    ("CONTROLS", ACTION_CONTROLS),
+    ("BACK", ACTION_BACK),
+];
+// High scores, credits and the controls page only have a way back
+const INFO_MENU: [(&str, u8); 1] = [("BACK", ACTION_BACK)];
+
+const HIGH_SCORE_SLOTS: usize = 5;
+
+const CREDITS_LINES: [&str; 7] = [
+    "SANTA RESCUE",
+    "",
+    "MADE BY THE SANTA RESCUE TEAM",
+    "BUILT WITH THE TURBO GENESIS SDK",
+    "WRITTEN IN RUST",
+    "",
+    "THANKS TO THE TURBO COMMUNITY AND ALL OUR PLAYTESTERS",
+];
 
-            // Spawn projectile at frame 4 (out of 5 attack frames)
-            if self.attack_frame == 4 {
-                self.spawn_player_projectile();
-            }
+// ============================================================================
+// MAIN GAME STATE
+// ============================================================================
+    #[turbo::game]
+    struct GameState {
+    frame: u32,
 
-            // Attack has 5 frames at 4 frames each = 20 frame total
-            if self.attack_frame >= 20 {
-                self.player_state = STATE_IDLE;
-                self.attack_cooldown = 24;
-            }
-            return;
-        }
+    // Player state
+    player_x: f32,
+    player_y: f32,
+    player_vx: f32,
+    player_vy: f32,
+    player_state: u8,
+    player_facing_right: bool,
+    player_on_ground: bool,
+    player_hp: u8,
+    player_max_hp: u8,
+    player_invuln_timer: u8,
+    player_breath: u16,        // Runs down under water (BREATH_FRAMES = full)
+    player_drown_timer: u16,   // Out of breath, counts to the next DROWN_DAMAGE_INTERVAL hit
+    player_anim_frame: u8,
+    player_anim_timer: u8,
+    player_coyote_timer: u8,
+    player_air_jumps: u8,        // Air jumps left before landing
+    player_shield_timer: u16,    // Candy-cane shield, blocks hits while > 0
+    movement_profiles: Vec<MovementProfile>,  // From data/movement_profiles.json, dev mode edits them live
+    movement_profile: usize,     // Index into movement_profiles
+    movement_tune_field: usize,  // Dev tuning: index into MOVEMENT_TUNE_FIELDS
+    player_jump_buffer: u8,
+    player_dash_timer: u8,         // Frames left in the current dash/slide
+    player_dash_cooldown: u8,
+    player_wall_side: i8,          // Wall touched this frame: -1 left, 1 right, 0 none
+    player_wall_sliding: bool,
+    player_wall_coyote: u8,        // Frames left for a late wall jump
+    player_wall_coyote_side: i8,   // Wall those frames belong to
+    player_wall_jump_lock: u8,
+    player_on_ladder: bool,
+    player_rope: Option<usize>,  // Index into LEVEL_ROPES while hanging
+    player_is_crouching: bool,
+    jump_anim_frame: u8,
+    jump_anim_timer: u8,
 
-        if self.attack_cooldown > 0 {
-            self.attack_cooldown -= 1;
-        }
+    // Attack state
+    attack_cooldown: u8,
+    attack_frame: u8,
+    melee_hit_boss: bool,  // Current bonk already hit the boss (one hit per swing)
 
-        // ==================== CLIMBING STATE ====================
-        if self.player_on_ladder {
-            // Apply ladder movement (no gravity while on ladder)
-            self.player_y += self.player_vy;
+    // Projectiles (up to 6 total, shared by Santa and the boss)
+    projectiles: [Projectile; 6],
 
-            let player_bottom = self.player_y + 19.0;
-            let player_left = self.player_x - 7.0;
-            let player_right = self.player_x + 7.0;
+    // Enemies (10 placed by the level, the last 2 slots are left for spawners)
+    // Format: (type, x, y, vx, vy, direction, anim_frame, patrol_start_x, active, anim_timer, is_attacking, respawn_timer)
+    // respawn_timer: 0 = alive, >0 = dead and counting down to respawn (600 frames = 10 seconds)
+    // cloud animation frame calculated from respawn_timer when needed
+    enemies: [(u8, f32, f32, f32, f32, u8, u8, u16, bool, u8, bool, u16); 12],
 
-            // Check for platform collisions when climbing UP
-            if self.player_vy < 0.0 {  // Moving up
-                for (px1, px2, py) in self.platforms.iter() {
-                    if *px2 > *px1 &&
-                       player_right > *px1 &&
-                       player_left < *px2
-                    {
-                        // Platform must be above us
-                        if *py < self.player_y {
-                            let dist_to_platform = player_bottom - *py;
+    // Penguin snowball projectiles (up to 5)
+    // Format: (active, x, y, vx, vy)
+    snowballs: [(bool, f32, f32, f32, f32); 5],
 
-                            // When feet reach the platform while climbing up
-                            if dist_to_platform >= -2.0 && dist_to_platform <= 4.0 {
-                                self.player_y = *py - 19.0;
-                                self.player_vy = 0.0;
-                                self.player_on_ground = true;
-                                self.player_on_ladder = false;
-                                self.player_state = STATE_IDLE;
-                                // Reset animation to idle
-                                self.player_anim_frame = 0;
-                                self.player_anim_timer = 0;
-                                break;
-                            }
-                        }
-                    }
-                }
-            }
+    // Gift Bombs
+    gift_bombs: u8,  // Number of gift bombs Santa has
+    gift_bomb_capacity: u8,  // Carry limit, reset on game over like the weapon tier
+    // Loot dropped by enemies and doors (rolled from the DROP_TABLE_* consts)
+    drop_items: [DropItem; 8],
+    // Placed or thrown bombs
+    placed_bombs: [PlacedBomb; 3],
 
-            // Check for platform collisions when climbing down
-            // This prevents falling through floors
-            if self.player_vy > 0.0 {  // Moving down
-                for (px1, px2, py) in self.platforms.iter() {
-                    if *px2 > *px1 &&
-                       player_right > *px1 &&
-                       player_left < *px2
-                    {
-                        // Only land on platforms where our FEET are approaching from ABOVE
-                        // Platform must be at least 30px below our current center
-                        // This ensures we've descended enough to reach the next floor
-                        if *py > (self.player_y + 21.0) {
-                            let dist_to_platform = player_bottom - *py;
+    // Doors (4 doors that can be destroyed by bombs)
+    // Format: (x, y, intact)
+    // x, y are top-left corner coordinates
+    // intact: true = normal door, false = exploded
+    doors: [(f32, f32, bool); 4],
 
-                            // Tight landing window when feet reach platform
-                            if dist_to_platform >= -2.0 && dist_to_platform <= 4.0 {
-                                self.player_y = *py - 19.0;
-                                self.player_vy = 0.0;
-                                self.player_on_ground = true;
-                                self.player_on_ladder = false;
-                                self.player_state = STATE_IDLE;
-                                // Reset animation to idle
-                                self.player_anim_frame = 0;
-                                self.player_anim_timer = 0;
-                                break;
-                            }
-                        }
-                    }
-                }
-            }
+    // Keys dropped from exploded doors
+    keys: [Key; 4],
+    keys_collected: u8,
+    keys_held: u8,                     // Bitmask of (1 << KEY_*)
+    key_pickup_flash: u8,
+    key_pickup_color: u8,
 
-            // Update climbing animation - cycle through all 10 frames
-            if self.player_vy.abs() > 0.1 {
-                self.player_anim_timer += 1;
-                if self.player_anim_timer >= 6 {  // Faster animation (was 10)
-                    self.player_anim_timer = 0;
-                    self.player_anim_frame = (self.player_anim_frame + 1) % 10;  // All 10 frames
-                }
-            }
-            return;
-        }
+    // Switches, plates and timers (see LEVEL_TRIGGERS)
+    triggers: [Trigger; 8],
+    // Link targets (see LEVEL_TARGETS)
+    targets: [LinkTarget; 8],
+    links_on: u16,                     // Bitmask of links with a trigger on
+    links_prev: u16,                   // Last frame's links_on, for rising edges
 
-        // ==================== CROUCHING STATE ====================
-        if self.player_is_crouching {
-            // Apply horizontal movement while crouching
-            self.player_x += self.player_vx;
-            self.check_player_collisions();
-            return;
-        }
+    // Gate open animation per walls entry, GATE_OPEN_FRAMES = fully open
+    gate_progress: [u8; 22],
+    gate_message: (usize, u16),        // (LEVEL_GATES index, frames left)
 
-        // ==================== NORMAL PHYSICS ====================
-        self.player_vy += GRAVITY;
-        if self.player_vy > TERMINAL_VEL {
-            self.player_vy = TERMINAL_VEL;
-        }
+    // Game state
+    score: u32,
+    lives: u8,
+    level: u8,
+    timer: u32,
+    camera_x: f32,
 
-        if !self.player_on_ground && self.player_coyote_timer > 0 {
-            self.player_coyote_timer -= 1;
-        }
+    // Developer options
+    dev_mode: bool,
+    // UI: Controls panel overlay
+    show_controls_panel: bool,
+    
+    // Game state management
+    in_menu: bool,  // true = in start menu, false = playing
+    menu_stack: Vec<(u8, u8)>,  // Open menus as (MENU_*, cursor), the last one has focus
+    sound: AudioManager,
+    show_controls: bool,  // true = showing controls page after starting page
+    game_over_timer: u16,  // Timer for game over screen (10 seconds)
+    game_won_timer: u16,  // Timer for victory screen (3 seconds)
+    time_up_timer: u16,  // Timer for time up screen (3 seconds)
+    show_game_over: bool,
+    show_victory: bool,
+    show_time_up: bool,  // true = showing time up screen
 
-        if self.player_invuln_timer > 0 {
-            self.player_invuln_timer -= 1;
-        }
+    // Level completion
+    level_complete: bool,
+    level_transition_timer: u8,
+    completion_trigger: (f32, f32, f32, f32), // x, y, width, height
 
-        self.player_x += self.player_vx;
-        self.player_y += self.player_vy;
+    // Level layout - 3 floors with multiple platforms
+    // Using Vec to support variable number of platforms (boss level has 52)
+    platforms: Vec<(f32, f32, f32)>, // x1, x2, y for each platform
+    ladders: [(f32, f32, f32); 6],    // x, y_top, y_bottom for each ladder
+    walls: [(f32, f32, f32, f32); 22], // x, y, width, height for each wall
+    // Destructible blocks (see LEVEL_BLOCKS), hp 0 = broken
+    blocks: [Block; 8],
 
-        self.check_player_collisions();
+    // Kids to rescue (up to 3 per level)
+    // Format: (x, y, active, collected, anim_frame, anim_timer, spawned_from_door_idx)
+    kids: [(f32, f32, bool, bool, u8, u8, i8); 3],
+    kids_collected: u8,
+    
+    // Level timer (in frames, 60 fps)
+    level_timer: u32,  // Current time elapsed
+    level_time_limit: u32,  // Time limit for current level
+    total_kids_in_level: u8,
+    kid_pickup_flash: u8,
 
-        // ==================== STATE DETERMINATION ====================
-        if self.player_on_ground {
-            if self.player_vx.abs() < 0.1 {
-                self.player_state = STATE_IDLE;
-            } else {
-                self.player_state = STATE_WALK;
-            }
-            // Reset jump animation when landing
-            self.jump_anim_frame = 0;
-            self.jump_anim_timer = 0;
-        } else {
-            if self.player_vy < 0.0 {
-                self.player_state = STATE_JUMP;
-            } else {
-                self.player_state = STATE_FALL;
-            }
-        }
+    // Life powerup (spawns from specific door in boss level)
+    life_position: (f32, f32),
+    life_active: bool,
+    life_collected: bool,
+    life_door_index: usize,  // Which door spawns life powerup (door #3 in level 3)
 
-        // ==================== ANIMATION UPDATES ====================
-        self.update_animations();
-    }
+    // PowerUp1 (spawns from door #4 in level 3)
+    powerup1_position: (f32, f32),
+    powerup1_active: bool,
+    powerup1_collected: bool,
 
-    fn update_animations(&mut self) {
-        match self.player_state {
-            STATE_IDLE => {
-                // 4 idle frames, 15 frames per sprite for breathing effect
-                self.player_anim_timer += 1;
-                if self.player_anim_timer >= 15 {
-                    self.player_anim_timer = 0;
-                    self.player_anim_frame = (self.player_anim_frame + 1) % IDLE_FRAMES;
-                }
-            },
-            STATE_WALK | STATE_RUN => {
-                // 10 running frames, 6 frames per sprite for smooth run cycle
-                self.player_anim_timer += 1;
-                if self.player_anim_timer >= 6 {
-                    self.player_anim_timer = 0;
-                    self.player_anim_frame = (self.player_anim_frame + 1) % RUN_FRAMES;
-                }
-            },
-            STATE_JUMP => {
-                // 5 jumping frames, progress through based on velocity
-                self.jump_anim_timer += 1;
-                if self.jump_anim_timer >= 8 {
-                    self.jump_anim_timer = 0;
-                    if self.jump_anim_frame < JUMP_FRAMES - 1 {
-                        self.jump_anim_frame += 1;
-                    }
-                }
-            },
-            STATE_FALL => {
-                // Use last jump frame or fall sprite
-                self.jump_anim_frame = JUMP_FRAMES - 1;
-            },
-            STATE_ATTACK => {
-                // 5 attack frames, 4 frames per sprite
-                let attack_sprite_frame = (self.attack_frame / 4) % ATTACK_FRAMES;
-                self.player_anim_frame = attack_sprite_frame;
-            },
-            STATE_CLIMB => {
-                // 10 climbing frames - animation handled in update_player
-                // This state is managed separately because climbing uses player_vy
-            },
-            STATE_CROUCH => {
-                // Use scared sprite for crouching
-                self.player_anim_frame = 0;
-            },
-            _ => {}
-        }
-    }
+    // Weapon (see WEAPONS)
+    weapon_tier: u8,             // WEAPON_*, upgraded by powerUp1
+    player_charge: u16,          // Frames Z has been held
+    charged_shot_pending: bool,  // Current attack fires a charged shot
+    has_wall_jump: bool,         // Wall boots, dropped by the first Giant Kickmouse
+    save: SaveData,
+    save_loaded: bool,           // Local storage is read on the first update
+    level_stats: LevelStats,
+    show_results: bool,          // Results screen after the level complete fade
+    show_level_select: bool,
+    level_select_cursor: u8,     // 0-based level under the cursor
+    last_result: (u8, bool),     // (stars, new best) of the level just finished
+    show_workshop: bool,         // Gift workshop intermission between levels
+    workshop_cursor: u8,         // Selected SHOP_ITEMS row
+    items: [u8; 4],              // Consumables carried, indexed by ITEM_*
+    selected_item: u8,
+    item_message: (u8, u8, u16),  // (ITEM_*, ITEM_MSG_*, frames) HUD line after picking up / using an item
+    enemy_freeze_timer: u16,     // Snow globe, enemies stand still while > 0
+    hidden_kids: [(bool, bool); 3],  // (revealed, rescued), indexed like LEVEL_HIDDEN_KIDS
+    upgrade_message_timer: u16,
 
-    fn check_player_collisions(&mut self) {
-        let was_on_ground = self.player_on_ground;
-        self.player_on_ground = false;
+    // Boss fight state
+        boss_active: bool,
+        boss_defeated: bool,  // Prevents boss from re-triggering after victory
+        use_boss_santa: bool,
+        boss_index: u8,         // Index into BOSS_DEFS of the current/last boss
+        boss: BossEntity,
+        // Player idle tracking for boss aggression
+        player_idle_x: f32,
+        player_idle_timer: u32,
+        // Boss death handling
+        boss_death_timer: u16,
+        // Fight phases and arena modifications
+        boss_fight_phase: u8,
+        boss_screen_flash: u8,
+        boss_floor_left: f32,   // Current floor extents (shrinks in phase 3)
+        boss_floor_right: f32,
+        boss_floor_shrink_timer: u16,
+        boss_icicle_timer: u16,
+        boss_minions: u16,  // Bit per enemies[] slot holding a summoned minion
+        falling_hazards: [FallingHazard; 8],
+        boss_shockwaves: [Shockwave; 2],
+        // Attack definitions and selection rules of the boss being fought
+        boss_pattern: BossPattern,
+        boss_patterns: Vec<BossPattern>,  // Per BOSS_DEFS entry, parsed and checked once at startup
+
+    // Cutscene playback (see CUTSCENES)
+    cutscene_id: u8,              // CUTSCENE_NONE when gameplay has control
+    cutscene_step: u8,
+    cutscene_timer: u16,          // Frames spent in the current step
+    cutscene_camera_from: f32,    // camera_x when the current step started
+    cutscene_camera_hold: bool,   // Script owns the camera (false = follow Santa)
+    cutscene_fade: u8,            // Black overlay alpha
+    cutscene_age: u16,            // Frames since the script started, ENTER skips after CUTSCENE_SKIP_DELAY
+    intros_seen: u8,              // Bitmask by level of intros played since the last new game
+    }
 
-        // Player hitbox (center-based Y coordinate)
-        let player_bottom = self.player_y + 19.0;
-        let player_left = self.player_x - 7.0;
-        let player_right = self.player_x + 7.0;
+impl GameState {
+    pub fn new() -> Self {
+        let boss_patterns = Self::load_boss_patterns();
+        let mut game = Self {
+            frame: 0,
 
-        // ============================================
-        // VERTICAL COLLISION PASS (NES-style)
-        // ============================================
-        // Platforms are 1D horizontal surfaces
-        // Wide tolerance (20px) handles high-speed falls
+            player_x: 50.0,
+            player_y: 100.0,
+            player_vx: 0.0,
+            player_vy: 0.0,
+            player_state: STATE_IDLE,
+            player_facing_right: true,
+            player_on_ground: false,
+            player_hp: 6,
+            player_max_hp: PLAYER_START_MAX_HP,
+            player_invuln_timer: 0,
+            player_breath: BREATH_FRAMES,
+            player_drown_timer: 0,
+            player_anim_frame: 0,
+            player_anim_timer: 0,
+            player_coyote_timer: 0,
+            player_air_jumps: 0,
+            player_shield_timer: 0,
+            movement_profiles: MovementProfile::list_from_json(MOVEMENT_PROFILES_JSON).expect("data/movement_profiles.json is invalid"),
+            movement_profile: 0,
+            movement_tune_field: 0,
+            player_jump_buffer: 0,
+            player_dash_timer: 0,
+            player_dash_cooldown: 0,
+            player_wall_side: 0,
+            player_wall_sliding: false,
+            player_wall_coyote: 0,
+            player_wall_coyote_side: 0,
+            player_wall_jump_lock: 0,
+            player_on_ladder: false,
+            player_rope: None,
+            player_is_crouching: false,
+            jump_anim_frame: 0,
+            jump_anim_timer: 0,
 
-        if self.player_vy >= 0.0 {  // Only when falling/grounded
-            for (px1, px2, py) in self.platforms.iter() {
-                // Check if platform is valid and player overlaps horizontally
-                if *px2 > *px1 &&
-                   player_right > *px1 &&
-                   player_left < *px2
-                {
-                    // Wide vertical tolerance for consistent collision
-                    let dist_to_platform = player_bottom - *py;
+            attack_cooldown: 0,
+            attack_frame: 0,
+            melee_hit_boss: false,
 
-                    if dist_to_platform >= 0.0 && dist_to_platform <= 20.0 {
-                        // HARD SNAP to platform top (NES-style, no smoothing)
-                        self.player_y = *py - 19.0;
-                        self.player_vy = 0.0;
-                        self.player_on_ground = true;
+            projectiles: [Projectile::EMPTY; 6],
+            enemies: [(0, 0.0, 0.0, 0.0, 0.0, 0, 0, 0, false, 0, false, 0); 12],
+            snowballs: [(false, 0.0, 0.0, 0.0, 0.0); 5],  // Penguin snowball projectiles
 
-                        if !was_on_ground {
-                            self.player_coyote_timer = COYOTE_TIME;
-                        }
-                        break;  // Stop at first collision
-                    }
-                }
-            }
-        }
+            gift_bombs: 0,
+            gift_bomb_capacity: GIFT_BOMB_START_CAPACITY,
+            drop_items: [DropItem::EMPTY; 8],
+            placed_bombs: [PlacedBomb::EMPTY; 3],
+            doors: [(0.0, 0.0, false); 4],  // Will be set in load_level
 
-        // ============================================
-        // HORIZONTAL COLLISION PASS (walls & bounds)
-        // ============================================
+            score: 0,
+            lives: 3,
+            level: 1,
+            timer: LEVEL_TIME,
+            camera_x: 0.0,
 
-        // Check wall collisions (AABB - Axis-Aligned Bounding Box)
-        let player_top = self.player_y - 19.0;
+            // Developer options
+            dev_mode: false,
+            show_controls_panel: false,
+            
+            in_menu: true,  // Start with menu screen
+            menu_stack: vec![(MENU_TITLE, 0)],
+            sound: AudioManager::new(),
+            show_controls: false,  // Controls page shown after starting page
+            show_game_over: false,
+            show_victory: false,
+            show_time_up: false,
+            game_over_timer: 0,
+            game_won_timer: 0,
+            time_up_timer: 0,
 
-        for (i, (wx, wy, ww, wh)) in self.walls.iter().enumerate() {
-            // Check if wall is valid
-            if *ww > 0.0 && *wh > 0.0 {
-                // Gate logic: different for each level
-                if self.level == 3 && !self.boss_active {
-                    // Level 3 (not in boss fight): Gate #1 (wall[0]) disappears with 1 key, Gate #2 (wall[1]) with 2 keys
-                    if (i == 0 && self.keys_collected >= 1) || (i == 1 && self.keys_collected >= 2) {
-                        continue;
-                    }
-                } else if self.level != 3 {
-                    // Other levels: Gate (wall #3) disappears once 3 keys are collected
-                    if i == 2 && self.keys_collected >= 3 {
-                        continue;
-                    }
-                }
-                // During boss fight in level 3, walls[0] and walls[1] are corner walls and should NOT disappear
+            level_complete: false,
+            level_transition_timer: 0,
+            completion_trigger: (0.0, 0.0, 0.0, 0.0),
 
-                let wall_left = *wx;
-                let wall_right = *wx + *ww;
-                let wall_top = *wy;
-                let wall_bottom = *wy + *wh;
+            platforms: Vec::new(), // Will be populated in load_level
+            ladders: [
+                // Initialize empty, will be set in load_level
+                (0.0, 0.0, 0.0); 6
+            ],
+            walls: [
+                // Initialize empty, will be set in load_level
+                (0.0, 0.0, 0.0, 0.0); 22
+            ],
+            blocks: [Block::EMPTY; 8],
 
-                // AABB collision detection
-                if player_right > wall_left &&
-                   player_left < wall_right &&
-                   player_bottom > wall_top &&
-                   player_top < wall_bottom
-                {
-                    // Collision detected - push player out
-                    let overlap_left = player_right - wall_left;
-                    let overlap_right = wall_right - player_left;
-                    let overlap_top = player_bottom - wall_top;
-                    let overlap_bottom = wall_bottom - player_top;
+            // Keys state
+            keys: [Key::EMPTY; 4],
+            keys_collected: 0,
+            keys_held: 0,
+            key_pickup_flash: 0,
+            key_pickup_color: KEY_RED,
+            triggers: [Trigger::EMPTY; 8],
+            targets: [LinkTarget::EMPTY; 8],
+            links_on: 0,
+            links_prev: 0,
+            gate_progress: [0; 22],
+            gate_message: (0, 0),
 
-                    // Find smallest overlap (that's the direction to push)
-                    let min_overlap = overlap_left.min(overlap_right).min(overlap_top).min(overlap_bottom);
+            // Kids state
+            kids: [(0.0, 0.0, false, false, 0, 0, -1); 3],
+            kids_collected: 0,
+            level_timer: 0,
+            level_time_limit: 180 * 60,  // Default 3 minutes
+            total_kids_in_level: 0,
+            kid_pickup_flash: 0,
 
-                    if min_overlap == overlap_left {
-                        // Push player left
-                        self.player_x = wall_left - 7.0;
-                        self.player_vx = 0.0;
-                    } else if min_overlap == overlap_right {
-                        // Push player right
-                        self.player_x = wall_right + 7.0;
-                        self.player_vx = 0.0;
-                    } else if min_overlap == overlap_top {
-                        // Push player down (hitting ceiling)
-                        self.player_y = wall_top - 19.0;
-                        self.player_vy = 0.0;
-                    } else {
-                        // Push player up (standing on wall top)
-                        self.player_y = wall_bottom + 19.0;
-                        self.player_vy = 0.0;
-                        self.player_on_ground = true;
-                    }
-                }
-            }
-        }
+            // Life powerup state
+            life_position: (0.0, 0.0),
+            life_active: false,
+            life_collected: false,
+            life_door_index: 2,  // Door #3 (index 2) in level 3
 
-        // World bounds - left wall
-        if self.player_x < 7.0 {
-            self.player_x = 7.0;
-            self.player_vx = 0.0;
-        }
+            // PowerUp1 state
+            powerup1_position: (0.0, 0.0),
+            powerup1_active: false,
+            powerup1_collected: false,
 
-        // World bounds - right wall (level dependent)
-        let max_x = match self.level {
-            1 => 1073.0,  // Level 1 boundary (1080 - 7)
-            2 => 1433.0,  // Level 2 boundary (1440 - 7)
-            3 => 2153.0,  // Boss level boundary (2160 - 7)
-            _ => 1073.0,  // Default
+            weapon_tier: WEAPON_BASIC,
+            player_charge: 0,
+            charged_shot_pending: false,
+            has_wall_jump: false,
+            save: SaveData::new(),
+            save_loaded: false,
+            level_stats: LevelStats::new(0, PLAYER_START_MAX_HP),
+            show_results: false,
+            show_level_select: false,
+            level_select_cursor: 0,
+            last_result: (0, false),
+            show_workshop: false,
+            workshop_cursor: 0,
+            items: [0; 4],
+            selected_item: ITEM_COCOA,
+            item_message: (0, ITEM_MSG_PICKUP, 0),
+            enemy_freeze_timer: 0,
+            hidden_kids: [(false, false); 3],
+            upgrade_message_timer: 0,
+
+        // Boss fight
+        boss_active: false,
+        boss_defeated: false,
+        use_boss_santa: false,
+        boss_index: 0,
+        boss: BossEntity::new(),
+        player_idle_x: 0.0,
+        player_idle_timer: 0,
+        boss_death_timer: 0,
+        boss_fight_phase: BOSS_FIGHT_PHASE_1,
+        boss_screen_flash: 0,
+        boss_floor_left: BOSS_ARENA_LEFT,
+        boss_floor_right: BOSS_ARENA_RIGHT,
+        boss_floor_shrink_timer: 0,
+        boss_icicle_timer: 0,
+        boss_minions: 0,
+        falling_hazards: [FallingHazard::EMPTY; 8],
+        boss_shockwaves: [Shockwave::EMPTY; 2],
+        boss_pattern: boss_patterns[0].clone(),
+        boss_patterns,
+
+            cutscene_id: CUTSCENE_NONE,
+            cutscene_step: 0,
+            cutscene_timer: 0,
+            cutscene_camera_from: 0.0,
+            cutscene_camera_hold: false,
+            cutscene_fade: 0,
+            cutscene_age: 0,
+            intros_seen: 0,
         };
-        if self.player_x > max_x {
-            self.player_x = max_x;
-            self.player_vx = 0.0;
-        }
 
-        // ============================================
-        // DEATH ZONE (below all platforms)
-        // ============================================
+        game.load_level(1);
+        game
+    }
 
-        if self.player_y > 260.0 {
-            self.player_hp = 0;
-            self.player_state = STATE_DEAD;
+    pub fn update(&mut self) {
+        self.frame += 1;
+        
+        let kb = keyboard::get();
+        
+        if !self.save_loaded {
+            self.load_save();
         }
-    }
+        self.sound.play_music(self.music_cue());
+        self.sound.update();
 
-    fn spawn_player_projectile(&mut self) {
-        let spawn_x = if self.player_facing_right {
-            self.player_x + 20.0
-        } else {
-            self.player_x - 20.0
-        };
-        let spawn_y = self.player_y;
-        let vel = if self.player_facing_right { 4.0 } else { -4.0 };
+        // LEVEL SELECT - unlocked levels with their best results, opened from the start page
+        if self.show_level_select {
+            self.update_level_select();
+            self.render();
+            return;
+        }
 
-        let player_proj_count = self.projectiles.iter()
-            .filter(|(active, _, _, _, is_enemy, _)| *active && !*is_enemy)
-            .count();
+        // MENU STATE - title menu over the starting page, START goes on to the controls page
+        if self.in_menu {
+            self.update_menu();
+            self.render();  // Render the starting page
+            return;  // Don't process game logic while in menu
+        }
+        
+        // CONTROLS STATE - show controls page, press Enter to start game
+        if self.show_controls {
+            if kb.enter().just_pressed() {
+                self.show_controls = false;
+                self.intros_seen = 0;  // New game from the title replays the story
+                self.start_run(1);
+                log!("Game started from controls page!");
+            } else if kb.escape().just_pressed() || kb.backspace().just_pressed() {
+                self.show_controls = false;
+                self.in_menu = true;
+            }
+            self.render();  // Render the controls page
+            return;  // Don't process game logic while showing controls
+        }
+        
+        // GAME OVER STATE - show game over screen for 3 seconds then restart from level 1
+        if self.show_game_over {
+            self.game_over_timer += 1;
+            if self.game_over_timer >= 180 {  // 3 seconds
+                // Reset game completely and restart from level 1
+                self.show_game_over = false;
+                self.game_over_timer = 0;
+                self.record_run_score();
+                self.start_run(1);
+                log!("Game restarted from level 1!");
+            }
+            self.render();  // Continuously render game over screen
+            return;  // Don't process game logic during game over
+        }
+        
+        // VICTORY STATE - show victory screen for 5 seconds then restart from level 1
+        // Triggered when Santa collects kid from door 0 in level 3
+        if self.show_victory {
+            self.game_won_timer += 1;
+            if self.game_won_timer >= 300 {  // 5 seconds
+                // Reset game completely and restart from level 1
+                self.show_victory = false;
+                self.game_won_timer = 0;
+                self.record_run_score();
+                self.start_run(1);
+                log!("Victory! Game restarted from level 1!");
+            }
+            self.render();  // Continuously render victory screen
+            return;  // Don't process game logic during victory
+        }
+        
+        // TIME UP STATE - show time up screen for 3 seconds then restart from level 1
+        if self.show_time_up {
+            self.time_up_timer += 1;
+            if self.time_up_timer >= 180 {  // 3 seconds
+                // Reset game completely and restart from level 1
+                self.show_time_up = false;
+                self.time_up_timer = 0;
+                self.record_run_score();
+                self.start_run(1);
+                log!("Game restarted from level 1 after time up!");
+            }
+            self.render();  // Continuously render time up screen
+            return;  // Don't process game logic during time up
+        }
+        // RESULTS STATE - stars for the level just cleared, Enter moves on
+        if self.show_results {
+            if kb.enter().just_pressed() {
+                self.show_results = false;
+                self.leave_completed_level();
+            }
+            self.render();
+            return;
+        }
+        // WORKSHOP STATE - spend toy tokens between levels, Enter moves on
+        if self.show_workshop {
+            self.update_workshop();
+            self.render();
+            return;
+        }
+        // CUTSCENE STATE - the script drives Santa, the boss and the camera; timers and enemies wait
+        if self.cutscene_id != CUTSCENE_NONE {
+            self.update_cutscene();
+            self.render();
+            return;
+        }
 
-        if player_proj_count >= 2 {
+        // PAUSE - Esc or P opens the pause menu, the level waits until it closes
+        if !self.menu_stack.is_empty() {
+            self.update_menu();
+            self.render();
+            return;
+        }
+        if kb.escape().just_pressed() || kb.key_p().just_pressed() {
+            self.open_menu(MENU_PAUSE);
+            self.render();
             return;
         }
 
-        for proj in self.projectiles.iter_mut() {
-            if !proj.0 {
-                *proj = (true, spawn_x, spawn_y, vel, false, 0.0);
-                break;
+        // Update level timer
+        if self.player_state != STATE_DEAD {
+            self.level_timer += 1;
+            if self.level_timer >= self.level_time_limit {
+                self.show_time_up = true;
+                self.time_up_timer = 0;
+                self.sound.play(CUE_GAME_OVER);
+                log!("Time's up! Level failed.");
+                self.render();
+                return;
             }
         }
-    }
 
-    fn spawn_gift_bomb(&mut self, x: f32, enemy_y: f32) {
-        // Enemy Y = platform_y - 28 (enemy center is 28px above platform)
-        // Santa Y = platform_y - 28 (Santa center is 28px above platform)
-        // Gift bomb should be at same Y as Santa/enemy for consistency
-        // Gift bomb sprite is 64x64 with gift at bottom middle
-        // We want the gift bomb center at the same Y as Santa (enemy_y)
-        let bomb_y = enemy_y;
-
-        // Find an empty slot for the gift bomb item
-        for item in self.gift_bomb_items.iter_mut() {
-            if !item.2 {  // If not active
-                // Set despawn timer to 600 frames (10 seconds at 60 FPS)
-                *item = (x, bomb_y, true, 600);
-                break;
-            }
+        // Developer mode toggle and stage jump controls (Press . to toggle)
+        if kb.period().just_pressed() {
+            self.dev_mode = !self.dev_mode;
         }
-    }
+        if self.dev_mode {
+            // Direct stage selection (ignores unlocks)
+            if kb.key_j().just_pressed() { self.enter_level(1); }
+            if kb.key_k().just_pressed() { self.enter_level(2); }
+            if kb.key_b().just_pressed() { self.enter_level(3); }
 
-    fn place_gift_bomb(&mut self) {
-        // Find an empty slot for the placed bomb
-        for bomb in self.placed_bombs.iter_mut() {
-            if !bomb.2 {  // If not active
-                // Place bomb at Santa's Y position (same level as Santa)
-                // Santa is at player_y (center is 28px above platform)
-                // Gift bomb should be at same Y as Santa
-                let bomb_y = self.player_y;
+            // Cycle stages
+            if kb.key_l().just_pressed() {
+                let next = if self.level >= 3 { 1 } else { self.level + 1 };
+                self.enter_level(next);
+            }
+            if kb.key_h().just_pressed() {
+                let prev = if self.level <= 1 { 3 } else { self.level - 1 };
+                self.enter_level(prev);
+            }
 
-                *bomb = (self.player_x, bomb_y, true, 0, 0);
-                self.gift_bombs -= 1;
-                break;
+            // Cycle weapon tiers
+            if kb.key_w().just_pressed() {
+                self.weapon_tier = (self.weapon_tier + 1) % WEAPON_TIERS.len() as u8;
+            }
+
+            // Toggle wall boots
+            if kb.key_v().just_pressed() {
+                self.has_wall_jump = !self.has_wall_jump;
+            }
+
+            // Cycle movement profiles (live, keeps Santa's current velocity)
+            if kb.key_m().just_pressed() {
+                self.movement_profile = (self.movement_profile + 1) % self.movement_profiles.len();
+            }
+
+            // Live tuning of the current profile: [ ] pick a value, - = change it.
+            // Every change logs the profile as JSON for data/movement_profiles.json
+            let fields = MOVEMENT_TUNE_FIELDS.len();
+            if kb.bracket_left().just_pressed() {
+                self.movement_tune_field = (self.movement_tune_field + fields - 1) % fields;
+            }
+            if kb.bracket_right().just_pressed() {
+                self.movement_tune_field = (self.movement_tune_field + 1) % fields;
+            }
+            let steps = if kb.equal().just_pressed() {
+                1.0
+            } else if kb.minus().just_pressed() {
+                -1.0
+            } else {
+                0.0
+            };
+            if steps != 0.0 {
+                let field = self.movement_tune_field;
+                let profile = &mut self.movement_profiles[self.movement_profile];
+                profile.tune(field, steps);
+                log!("{}", serde_json::to_string(profile).unwrap_or_default());
             }
         }
-    }
 
-    fn update_projectiles(&mut self) {
-        // First, check for projectile-enemy collisions (player projectiles only)
-        for proj_idx in 0..self.projectiles.len() {
-            if self.projectiles[proj_idx].0 && !self.projectiles[proj_idx].4 {
-                // Active player projectile - check against all enemies
-                let proj_x = self.projectiles[proj_idx].1;
-                let proj_y = self.projectiles[proj_idx].2;
-                
-                // Check boss collision first
-                let mut hit_boss = false;
-                if self.boss_active && self.evil_santa_hp > 0 {
-                    let dxb = (proj_x - self.evil_santa_x).abs();
-                    let dyb = (proj_y - self.evil_santa_y).abs();
-                    if dxb < 20.0 && dyb < 20.0 {
-                        // Hit boss: decrement HP and apply small knockback
-                        self.projectiles[proj_idx].0 = false;
-                        self.evil_santa_hp = self.evil_santa_hp.saturating_sub(2); // Increased damage
-                        audio::play("evilSanta_hurt");
-                        self.evil_santa_vx = if proj_x < self.evil_santa_x { 0.8 } else { -0.8 };
-                        self.evil_santa_flash_timer = 5; // Flash for 5 frames
-                        // Reset to idle after hit if on ground
-                        if self.evil_santa_on_ground { self.evil_santa_state = BOSS_STATE_IDLE; }
-                        hit_boss = true;
-                    }
-                }
+        if self.player_state != STATE_DEAD {
+            self.handle_input();
+            self.update_player();
+            self.update_camera();
+        }
 
-                if hit_boss { continue; }
+        // Boss arena trigger: any boss defined for this level whose trigger zone Santa enters
+        if !self.boss_active && !self.boss_defeated {
+            if let Some(index) = self.find_boss_trigger() {
+                self.start_boss_fight(index);
+            }
+        }
 
-                for enemy_idx in 0..self.enemies.len() {
-                    // Check if enemy is alive (active and not respawning)
-                    if self.enemies[enemy_idx].8 && self.enemies[enemy_idx].11 == 0 {
-                        let enemy_x = self.enemies[enemy_idx].1;
-                        let enemy_y = self.enemies[enemy_idx].2;
-                        
-                        // Collision check: 16px radius for both projectile and enemy
-                        let dx = (proj_x - enemy_x).abs();
-                        let dy = (proj_y - enemy_y).abs();
-                        
-                        if dx < 20.0 && dy < 20.0 {
-                            // Hit! Kill the enemy and deactivate projectile
-                            self.projectiles[proj_idx].0 = false;
+        if self.boss_active {
+            // In-place arenas have no corner walls, keep Santa inside the locked screen
+            let def = self.boss_def();
+            if !def.own_screen {
+                self.player_x = self.player_x.clamp(def.arena.0 + 8.0, def.arena.1 - 8.0);
+            }
+            self.update_boss();
+        }
 
-                            // Start respawn timer (600 frames = 10 seconds at 60fps)
-                            self.enemies[enemy_idx].11 = 600;
+        self.update_enemies();
+        self.check_enemy_collisions();  // Check for damage from enemies
+        self.update_consumables();  // Shield, snow globe and item message timers
+        self.update_projectiles();
+        self.update_snowballs();  // Update penguin snowball projectiles
+        self.update_placed_bombs();
+        self.update_blocks();
+        self.update_triggers();  // Switches, plates, timers and what they drive
+        self.update_hazards();
+        self.update_breath();
 
-                            // Drop a gift bomb at enemy's position
-                            self.spawn_gift_bomb(enemy_x, enemy_y);
+        if self.timer > 0 {
+            self.timer -= 1;
+        } else {
+            self.player_hp = 0;
+        }
+        
+        // Every HP lost this frame, whatever the source, counts toward the results screen
+        if self.player_hp < self.level_stats.hp_seen {
+            self.level_stats.damage_taken += (self.level_stats.hp_seen - self.player_hp) as u16;
+        }
+        self.level_stats.hp_seen = self.player_hp;
 
-                            // Add score for kill
-                            self.score += 100;
-                            
-                            // Play randomized kill sound
-                            let kill_sfx = if self.frame % 2 == 0 { "santa_kill" } else { "santa_kill_2" };
-                            audio::play(kill_sfx);
+        // Check for death and handle respawn/game over
+        if self.player_hp == 0 && self.player_state != STATE_DEAD {
+            self.handle_death();
+        }
 
-                            break;  // Projectile can only hit one enemy
-                        }
-                    }
-                }
-            }
+        self.update_keys_animation();
+        self.check_keys();
+        self.update_gates();
+        self.check_drop_pickups();
+
+        // Update and check kids
+        self.update_kids_animation();
+        self.check_kid_collection();
+        self.check_hidden_kid_collection();
+        self.check_life_collection();  // Check life powerup collection
+        self.check_powerup1_collection();  // Check powerup1 collection
+
+        // Check for level completion
+        if !self.level_complete {
+            self.check_level_completion();
         }
-        
-        // Boss projectiles hit Santa
-        for proj_idx in 0..self.projectiles.len() {
-            if self.projectiles[proj_idx].0 && self.projectiles[proj_idx].4 {
-                let proj_x = self.projectiles[proj_idx].1;
-                let proj_y = self.projectiles[proj_idx].2;
-                let dx = (proj_x - self.player_x).abs();
-                let dy = (proj_y - self.player_y).abs();
-                if dx < 16.0 && dy < 16.0 && self.player_invuln_timer == 0 {
-                    // Apply modest damage and knockback
-                    let damage = ((self.player_max_hp as f32 * 0.10).ceil() as u8).max(1);
-                    self.player_hp = self.player_hp.saturating_sub(damage);
-                    let hurt_sfx = if self.frame % 2 == 0 { "santa_hurt_1" } else { "santa_hurt_2" };
-                    audio::play(hurt_sfx);
-                    self.player_invuln_timer = 60;
-                    self.player_vx = if proj_x > self.player_x { -3.0 } else { 3.0 };
-                    self.player_vy = -3.5;
-                    // Despawn projectile on hit
-                    self.projectiles[proj_idx].0 = false;
-                }
+
+        // Handle level transition
+        if self.level_complete {
+            if self.level_transition_timer > 0 {
+                self.level_transition_timer -= 1;
+            } else {
+                // Results screen first, then the workshop or the next level
+                self.record_level_result();
+                self.show_results = true;
             }
         }
-        
-        // Then update projectile positions
-        for proj in self.projectiles.iter_mut() {
-            if proj.0 {
-                proj.1 += proj.3;
-                proj.5 += proj.3.abs();
 
-                let max_dist = if proj.4 { 200.0 } else { 5.0 };
-                if proj.5 > max_dist {
-                    proj.0 = false;
-                }
+        self.render();
+    }
+
+    fn restart_level(&mut self) {
+        // Reset player health
+        self.player_hp = self.player_max_hp;
+        self.player_state = STATE_IDLE;
+        self.player_invuln_timer = 0;
+
+        // Reload current level
+        self.load_level(self.level);
+    }
+
+    fn handle_death(&mut self) {
+        // Check if player has lives remaining
+        if self.lives > 0 {
+            // Lose a life
+            self.lives -= 1;
 
-                if proj.1 < self.camera_x - 50.0 || proj.1 > self.camera_x + SCREEN_WIDTH + 50.0 {
-                    proj.0 = false;
+            // Respawn at starting position based on current level (or in the boss arena)
+            if self.boss_active {
+                // Boss arena starting point, kept over solid floor if phase 3 shrank it
+                let start = self.boss_def().player_start;
+                self.player_x = start.0.max(self.boss_floor_left + 40.0);
+                self.player_y = start.1;
+            } else {
+            match self.level {
+                1 => {
+                    self.player_x = 145.0;  // Level 1 starting X
+                    self.player_y = 72.0;   // Level 1 starting Y
+                },
+                2 => {
+                    self.player_x = 60.0;   // Level 2 starting X
+                    self.player_y = 58.0;   // Level 2 starting Y (86 - 28)
+                },
+                3 => {
+                    self.player_x = 150.0;  // Boss level starting X
+                    self.player_y = 194.0;  // Boss level starting Y (222 - 28)
+                },
+                _ => {
+                    self.player_x = 145.0;
+                    self.player_y = 72.0;
                 }
             }
+            }
+
+            self.player_vx = 0.0;
+            self.player_vy = 0.0;
+
+            // Reset health and state, letting go of any rope or ladder and ending a dash
+            self.player_hp = self.player_max_hp;
+            self.player_state = STATE_IDLE;
+            self.player_on_ground = true;
+            self.player_rope = None;
+            self.player_on_ladder = false;
+            self.player_dash_timer = 0;
+
+            // Grant 3 seconds of invulnerability after respawn
+            self.player_invuln_timer = 180;
+
+            // Reset animation
+            self.player_anim_frame = 0;
+            self.player_anim_timer = 0;
+        } else {
+            // No lives left AND health is 0 - Game Over!
+            // Trigger game over: lives <= 0 and player_hp <= 0
+            self.show_game_over = true;
+            self.game_over_timer = 0;
+            self.player_state = STATE_DEAD;
+            self.sound.play(CUE_GAME_OVER);
+            log!("Game Over! Lives: {}, HP: {}", self.lives, self.player_hp);
         }
     }
 
-    fn check_enemy_collisions(&mut self) {
-        // Decrement invulnerability timer
-        if self.player_invuln_timer > 0 {
-            self.player_invuln_timer -= 1;
-            return;  // Can't take damage while invulnerable
+    fn handle_input(&mut self) {
+        let kb = keyboard::get();
+
+        // Toggle controls panel with 'S'
+        if kb.key_s().just_pressed() {
+            self.show_controls_panel = !self.show_controls_panel;
         }
 
-        // Only check collisions when alive
-        if self.player_state == STATE_DEAD {
+        // Consumables: Q cycles the selection, E uses it
+        if kb.key_q().just_pressed() {
+            self.cycle_item();
+        }
+        if kb.key_e().just_pressed() {
+            self.use_item();
+        }
+
+        let input = PlayerInput {
+            left: kb.arrow_left().pressed(),
+            right: kb.arrow_right().pressed(),
+            up: kb.arrow_up().pressed(),
+            down: kb.arrow_down().pressed(),
+            jump_pressed: kb.key_x().just_pressed(),
+            jump_held: kb.key_x().pressed(),
+            attack: kb.key_z().just_pressed(),
+            attack_held: kb.key_z().pressed(),
+            place_bomb: kb.key_c().just_pressed(),
+            melee_held: kb.shift_left().pressed() || kb.shift_right().pressed(),
+            run_held: kb.key_a().pressed(),
+            dash_pressed: kb.key_d().just_pressed(),
+        };
+        self.apply_player_input(input);
+    }
+
+    fn apply_player_input(&mut self, input: PlayerInput) {
+        let PlayerInput {
+            left, right, up, down, jump_pressed, jump_held, attack, attack_held,
+            place_bomb, melee_held, run_held, dash_pressed,
+        } = input;
+
+        // Charge shot: holding Z builds charge, releasing a full charge fires it
+        let charged_release = !attack_held && self.player_charge >= CHARGE_SHOT_TIME;
+        if attack_held {
+            self.player_charge = (self.player_charge + 1).min(CHARGE_SHOT_TIME);
+        } else {
+            self.player_charge = 0;
+        }
+
+        // Don't allow input during attack or hurt states
+        if self.player_state == STATE_ATTACK || self.player_state == STATE_MELEE || self.player_state == STATE_HURT {
             return;
         }
 
-        // Check collision with each active enemy
+        // Committed to a dash until it ends (update_player moves Santa)
+        if self.player_dash_timer > 0 {
+            return;
+        }
+        if self.player_dash_cooldown > 0 {
+            self.player_dash_cooldown -= 1;
+        }
 
-        // Check collision with each active enemy
-        for enemy in self.enemies.iter() {
-            // Skip if enemy is dead/respawning (respawn_timer > 0)
-            if enemy.8 && enemy.11 == 0 {  // active AND alive (not respawning)
-                let dx = (self.player_x - enemy.1).abs();
-                let dy = (self.player_y - enemy.2).abs();
-                
-                // Collision threshold: 20px horizontal, 20px vertical
-                if dx < 20.0 && dy < 20.0 {
-                    // Apply damage: 10% of max HP = 1 HP from max 10
-                    let damage = ((self.player_max_hp as f32 * 0.1).ceil() as u8).max(1);
-                    self.player_hp = self.player_hp.saturating_sub(damage);
-                    let hurt_sfx = if self.frame % 2 == 0 { "santa_hurt_1" } else { "santa_hurt_2" };
-                    audio::play(hurt_sfx);
-                    
-                    // Set invulnerability frames (2 seconds at 60fps)
-                    self.player_invuln_timer = 120;
-                    
-                    // Knockback effect
-                    if enemy.1 < self.player_x {
-                        self.player_vx = 3.0;  // Push right
-                    } else {
-                        self.player_vx = -3.0; // Push left
-                    }
-                    self.player_vy = -3.0;  // Push up slightly
-                    
-                    // Death is handled in update() loop
-                    // (handle_death will be called there)
-                    
-                    break;  // Only one hit per frame
-                }
+        // Check if player is near a ladder
+        let on_ladder = self.check_ladder_collision();
+
+        // ==================== CLIMBING LOGIC ====================
+        // Ladder tops are one-way platforms, Down climbs back on from the top
+        if down && self.player_on_ground && !self.player_on_ladder {
+            if let Some(i) = self.ladder_top_at(self.player_x, self.player_y + 19.0) {
+                self.player_on_ladder = true;
+                self.player_on_ground = false;
+                self.player_state = STATE_CLIMB;
+                self.player_x = self.ladders[i].0;
+                self.player_vx = 0.0;
+                self.player_vy = 0.0;
             }
         }
-    }
 
-    fn update_enemies(&mut self) {
-        for i in 0..self.enemies.len() {
-            // Skip if enemy type is 0 (not initialized)
-            if self.enemies[i].0 == 0 {
-                continue;
-            }
-            
-            // Handle respawn timer (enemy.11 = respawn_timer)
-            if self.enemies[i].11 > 0 {
-                self.enemies[i].11 -= 1;
-                
-                // Cloud animation calculated on-the-fly in draw_enemies()
-                // during last 120 frames before respawn
-                
-                // Respawn complete - reset enemy to patrol start position
-                if self.enemies[i].11 == 0 {
-                    self.enemies[i].1 = self.enemies[i].7 as f32 + 50.0;  // Reset X to patrol center
-                    self.enemies[i].6 = 0;  // Reset animation frame
-                    self.enemies[i].9 = 0;  // Reset animation timer
-                    self.enemies[i].10 = false;  // Not attacking
-                }
-                
-                continue;  // Skip normal AI while dead/respawning
+        if on_ladder {
+            // Press UP to start climbing up, or DOWN to start climbing down
+            // (Up does nothing standing on a ladder's top, there's nothing left to climb)
+            let at_ladder_top = self.player_on_ground && self.ladder_top_at(self.player_x, self.player_y + 19.0).is_some();
+            if (down || (up && !at_ladder_top)) && !self.player_on_ladder {
+                self.player_on_ladder = true;
+                self.player_state = STATE_CLIMB;
+                self.player_vy = 0.0;
+                self.player_vx = 0.0;
             }
-            
-            // Only process active enemies that are alive
-            if self.enemies[i].8 {
-                let enemy_type = self.enemies[i].0;
+        }
 
-                // Attack detection for kickmouse (type 2) and penguin (type 3)
-                if enemy_type == 2 || enemy_type == 3 {
-                    let dx = (self.player_x - self.enemies[i].1).abs();
-                    let dy = (self.player_y - self.enemies[i].2).abs();
+        // Handle ladder climbing movement
+        if self.player_on_ladder {
+            // Force exit ladder if standing on solid ground and not pressing up/down
+            if self.player_on_ground && !up && !down {
+                self.player_on_ladder = false;
+                self.player_state = STATE_IDLE;
+                self.player_vy = 0.0;
+                self.player_anim_frame = 0;
+                self.player_anim_timer = 0;
+                // Don't return, let normal movement take over
+            } else {
+                self.player_vx = 0.0;  // No horizontal movement while climbing
 
-                    // Trigger attack if player is within range
-                    let attack_range_h = if enemy_type == 3 { 30.0 } else { 10.0 }; // Penguin: 30px, Kickmouse: 10px
-                    let attack_range_v = if enemy_type == 3 { 10.0 } else { 20.0 }; // Penguin: 10px, Kickmouse: 20px
-                    if dx < attack_range_h && dy < attack_range_v && !self.enemies[i].10 {
-                        self.enemies[i].10 = true;  // Start attack
-                        self.enemies[i].6 = 0;  // Reset animation to frame 0
-                        self.enemies[i].9 = 0;  // Reset timer
-                        
-                        // Penguin always faces Santa when attacking
-                        if enemy_type == 3 {
-                            // Set direction based on Santa's position relative to penguin
-                            self.enemies[i].5 = if self.player_x > self.enemies[i].1 { 1 } else { 0 };
-                            
-                            // Spawn snowball projectile toward Santa
-                            let penguin_x = self.enemies[i].1;
-                            let penguin_y = self.enemies[i].2;
-                            let snowball_speed = 3.0;
-                            let vx = if self.player_x > penguin_x { snowball_speed } else { -snowball_speed };
-                            
-                            // Find an empty slot for snowball
-                            for snowball in self.snowballs.iter_mut() {
-                                if !snowball.0 {
-                                    *snowball = (true, penguin_x, penguin_y, vx, 0.0);
-                                    break;
-                                }
-                            }
-                        }
-                    }
-                }
-
-                // Patrol AI (only when not attacking)
-                if !self.enemies[i].10 {
-                    if enemy_type == 1 || enemy_type == 2 || enemy_type == 3 {
-                        // Store old position for collision detection
-                        let old_x = self.enemies[i].1;
-                        let patrol_width = if self.level == 3 {
-                                                60.0   // boss level: tight platforms
-                                        } else {
-                                                100.0  // level 1 & 2: existing behavior
-                                        };
-                        if self.enemies[i].5 == 0 {
-                            self.enemies[i].1 -= 0.75;
-                            if self.enemies[i].1 < self.enemies[i].7 as f32 {
-                                self.enemies[i].5 = 1;
-                            }
-                        } else {
-                            self.enemies[i].1 += 0.75;
-                            if self.enemies[i].1 > (self.enemies[i].7 as f32 + patrol_width) {
-                                self.enemies[i].5 = 0;
-                            }
-                        }
-
-                        // Check wall collision (enemy is 16px wide, centered)
-                        let enemy_left = self.enemies[i].1 - 8.0;
-                        let enemy_right = self.enemies[i].1 + 8.0;
-                        let enemy_top = self.enemies[i].2 - 8.0;
-                        let enemy_bottom = self.enemies[i].2 + 8.0;
-
-                        for (wx, wy, ww, wh) in self.walls.iter() {
-                            if *ww > 0.0 && *wh > 0.0 {
-                                let wall_left = *wx;
-                                let wall_right = *wx + *ww;
-                                let wall_top = *wy;
-                                let wall_bottom = *wy + *wh;
-
-                                // AABB collision check
-                                if enemy_right > wall_left && enemy_left < wall_right &&
-                                   enemy_bottom > wall_top && enemy_top < wall_bottom {
-                                    // Hit a wall! Reverse direction and restore position
-                                    self.enemies[i].1 = old_x;
-                                    self.enemies[i].5 = 1 - self.enemies[i].5;  // Flip direction
-                                    break;
-                                }
-                            }
-                        }
-                    }
+                if up {
+                    self.player_vy = -CLIMB_SPEED;
+                    self.player_state = STATE_CLIMB;
+                } else if down {
+                    self.player_vy = CLIMB_SPEED;
+                    self.player_state = STATE_CLIMB;
+                } else {
+                    self.player_vy = 0.0;
                 }
 
-                // Update animation
-                self.enemies[i].9 += 1; // anim_timer
-
-                if enemy_type == 1 {
-                    // Enemy1 (easy mouse): 8 frames, 8 frames per sprite
-                    if self.enemies[i].9 >= 8 {
-                        self.enemies[i].9 = 0;
-                        self.enemies[i].6 = (self.enemies[i].6 + 1) % 8; // anim_frame cycles 0-7
-                    }
-                } else if enemy_type == 2 || enemy_type == 3 {
-                    // Kickmouse and Penguin: Handle attack or walk animation
-                    if self.enemies[i].10 {
-                        // Attack animation: 3 frames, 10 ticks per frame (slower)
-                        if self.enemies[i].9 >= 10 {
-                            self.enemies[i].9 = 0;
-                            self.enemies[i].6 += 1;
-
-                            // After 3 attack frames, return to walk
-                            if self.enemies[i].6 >= 3 {
-                                self.enemies[i].10 = false;  // End attack
-                                self.enemies[i].6 = 0;  // Reset to walk frame 0
-                            }
-                        }
+                // Jump off ladder with X key + left/right
+                if jump_pressed && (left || right) {
+                    self.player_on_ladder = false;
+                    self.player_vy = JUMP_VEL * 0.7;  // Smaller jump when jumping off ladder
+                    self.player_state = STATE_JUMP;
+                    self.jump_anim_frame = 0;
+                    self.jump_anim_timer = 0;
+                    self.sound.play(CUE_JUMP);
+                    if left {
+                        self.player_facing_right = false;
+                        self.player_vx = -WALK_SPEED;
                     } else {
-                        // Walk animation: 8 frames, 8 ticks per frame
-                        if self.enemies[i].9 >= 8 {
-                            self.enemies[i].9 = 0;
-                            self.enemies[i].6 = (self.enemies[i].6 + 1) % 8; // anim_frame cycles 0-7
-                        }
+                        self.player_facing_right = true;
+                        self.player_vx = WALK_SPEED;
                     }
                 }
-            }
-        }
-    }
 
-    fn update_snowballs(&mut self) {
-        // Update each active snowball
-        for snowball in self.snowballs.iter_mut() {
-            if snowball.0 {
-                // Move snowball
-                snowball.1 += snowball.3;  // x += vx
-                snowball.2 += snowball.4;  // y += vy (gravity can be added later)
-                
-                // Check collision with Santa (if not invulnerable)
-                if self.player_invuln_timer == 0 && self.player_state != STATE_DEAD {
-                    let dx = (snowball.1 - self.player_x).abs();
-                    let dy = (snowball.2 - self.player_y).abs();
-                    
-                    // Collision check: 16px radius
-                    if dx < 16.0 && dy < 16.0 {
-                        // Hit Santa! Deal 1 HP damage
-                        self.player_hp = self.player_hp.saturating_sub(1);
-                        
-                        // Play hurt sound
-                        let hurt_sfx = if self.frame % 2 == 0 { "santa_hurt_1" } else { "santa_hurt_2" };
-                        audio::play(hurt_sfx);
-                        
-                        // Grant invulnerability frames (1 second)
-                        self.player_invuln_timer = 60;
-                        
-                        // Small knockback from snowball direction
-                        if snowball.3 > 0.0 {
-                            self.player_vx = 2.0;  // Push right
-                        } else {
-                            self.player_vx = -2.0; // Push left
-                        }
-                        self.player_vy = -2.0;  // Small upward push
-                        
-                        // Deactivate snowball
-                        snowball.0 = false;
-                        continue;
-                    }
-                }
-                
-                // Despawn if off-screen (traveled too far)
-                if snowball.1 < self.camera_x - 50.0 || snowball.1 > self.camera_x + SCREEN_WIDTH + 50.0 {
-                    snowball.0 = false;
+                // Shoot left/right without letting go
+                if charged_release || (attack && self.attack_cooldown == 0) {
+                    self.shoot_while_holding_on(left, right, charged_release);
                 }
-            }
-        }
-    }
 
-    fn check_gift_bomb_pickups(&mut self) {
-        for item in self.gift_bomb_items.iter_mut() {
-            if item.2 {  // If active
-                // Countdown despawn timer
-                if item.3 > 0 {
-                    item.3 -= 1;
-                } else {
-                    // Timer expired - despawn the gift bomb
-                    item.2 = false;
-                    continue;
-                }
-                
-                // Check for pickup collision
-                let dx = (self.player_x - item.0).abs();
-                let dy = (self.player_y - item.1).abs();
-                if dx < 20.0 && dy < 20.0 && self.gift_bombs < 1 {
-                    // Only pick up if we don't already have a bomb
-                    item.2 = false;  // Deactivate the item
-                    self.gift_bombs = 1;  // Set to 1 bomb
-                    self.score += 50;
-                    audio::play("collection");
+                // Exit ladder if no longer overlapping
+                if !on_ladder {
+                    self.player_on_ladder = false;
                 }
+
+                return;  // Skip normal movement while on ladder
             }
         }
-    }
 
-    fn update_placed_bombs(&mut self) {
-        for i in 0..self.placed_bombs.len() {
-            if self.placed_bombs[i].2 {  // If active
-                self.placed_bombs[i].4 += 1;  // Increment anim_timer
+        // ==================== ROPES ====================
+        if self.player_rope.is_some() {
+            if jump_pressed || down {
+                // Let go
+                self.player_rope = None;
+                self.player_vy = 0.0;
+                self.player_state = STATE_FALL;
+                return;
+            }
 
-                // New bomb animation sequence (5+ seconds total):
-                // Frame 0 (idle): 60 frames (1 second)
-                // Frame 1-4 (sprites 2-5, first cycle): 30 frames each (2 seconds) - WARNING
-                // Frame 5-8 (sprites 2-5, second cycle): 30 frames each (2 seconds) - WARNING
-                // Frame 9-11 (sprites 6-8): 10 frames each (0.5 seconds) - EXPLOSION
+            // Hand over hand along the rope (update_player keeps Santa under it)
+            if left && !right {
+                self.player_vx = -HANG_SPEED;
+                self.player_facing_right = false;
+            } else if right && !left {
+                self.player_vx = HANG_SPEED;
+                self.player_facing_right = true;
+            } else {
+                self.player_vx = 0.0;
+            }
+            self.player_state = STATE_HANG;
 
-                if self.placed_bombs[i].3 == 0 {
-                    // Idle phase - 1 second
-                    if self.placed_bombs[i].4 >= 60 {
-                        self.placed_bombs[i].4 = 0;
-                        self.placed_bombs[i].3 = 1;  // Move to warning phase
-                    }
-                } else if self.placed_bombs[i].3 >= 1 && self.placed_bombs[i].3 <= 8 {
-                    // Warning phase - frames 2-5 played twice (frames 1-8)
-                    if self.placed_bombs[i].4 >= 30 {
-                        self.placed_bombs[i].4 = 0;
-                        self.placed_bombs[i].3 += 1;
-                    }
-                } else if self.placed_bombs[i].3 == 9 {
-                    // EXPLOSION STARTS - Frame 6 (sprite 6)
-                    if self.placed_bombs[i].4 == 1 {
-                        // Apply damage on first frame of explosion
-                        self.check_bomb_damage(i);
-                        audio::play("explosion");
-                    }
-                    if self.placed_bombs[i].4 >= 10 {
-                        self.placed_bombs[i].4 = 0;
-                        self.placed_bombs[i].3 = 10;
-                    }
-                } else if self.placed_bombs[i].3 >= 10 && self.placed_bombs[i].3 <= 11 {
-                    // Explosion frames 7-8
-                    if self.placed_bombs[i].4 >= 10 {
-                        self.placed_bombs[i].4 = 0;
-                        self.placed_bombs[i].3 += 1;
-                        if self.placed_bombs[i].3 > 11 {
-                            // Animation complete, deactivate bomb
-                            self.placed_bombs[i].2 = false;
-                        }
-                    }
-                }
+            if charged_release || (attack && self.attack_cooldown == 0) {
+                self.shoot_while_holding_on(left, right, charged_release);
             }
+            return;
         }
-    }
-
-    fn check_bomb_damage(&mut self, bomb_idx: usize) {
-        let bomb_x = self.placed_bombs[bomb_idx].0;
-        let bomb_y = self.placed_bombs[bomb_idx].1;
 
-        // Check if Santa is in explosion radius
-        let dx_player = (bomb_x - self.player_x).abs();
-        let dy_player = (bomb_y - self.player_y).abs();
+        // Jumping into a rope (or holding Up next to one) grabs it
+        let rope = if !self.player_on_ground && !self.player_on_ladder && !down && (up || self.player_vy < 0.0) {
+            self.rope_at(self.player_x, self.player_y)
+        } else {
+            None
+        };
+        if let Some(i) = rope {
+            self.player_rope = Some(i);
+            self.player_vx = 0.0;
+            self.player_vy = 0.0;
+            self.player_state = STATE_HANG;
+            self.player_wall_jump_lock = 0;
+            self.player_anim_frame = 0;
+            self.player_anim_timer = 0;
+            return;
+        }
 
-        if dx_player < 50.0 && dy_player < 50.0 {
-            // Santa is caught in the explosion!
-            if self.player_invuln_timer == 0 {
-                 self.player_hp = self.player_hp.saturating_sub(2);  // Take 2 damage
-                let hurt_sfx = if self.frame % 2 == 0 { "santa_hurt_1" } else { "santa_hurt_2" };
-                audio::play(hurt_sfx);
-                self.player_invuln_timer = 60;  // 1 second invulnerability
+        // ==================== DASH / SLIDE ====================
+        if dash_pressed && self.player_dash_cooldown == 0 && !self.player_on_ladder {
+            // Arrow held picks the direction, otherwise the way Santa faces
+            let dir = if left && !right {
+                -1.0
+            } else if (right && !left) || self.player_facing_right {
+                1.0
+            } else {
+                -1.0
+            };
+            let slide = down && self.player_on_ground;
+            self.player_facing_right = dir > 0.0;
+            self.player_vx = DASH_SPEED * dir;
+            self.player_vy = 0.0;
+            self.player_state = if slide { STATE_SLIDE } else { STATE_DASH };
+            self.player_dash_timer = if slide { SLIDE_FRAMES } else { DASH_FRAMES };
+            self.player_dash_cooldown = DASH_COOLDOWN;
+            self.player_is_crouching = false;
+            self.player_invuln_timer = self.player_invuln_timer.max(DASH_INVULN);
+            return;
+        }
 
-                if self.player_hp == 0 {
-                    self.player_state = STATE_DEAD;
-                    self.lives = self.lives.saturating_sub(1);
-                }
+        // ==================== CROUCHING LOGIC ====================
+        // Stay down while something low is overhead
+        let stuck_low = self.player_is_crouching && self.ceiling_blocked();
+        if (down || stuck_low) && self.player_on_ground && !self.player_on_ladder {
+            self.player_is_crouching = true;
+            self.player_state = STATE_CROUCH;
+            // Can still move slowly while crouching
+            let crouch_target = if left && !right {
+                -CROUCH_SPEED
+            } else if right && !left {
+                CROUCH_SPEED
+            } else {
+                0.0
+            };
+            self.player_vx = crouch_target;
+            
+            if left && !right {
+                self.player_facing_right = false;
+            } else if right && !left {
+                self.player_facing_right = true;
             }
+            return;
+        } else {
+            self.player_is_crouching = false;
         }
 
-        // Check all enemies for collision with bomb explosion
-        // Explosion radius is approximately 50 pixels
-        for enemy_idx in 0..self.enemies.len() {
-            if self.enemies[enemy_idx].8 && self.enemies[enemy_idx].11 == 0 {  // Active and alive
-                let enemy_x = self.enemies[enemy_idx].1;
-                let enemy_y = self.enemies[enemy_idx].2;
-
-                let dx = (bomb_x - enemy_x).abs();
-                let dy = (bomb_y - enemy_y).abs();
+        // ==================== HORIZONTAL MOVEMENT ====================
+        if left && !right {
+            self.player_facing_right = false;
+        } else if right && !left {
+            self.player_facing_right = true;
+        }
 
-                // Kill enemies within explosion radius
-                if dx < 50.0 && dy < 50.0 {
-                    // Kill the enemy (start respawn timer)
-                    self.enemies[enemy_idx].11 = 600;
+        let physics = self.physics_at(self.player_x, self.player_y);
+        let profile = self.movement();
+        let mut max_speed = if run_held { physics.walk_speed * RUN_SPEED / WALK_SPEED } else { physics.walk_speed };
+        if !self.player_on_ground {
+            // Jumps carry further than a walk
+            max_speed *= profile.air_speed_scale;
+        }
+        let target_speed = if left && !right {
+            -max_speed
+        } else if right && !left {
+            max_speed
+        } else {
+            0.0
+        };
 
-                    // Add score for bomb kill
-                    self.score += 150;
+        // Apply acceleration/deceleration (ice cuts both)
+        if self.player_on_ground {
+            let grip = if self.floor_hazard(self.player_x, self.player_y + 19.0).is_some_and(|(kind, _)| kind == HAZARD_ICE) {
+                ICE_FRICTION
+            } else {
+                1.0
+            };
+            if target_speed.abs() > 0.01 {
+                if (target_speed > 0.0 && self.player_vx < target_speed) ||
+                   (target_speed < 0.0 && self.player_vx > target_speed) {
+                    self.player_vx += target_speed.signum() * profile.accel * grip;
+                }
+            } else {
+                if self.player_vx.abs() > profile.decel * grip {
+                    self.player_vx -= self.player_vx.signum() * profile.decel * grip;
+                } else {
+                    self.player_vx = 0.0;
                 }
             }
+        } else if self.player_wall_jump_lock > 0 {
+            // Kicked off a wall, hold the arc for a moment
+            self.player_wall_jump_lock -= 1;
+        } else {
+            // Air control
+            if target_speed.abs() > 0.01 {
+                if (target_speed > 0.0 && self.player_vx < target_speed) ||
+                   (target_speed < 0.0 && self.player_vx > target_speed) {
+                    self.player_vx += target_speed.signum() * profile.air_accel;
+                }
+            } else if self.player_vx.abs() > profile.air_decel {
+                self.player_vx -= self.player_vx.signum() * profile.air_decel;
+            } else {
+                self.player_vx = 0.0;
+            }
         }
 
-        // Check all doors for collision with bomb explosion
-        // Doors are destroyed if within explosion radius
-        for door_idx in 0..self.doors.len() {
-            if self.doors[door_idx].2 {  // If door is still intact
-                let door_x = self.doors[door_idx].0;
-                let door_y = self.doors[door_idx].1;
-                
-                // Door is 42x42, so center is at (x+21, y+21)
-                let door_center_x = door_x + 21.0;
-                let door_center_y = door_y + 21.0;
-                
-                let dx = (bomb_x - door_center_x).abs();
-                let dy = (bomb_y - door_center_y).abs();
-                
-                // Destroy door if within explosion radius
-                if dx < 50.0 && dy < 50.0 {
-                    self.doors[door_idx].2 = false;  // Mark as destroyed
-                    self.score += 200;  // Bonus score for destroying door
+        self.player_vx = self.player_vx.clamp(-max_speed, max_speed);
 
-                    // Check if this door spawns a life powerup (door #3 in level 3)
-                    if self.level == 3 && door_idx == self.life_door_index && !self.life_collected {
-                        // Spawn life powerup at door position
-                        self.life_position = (door_center_x, door_center_y - 10.0);
-                        self.life_active = true;
-                        log!("Life powerup spawned from door {}!", door_idx);
-                    }
-                    // Check if this door spawns powerup1 (door #4 in level 3)
-                    else if self.level == 3 && door_idx == 3 && !self.powerup1_collected {
-                        // Spawn powerup1 at door position
-                        self.powerup1_position = (door_center_x, door_center_y - 10.0);
-                        self.powerup1_active = true;
-                        log!("PowerUp1 spawned from door {}!", door_idx);
-                    }
-                    // Check if this door spawns a kid
-                    // For level 3: doors 0 and 1 spawn KIDS (door 0 triggers victory when collected)
-                    // For other levels: kid_door_index spawns a kid  
-                    else if (self.level == 3 && (door_idx == 0 || door_idx == 1)) || (self.level != 3 && door_idx == self.kid_door_index) {
-                        // Spawn a kid at the door position
-                        // Find first available kid slot
-                        for kid_idx in 0..self.kids.len() {
-                            if !self.kids[kid_idx].2 && !self.kids[kid_idx].3 {
-                                let spawn_x = door_center_x;
-                                let spawn_y = door_center_y - 10.0;
-                                self.kids[kid_idx] = (spawn_x, spawn_y, true, false, 0, 0, door_idx as i8);
-                                self.total_kids_in_level += 1;
-                                log!("Kid spawned from door {}!", door_idx);
-                                break;
-                            }
-                        }
-                    } else {
-                        // Spawn a key at the door position (slightly above center)
-                        // Use first available slot and associate with door index
-                        for k in 0..self.keys.len() {
-                            if !self.keys[k].2 {
-                                let spawn_x = door_center_x;
-                                let spawn_y = door_center_y - 10.0;
-                                self.keys[k] = (spawn_x, spawn_y, true, 0, 0);
-                                break;
-                            }
-                        }
-                    }
-                }
-            }
+        // ==================== JUMPING LOGIC ====================
+        // Input buffering for jump
+        if jump_pressed {
+            self.player_jump_buffer = INPUT_BUFFER;
+        } else if self.player_jump_buffer > 0 {
+            self.player_jump_buffer -= 1;
         }
-    }
 
-    fn update_camera(&mut self) {
-        // During boss fight, camera is fixed (no scrolling)
-        if self.boss_active {
-            self.camera_x = 0.0;
-            return;
+        // Landing (or a wall slide) gives the air jumps back
+        if self.player_on_ground || self.player_wall_sliding {
+            self.player_air_jumps = profile.air_jumps;
         }
 
-        let target_x = self.player_x - (SCREEN_WIDTH / 2.0);
-        // Clamp camera to level bounds (0 to level_width - screen_width)
-        let level_width = match self.level {
-            1 => 1080.0,  // Level 1 width
-            2 => 1440.0,  // Level 2 width
-            3 => 2160.0,  // Boss fight width (before trigger)
-            _ => 1080.0,  // Default
-        };
-        let max_camera = level_width - SCREEN_WIDTH;
-        self.camera_x = target_x.max(0.0).min(max_camera);
-    }
+        // Execute jump if buffer valid and grounded (or coyote time), swimmers can always stroke
+        if self.player_jump_buffer > 0 && (self.player_on_ground || self.player_coyote_timer > 0 || physics.swim) {
+            self.player_vy = physics.jump_vel * profile.jump_scale;
+            self.player_state = STATE_JUMP;
+            self.player_jump_buffer = 0;
+            self.player_on_ground = false;
+            self.player_coyote_timer = 0;
+            // Reset jump animation
+            self.jump_anim_frame = 0;
+            self.jump_anim_timer = 0;
+            self.sound.play(CUE_JUMP);
+        }
 
-    fn load_level(&mut self, level_num: u8) {
-        self.level = level_num;
-        self.timer = LEVEL_TIME;
-        
-        // Set level time limits (in frames, 60 fps)
-        self.level_timer = 0;
-        self.level_time_limit = match level_num {
-            1 => 180 * 60,  // 3 minutes
-            2 => 480 * 60,  // 8 minutes
-            3 => 600 * 60,  // 10 minutes
-            _ => 180 * 60,  // Default 3 minutes
-        };
+        // ==================== WALL SLIDE / WALL JUMP ====================
+        // Sliding = airborne, falling and pushing into the wall touched last frame
+        let pushing_wall = (left && self.player_wall_side < 0) || (right && self.player_wall_side > 0);
+        self.player_wall_sliding = self.has_wall_jump && !self.player_on_ground && !self.player_on_ladder
+            && pushing_wall && self.player_vy >= 0.0;
+        if self.player_wall_sliding {
+            self.player_wall_coyote = WALL_COYOTE_TIME;
+            self.player_wall_coyote_side = self.player_wall_side;
+        } else if self.player_on_ground {
+            self.player_wall_coyote = 0;
+        }
 
-        for enemy in self.enemies.iter_mut() {
-            enemy.8 = false;
+        // A buffered jump that the ground didn't take kicks off the wall
+        if self.player_jump_buffer > 0 && self.has_wall_jump && self.player_wall_coyote > 0 {
+            let away = -self.player_wall_coyote_side as f32;
+            self.player_vy = physics.jump_vel * profile.jump_scale;
+            self.player_vx = physics.walk_speed * away;
+            self.player_facing_right = away > 0.0;
+            self.player_state = STATE_WALL_JUMP;
+            self.player_wall_jump_lock = WALL_JUMP_LOCK;
+            self.player_wall_sliding = false;
+            self.player_wall_coyote = 0;
+            self.player_jump_buffer = 0;
+            self.player_coyote_timer = 0;
+            self.jump_anim_frame = 0;
+            self.jump_anim_timer = 0;
+            self.sound.play(CUE_JUMP);
         }
 
-        match level_num {
-            1 => {
-                // Level 1 - 3 Floor Christmas House (based on spritebg1080x240.png)
-                // Using precise tilemap from sprites/tilemap.json
-                // Platforms are the ONLY ground - no hardcoded floor
+        // Counted down after the kick so all WALL_COYOTE_TIME frames off the wall can use it
+        if !self.player_wall_sliding && self.player_wall_coyote > 0 {
+            self.player_wall_coyote -= 1;
+        }
 
-                // Santa starts on TILE #1 (left section, top floor)
-                // Offset applied: +60px X, +18px Y
-                // Tile #1: X(60-230) at Y=100
-                // Santa sprite is 32x32, positioned from center
-                // Platform Y=100, so Santa Y = 100 - 28 = 72 (28px hitbox height)
-                self.player_x = 145.0;  // Center of tile #1 (60-230)
-                self.player_y = 72.0;   // Tile #1 platform position
-                self.player_vx = 0.0;
-                self.player_vy = 0.0;
-                self.player_state = STATE_IDLE;
-                self.player_on_ground = true; // Start on platform
+        // Still nothing took the press: spend an air jump (double jump). Right above
+        // a floor the press stays buffered for the ground jump instead
+        if self.player_jump_buffer > 0 && self.player_air_jumps > 0 && !self.player_on_ground && !self.landing_soon() {
+            self.player_vy = physics.jump_vel * profile.jump_scale * profile.air_jump_scale;
+            self.player_air_jumps -= 1;
+            self.player_state = STATE_JUMP;
+            self.player_jump_buffer = 0;
+            self.player_coyote_timer = 0;
+            self.jump_anim_frame = 0;
+            self.jump_anim_timer = 0;
+            self.sound.play(CUE_JUMP);
+        }
 
-                // ============================================================
-                // PLATFORM POSITIONS - From sprites/layout.md tilemap
-                // ============================================================
-                // Background: 1080x240px, 3 floors visible
-                // Positions calculated from center (540, 120) with layout dimensions
-                // Format: (x_start, x_end, y_position)
+        // Variable jump height - release early for shorter jump
+        if !jump_held && self.player_vy < 0.0 {
+            self.player_vy *= profile.jump_cut;
+        }
 
-                // Clear and populate platforms for Level 1
-                self.platforms.clear();
+        // ==================== ATTACK LOGIC ====================
+        // Place gift bomb with C key
+        if place_bomb && self.gift_bombs > 0 && up {
+            // Up+C lobs the bomb in an arc, works in the air too
+            self.place_gift_bomb(true);
+        } else if place_bomb && self.gift_bombs > 0 && self.player_on_ground {
+            self.place_gift_bomb(false);
+        } else if charged_release {
+            // Charged shots ignore the cooldown, the charge time already paid for it
+            self.player_state = STATE_ATTACK;
+            self.attack_frame = 0;
+            self.charged_shot_pending = true;
+        } else if attack && self.attack_cooldown == 0 {
+            if melee_held || self.enemy_in_melee_range() {
+                self.player_state = STATE_MELEE;
+                self.melee_hit_boss = false;
+            } else {
+                self.player_state = STATE_ATTACK;
+            }
+            self.attack_frame = 0;
+        }
+    }
 
-                // TOP FLOOR (Y ≈ 100) - platforms from tiles 1, 3, 4, 8, 14
-                self.platforms.push((60.0, 230.0, 86.0));         // Tile #1
-                self.platforms.push((275.0, 300.0, 95.0-9.0));    // Tile #3
-                self.platforms.push((400.0, 765.0, 76.0-9.0));    // Tile #4
-                self.platforms.push((804.0, 920.0, 95.0-9.0));    // Tile #8
-                self.platforms.push((931.0, 1045.0, 95.0-9.0));   // Tile #14
+    // Fire a shot from a ladder or rope. Left/Right picks the side, Santa keeps his grip
+    fn shoot_while_holding_on(&mut self, left: bool, right: bool, charged: bool) {
+        if left && !right {
+            self.player_facing_right = false;
+        } else if right && !left {
+            self.player_facing_right = true;
+        }
+        self.player_vx = 0.0;
+        self.player_vy = 0.0;
+        self.player_state = STATE_ATTACK;
+        self.attack_frame = 0;
+        if charged {
+            self.charged_shot_pending = true;
+        }
+    }
 
-                // MIDDLE FLOOR (Y ≈ 168) - platforms from tiles 5, 6, 9, 11, 12, 13
-                self.platforms.push((60.0, 252.0, 163.0-9.0));    // Tile #13
-                self.platforms.push((264.0, 495.0, 163.0-9.0));   // Tile #12
-                self.platforms.push((542.0, 654.0, 163.0-9.0));   // Tile #11
-                self.platforms.push((665.0, 768.0, 163.0-9.0));   // Tile #9
-                self.platforms.push((806.0, 1008.0, 163.0-9.0));  // Tile #5
-                self.platforms.push((1020.0, 1043.0, 163.0-9.0)); // Tile #6
+    // Check if player is overlapping with any ladder
+    fn check_ladder_collision(&self) -> bool {
+        self.ladder_at().is_some()
+    }
 
-                // BOTTOM FLOOR (Y ≈ 238) - platforms from tiles 7, 15
-                self.platforms.push((36.0, 81.0, 233.0-9.0));     // Tile #7
-                self.platforms.push((93.0, 1063.0, 233.0-9.0));   // Tile #15
+    // Index of the ladder Santa overlaps, if any
+    fn ladder_at(&self) -> Option<usize> {
+        let player_center_x = self.player_x;
+        let player_left = self.player_x - 7.0;
+        let player_right = self.player_x + 7.0;
+        let player_top = self.player_y - 19.0;  // Full player height
+        let player_bottom = self.player_y + 19.0;
 
-                // Additional platforms
-                self.platforms.push((310.0, 400.0, 95.0-9.0));
+        for (i, (lx, ly_top, ly_bottom)) in self.ladders.iter().enumerate() {
+            if *lx > 0.0 {  // Valid ladder
+                let ladder_left = *lx - LADDER_GRAB_HALF_WIDTH;
+                let ladder_right = *lx + LADDER_GRAB_HALF_WIDTH;
 
-                //
+                // Check if player overlaps ladder horizontally and vertically
+                // More forgiving: check if ANY part of player overlaps, not just center
+                if player_right > ladder_left && player_left < ladder_right {
+                    if player_bottom >= *ly_top && player_top <= *ly_bottom {
+                        return Some(i);
+                    }
+                }
+            }
+        }
+        None
+    }
 
-                // ============================================================
-                // LADDERS - Connect the three floors
-                // ============================================================
-                // Format: (x_center, y_top, y_bottom)
-                // Offset applied: +60px X, +18px Y
+    // Ladder whose top a pair of feet is standing on
+    fn ladder_top_at(&self, x: f32, feet_y: f32) -> Option<usize> {
+        self.ladders.iter().position(|(lx, ly_top, _)| {
+            *lx > 0.0 && (x - lx).abs() < LADDER_TOP_HALF_WIDTH + 7.0 && (feet_y - ly_top).abs() < 1.0
+        })
+    }
 
-                // Left side ladders
-                self.ladders[0] = (305.0, 85.0, 117.0);    // Top to Middle (left)
-                self.ladders[1] = (258.0, 150.0, 187.0);   // Middle to Bottom (left)
+    // Rope within reach of Santa's hands, for a Santa centered at (x, y)
+    fn rope_at(&self, x: f32, y: f32) -> Option<usize> {
+        let hands_y = y - HANG_OFFSET;
+        LEVEL_ROPES.iter().position(|rope| {
+            rope.level == self.level && x >= rope.x1 && x <= rope.x2
+                && hands_y >= rope.y - ROPE_GRAB_ABOVE && hands_y <= rope.y + ROPE_GRAB_BELOW
+        })
+    }
 
-                // Center ladders
-                self.ladders[2] = (383.0, 85.0, 117.0);    // Top to Middle (center)
-                self.ladders[3] = (660.0, 150.0, 187.0);   // Middle to Bottom (center)
+    fn movement(&self) -> MovementProfile {
+        self.movement_profiles[self.movement_profile].clone()
+    }
 
-                // Right side ladders
-                self.ladders[4] = (925.0, 85.0, 117.0);    // Top to Middle (right)
-                self.ladders[5] = (1015.0, 150.0, 187.0);   // Middle to Bottom (right)
+    // Falling onto a platform within the jump buffer window: a press now should
+    // become a ground jump on landing rather than spend the air jump
+    fn landing_soon(&self) -> bool {
+        if self.player_vy <= 0.0 {
+            return false;
+        }
+        let frames = INPUT_BUFFER as f32;
+        let feet = self.player_y + 19.0;
+        let reach = self.player_vy * frames + GRAVITY * frames * (frames + 1.0) / 2.0;
+        let (left, right) = (self.player_x - 7.0, self.player_x + 7.0);
+        self.platforms.iter().any(|&(x1, x2, y)| x2 > x1 && right > x1 && left < x2 && y >= feet && y <= feet + reach)
+            || self.solid_rects().iter().any(|&(x, y, w, _)| right > x && left < x + w && y >= feet && y <= feet + reach)
+    }
 
-                // ============================================================
-                // ENEMIES - Positioned on platform surfaces
-                // ============================================================
-                // Enemy Y = platform_y - 28 (same formula as Santa!)
-                // This ensures enemies walk at the exact same level as Santa
-                
-                // Top floor at Y=95, so enemy Y = 95 - 28 = 67
-                // Large center platform at Y=76, so enemy Y = 76 - 28 = 48
-                self.enemies[0] = (1, 500.0, 48.0, 0.0, 0.0, 0, 0, 420, true, 0, false, 0);  // On large center platform (Y=76)
-                self.enemies[1] = (2, 870.0, 67.0, 0.0, 0.0, 0, 0, 810, true, 0, false, 0);  // Top-right patrol at Y=95 (kickmouse)
-                self.enemies[5] = (1, 500.0, 135.0, 0.0, 0.0, 0, 0,340, true, 0, false, 0);   // Top-left patrol at Y=95 (kickmouse)
-                self.enemies[8] = (3, 680.0, 48.0, 0.0, 0.0, 0, 0, 600, true, 0, false, 0);  // Large center platform (Y=76)
+    fn update_player(&mut self) {
+        // ==================== ATTACK STATE ====================
+        if self.player_state == STATE_ATTACK {
+            self.attack_frame += 1;
 
-                // Middle floor at Y=163, so enemy Y = 163 - 28 = 135
-                self.enemies[2] = (3, 310.0, 135.0, 0.0, 0.0, 0, 0, 160, true, 0, false, 0); // Middle floor patrol left
-                self.enemies[6] = (1, 600.0, 135.0, 0.0, 0.0, 0, 0, 542, true, 0, false, 0); // Middle floor patrol center
-                self.enemies[7] = (2, 900.0, 135.0, 0.0, 0.0, 0, 0, 940, true, 0, false, 0); // Middle floor patrol right (kickmouse)
+            // Spawn projectile at frame 4 (out of 5 attack frames)
+            if self.attack_frame == 4 {
+                self.spawn_player_projectile();
+            }
 
-                // Bottom floor at Y=233, so enemy Y = 233 - 28 = 205
-                self.enemies[3] = (2, 400.0, 205.0, 0.0, 0.0, 0, 0, 200, true, 0, false, 0); // Ground patrol left (kickmouse)
-                self.enemies[4] = (1, 740.0, 205.0, 0.0, 0.0, 0, 0, 640, true, 0, false, 0); // Ground patrol right
-                self.enemies[9] = (2, 500.0, 205.0, 0.0, 0.0, 0, 0, 400, true, 0, false, 0); // Ground patrol center (kickmouse)
+            // Attack has 5 frames at 4 frames each = 20 frame total
+            if self.attack_frame >= 20 {
+                self.player_state = if self.player_on_ladder {
+                    STATE_CLIMB
+                } else if self.player_rope.is_some() {
+                    STATE_HANG
+                } else {
+                    STATE_IDLE
+                };
+                self.attack_cooldown = 24;
+            }
+            return;
+        }
 
-                // ============================================================
-                // DOORS - Destructible by bombs
-                // ============================================================
-                // Format: (x, y, intact)
-                // x, y are top-left corner of 42x42 door sprite
-                // Door sprite is 42x42, exploded_gate is 64x64
-                self.doors[0] = (96.0, 42.0, true);    // Door #1 - Top left
-                self.doors[1] = (411.0, 111.0, true);  // Door #2 - Middle left
-                self.doors[2] = (676.0, 180.0, true);  // Door #3 - Bottom center
-                self.doors[3] = (990.0, 42.0, true);   // Door #4 - Top right
+        // ==================== MELEE STATE ====================
+        if self.player_state == STATE_MELEE {
+            self.attack_frame += 1;
 
-                // ============================================================
-                // WALLS - Vertical collision boundaries
-                // ============================================================
-                // Format: (x, y, width, height)
-                self.walls[0] = (51.0, 28.0, 8.0, 136.0);    // Wall #1 - Left top vertical
-                self.walls[1] = (27.0, 165.0, 8.0, 60.0);    // Wall #2 - Left middle vertical
-                self.walls[2] = (129.0, 165.0, 23.0, 60.0);  // Wall #3 - Left-center vertical
-                self.walls[3] = (0.0, 0.0, 0.0, 0.0);        // Wall #4 - REMOVED
-                self.walls[4] = (320.0, 98.0, 36.0, 59.0);   // Wall #5 - Center-left vertical
-                self.walls[5] = (542.0, 168.0, 86.0, 60.0);  // Wall #6 - Center vertical
-                self.walls[6] = (675.0, 78.0, 34.0, 78.0);   // Wall #7 - Center-right vertical
-                self.walls[7] = (851.0, 98.0, 34.0, 59.0);   // Wall #8 - Right-center vertical
-                self.walls[8] = (0.0, 0.0, 0.0, 0.0);        // Wall #9 - REMOVED
-                self.walls[9] = (1045.0, 28.0, 8.0, 197.0);  // Wall #10 - Right top vertical
+            if self.attack_frame >= MELEE_HIT_START && self.attack_frame < MELEE_HIT_END {
+                self.apply_melee_hits();
+            }
 
-                // ============================================================
-                // LEVEL COMPLETION TRIGGER
-                // ============================================================
-                // Place trigger zone on the right side of the gate (wall #3)
-                // Gate is at (129, 165, 23, 60)
-                // Trigger at x=160 (just past the gate), covering the passage
-                self.completion_trigger = (160.0, 165.0, 30.0, 60.0);
+            if self.attack_frame >= MELEE_FRAMES {
+                self.player_state = STATE_IDLE;
+                self.attack_cooldown = MELEE_COOLDOWN;
+            }
+            return;
+        }
 
-                // Reset level completion state
-                self.level_complete = false;
-                self.level_transition_timer = 0;
+        if self.attack_cooldown > 0 {
+            self.attack_cooldown -= 1;
+        }
 
-                // Reset player movement state
-                self.player_on_ladder = false;
-                self.player_is_crouching = false;
+        // ==================== CLIMBING STATE ====================
+        if self.player_on_ladder {
+            // Apply ladder movement (no gravity while on ladder)
+            let ladder = self.ladder_at();
+            self.player_y += self.player_vy;
 
-                // ============================================================
-                // KIDS - Level 1
-                // ============================================================
-                // One door will spawn a kid instead of a key (door index 0-3)
-                // Using frame count for pseudo-random selection
-                self.kid_door_index = (self.frame % 4) as usize;
-                self.kids_collected = 0;
-                self.total_kids_in_level = 0;  // Will increment when kid spawns from door
-                for kid in self.kids.iter_mut() {
-                    *kid = (0.0, 0.0, false, false, 0, 0, -1);
+            let player_bottom = self.player_y + 19.0;
+            let player_left = self.player_x - 7.0;
+            let player_right = self.player_x + 7.0;
+
+            // Climbed past the top: step off onto it (ladder tops are platforms)
+            if let Some(i) = ladder {
+                let ly_top = self.ladders[i].1;
+                if self.player_vy < 0.0 && player_bottom <= ly_top {
+                    self.player_y = ly_top - 19.0;
+                    self.player_vy = 0.0;
+                    self.player_on_ground = true;
+                    self.player_on_ladder = false;
+                    self.player_state = STATE_IDLE;
+                    self.player_anim_frame = 0;
+                    self.player_anim_timer = 0;
+                    return;
                 }
-            },
-            2 => {
-                // Level 2 - Rooftop Christmas Adventure (level2finalsprite 1440x240px)
-                // Using exact coordinates from Sprites/layout.md
+            }
 
-                // ============================================================
-                // PLAYER SPAWN - Tile 1 (recommended spawn)
-                // ============================================================
-                self.player_x = 60.0;
-                self.player_y = 86.0 - 19.0;  // 58.0
-                self.player_vx = 0.0;
-                self.player_vy = 0.0;
-                self.player_state = STATE_IDLE;
-                self.player_on_ground = true;
+            // Check for platform collisions when climbing UP
+            if self.player_vy < 0.0 {  // Moving up
+                for (px1, px2, py) in self.platforms.iter() {
+                    if *px2 > *px1 &&
+                       player_right > *px1 &&
+                       player_left < *px2
+                    {
+                        // Platform must be above us
+                        if *py < self.player_y {
+                            let dist_to_platform = player_bottom - *py;
 
-                // ============================================================
-                // PLATFORMS (29 floor tiles) - From layout.md
-                // ============================================================
-                // Format: (x_start, x_end, y) where x_end = x + w
+                            // When feet reach the platform while climbing up
+                            if dist_to_platform >= -2.0 && dist_to_platform <= 4.0 {
+                                self.player_y = *py - 19.0;
+                                self.player_vy = 0.0;
+                                self.player_on_ground = true;
+                                self.player_on_ladder = false;
+                                self.player_state = STATE_IDLE;
+                                // Reset animation to idle
+                                self.player_anim_frame = 0;
+                                self.player_anim_timer = 0;
+                                break;
+                            }
+                        }
+                    }
+                }
+            }
 
-                self.platforms.clear();
-                self.platforms.push((35.0, 126.0, 88.0));      // ID 1
-                self.platforms.push((126.0, 146.0, 115.0));    // ID 2
-                self.platforms.push((146.0, 169.0, 135.0));    // ID 3
-                self.platforms.push((172.0, 257.0, 155.0));    // ID 4
-                self.platforms.push((235.0, 301.0, 88.0));     // ID 5
-                self.platforms.push((313.0, 323.0, 88.0));     // ID 6
-                self.platforms.push((367.0, 479.0, 88.0));     // ID 7
-                self.platforms.push((746.0, 836.0, 88.0));     // ID 8
-                self.platforms.push((847.0, 907.0, 88.0));     // ID 9
-                self.platforms.push((1037.0, 1203.0, 88.0));   // ID 10
-                self.platforms.push((1303.0, 1326.0, 88.0));   // ID 11
-                self.platforms.push((1336.0, 1394.0, 88.0));   // ID 12
-                self.platforms.push((268.0, 367.0, 155.0));    // ID 13
-                self.platforms.push((479.0, 500.0, 115.0));    // ID 14
-                self.platforms.push((542.0, 674.0, 135.0));    // ID 15
-                self.platforms.push((674.0, 746.0, 155.0));    // ID 16
-                self.platforms.push((757.0, 764.0, 155.0));    // ID 17
-                self.platforms.push((813.0, 897.0, 155.0));    // ID 18
-                self.platforms.push((947.0, 990.0, 135.0));    // ID 19
-                self.platforms.push((990.0, 1037.0, 115.0));   // ID 20
-                self.platforms.push((1203.0, 1226.0, 115.0));  // ID 21
-                self.platforms.push((1226.0, 1292.0, 135.0));  // ID 22
-                self.platforms.push((1292.0, 1416.0, 155.0));  // ID 23
-                self.platforms.push((1079.0, 1146.0, 155.0));  // ID 24
-                self.platforms.push((1156.0, 1168.0, 155.0));  // ID 25
-                self.platforms.push((13.0, 192.0, 225.0));     // ID 26
-                self.platforms.push((247.0, 553.0, 225.0));    // ID 27
-                self.platforms.push((589.0, 903.0, 225.0));    // ID 28
-                self.platforms.push((960.0, 1416.0, 225.0));   // ID 29
+            // Check for platform collisions when climbing down
+            // This prevents falling through floors
+            if self.player_vy > 0.0 {  // Moving down
+                for (px1, px2, py) in self.platforms.iter() {
+                    if *px2 > *px1 &&
+                       player_right > *px1 &&
+                       player_left < *px2
+                    {
+                        // Only land on platforms where our FEET are approaching from ABOVE
+                        // Platform must be at least 30px below our current center
+                        // This ensures we've descended enough to reach the next floor
+                        if *py > (self.player_y + 21.0) {
+                            let dist_to_platform = player_bottom - *py;
 
-                // ============================================================
-                // LADDERS (6 ladders) - From layout.md
-                // ============================================================
-                // Format: (x_center, y_top, y_bottom) where y_bottom = y_top + 28
+                            // Tight landing window when feet reach platform
+                            if dist_to_platform >= -2.0 && dist_to_platform <= 4.0 {
+                                self.player_y = *py - 19.0;
+                                self.player_vy = 0.0;
+                                self.player_on_ground = true;
+                                self.player_on_ladder = false;
+                                self.player_state = STATE_IDLE;
+                                // Reset animation to idle
+                                self.player_anim_frame = 0;
+                                self.player_anim_timer = 0;
+                                break;
+                            }
+                        }
+                    }
+                }
+            }
 
-                self.ladders[0] = (303.0, 85.0, 122.0);   // ID 1
-                self.ladders[1] = (259.0, 150.0, 190.0);  // ID 2
-                self.ladders[2] = (747.0, 152.0, 189.0);  // ID 3
-                self.ladders[3] = (837.0, 85.0, 122.0);   // ID 4
-                self.ladders[4] = (1327.0, 84.0, 121.0);  // ID 5
-                self.ladders[5] = (1148.0, 152.0, 189.0); // ID 6
+            // Update climbing animation - cycle through all 10 frames
+            if self.player_vy.abs() > 0.1 {
+                self.player_anim_timer += 1;
+                if self.player_anim_timer >= 6 {  // Faster animation (was 10)
+                    self.player_anim_timer = 0;
+                    self.player_anim_frame = (self.player_anim_frame + 1) % 10;  // All 10 frames
+                }
+            }
+            return;
+        }
 
-                // ============================================================
-                // WALLS (22 wall colliders) - From layout.md
-                // ============================================================
-                // Format: (x, y, width, height)
+        // ==================== HANGING STATE ====================
+        if let Some(i) = self.player_rope {
+            // No gravity, Santa's hands stay on the rope
+            let rope = &LEVEL_ROPES[i];
+            self.player_x = (self.player_x + self.player_vx).clamp(rope.x1, rope.x2);
+            self.player_y = rope.y + HANG_OFFSET;
+            self.player_vy = 0.0;
+            if self.player_invuln_timer > 0 {
+                self.player_invuln_timer -= 1;
+            }
 
-                self.walls[0] = (25.0, 35.0, 9.0, 50.0);      // ID 1
-                self.walls[1] = (116.0, 95.0, 9.0, 28.0);     // ID 2
-                self.walls[2] = (1350.0, 30.0, 23.0, 60.0);   // ID 3 - GATE (repositioned)
-                self.walls[3] = (159.0, 142.0, 10.0, 20.0);   // ID 4
-                self.walls[4] = (52.0, 161.0, 9.0, 59.0);     // ID 5
-                self.walls[5] = (368.0, 89.0, 17.0, 70.0);    // ID 6
-                self.walls[6] = (487.0, 115.0, 14.0, 62.0);   // ID 7
-                self.walls[7] = (546.0, 136.0, 14.0, 85.0);   // ID 8
-                self.walls[8] = (621.0, 162.0, 13.0, 59.0);   // ID 9
-                self.walls[9] = (726.0, 35.0, 17.0, 51.0);    // ID 10
-                self.walls[10] = (990.0, 118.0, 7.0, 19.0);   // ID 11
-                self.walls[11] = (1038.0, 88.0, 8.0, 30.0);   // ID 12
-                self.walls[12] = (1071.0, 108.0, 9.0, 49.0);  // ID 13
-                self.walls[13] = (1168.0, 108.0, 12.0, 49.0); // ID 14
-                self.walls[14] = (1196.0, 90.0, 6.0, 29.0);   // ID 15
-                self.walls[15] = (1220.0, 119.0, 6.0, 19.0);  // ID 16
-                self.walls[16] = (1286.0, 139.0, 6.0, 17.0);  // ID 17
-                self.walls[17] = (140.0, 120.0, 6.0, 10.0);  // ID 18
-                self.walls[18] = (1416.0, 55.0, 16.0, 180.0);  // ID 19
-                self.walls[19] = (1393.0, 161.0, 12.0, 59.0); // ID 20
-                self.walls[20] = (467.0, 92.0, 7.0, 25.0);        // Id 21 
-                self.walls[21] = (667.0, 136.0, 6.0, 16.0);          // Id 22 (unused)
-                // ============================================================
-                // DOORS - Destructible by bombs (Level 2)
-                // ============================================================
-                // Format: (x, y, intact)
-                // x, y are top-left corner of 42x42 door sprite
-                // Door sprite is 42x42, exploded_gate is 64x64
-                // Positioned on platforms to block paths
-                
-                // Door #1 - On platform ID 7 (367-479, Y=88), blocking path
-                self.doors[0] = (103.0, 181.0, true);
-                
-                // Door #2 - On platform ID 15 (546-674, Y=135), blocking middle section
-                self.doors[1] = (768.0, 46.0, true);
-                
-                // Door #3 - On platform ID 18 (813-897, Y=155), blocking right-middle
-                self.doors[2] = (416.0, 44.0, true);
-                
-                // Door #4 - On platform ID 10 (1037-1203, Y=88), blocking far right top
-                self.doors[3] = (1093.0, 113.0, true);
+            // Hand-over-hand reuses the climbing cycle
+            if self.player_vx.abs() > 0.1 {
+                self.player_anim_timer += 1;
+                if self.player_anim_timer >= 6 {
+                    self.player_anim_timer = 0;
+                    self.player_anim_frame = (self.player_anim_frame + 1) % 10;
+                }
+            }
+            return;
+        }
+
+        // ==================== DASH / SLIDE STATE ====================
+        if self.player_dash_timer > 0 {
+            self.player_dash_timer -= 1;
+            if self.player_invuln_timer > 0 {
+                self.player_invuln_timer -= 1;
+            }
+
+            let sliding = self.player_state == STATE_SLIDE;
+            if sliding {
+                // Slides follow the floor (and drop off ledges), dashes hold their height
+                self.player_vx *= SLIDE_FRICTION;
+                self.player_vy = (self.player_vy + GRAVITY).min(TERMINAL_VEL);
+            } else {
+                self.player_vy = 0.0;
+            }
+            self.player_x += self.player_vx;
+            self.player_y += self.player_vy;
+            self.check_player_collisions();
+
+            if self.player_dash_timer == 0 {
+                let walk_speed = self.physics_at(self.player_x, self.player_y).walk_speed;
+                self.player_vx = self.player_vx.clamp(-walk_speed, walk_speed);
+                if sliding && self.player_on_ground && self.ceiling_blocked() {
+                    // Came to a stop under something low
+                    self.player_is_crouching = true;
+                    self.player_state = STATE_CROUCH;
+                } else {
+                    self.player_state = if self.player_on_ground { STATE_IDLE } else { STATE_FALL };
+                }
+            }
+            return;
+        }
+
+        // ==================== CROUCHING STATE ====================
+        if self.player_is_crouching {
+            // Apply horizontal movement while crouching
+            self.player_x += self.player_vx;
+            self.check_player_collisions();
+            return;
+        }
+
+        // ==================== NORMAL PHYSICS ====================
+        // Constants come from the volume Santa is in (see LEVEL_VOLUMES)
+        let physics = self.physics_at(self.player_x, self.player_y);
+        let profile = self.movement();
+        // The profile shapes the arc: floatier apex, heavier fall (swimming keeps plain gravity)
+        let gravity_scale = if physics.swim || self.player_on_ground || self.player_vy <= -profile.apex_threshold {
+            1.0
+        } else if self.player_vy < profile.apex_threshold {
+            profile.apex_gravity
+        } else {
+            profile.fall_gravity
+        };
+        self.player_vy += physics.gravity * gravity_scale;
+        if self.player_vy > physics.terminal_vel {
+            self.player_vy = physics.terminal_vel;
+        }
+        if self.player_wall_sliding {
+            self.player_vy = self.player_vy.min(WALL_SLIDE_SPEED);
+        }
+
+        if !self.player_on_ground && self.player_coyote_timer > 0 {
+            self.player_coyote_timer -= 1;
+        }
+
+        if self.player_invuln_timer > 0 {
+            self.player_invuln_timer -= 1;
+        }
+
+        self.player_x += self.player_vx + physics.push_x;
+        self.player_y += self.player_vy;
+
+        self.check_player_collisions();
+
+        // ==================== STATE DETERMINATION ====================
+        if self.player_on_ground {
+            if self.player_vx.abs() < 0.1 {
+                self.player_state = STATE_IDLE;
+            } else if self.player_vx.abs() > physics.walk_speed + 0.1 {
+                self.player_state = STATE_RUN;
+            } else {
+                self.player_state = STATE_WALK;
+            }
+            // Reset jump animation when landing
+            self.jump_anim_frame = 0;
+            self.jump_anim_timer = 0;
+        } else if self.player_wall_sliding {
+            self.player_state = STATE_WALL_SLIDE;
+            // Face away from the wall, ready to kick off
+            self.player_facing_right = self.player_wall_side < 0;
+        } else if self.player_wall_jump_lock > 0 && self.player_vy < 0.0 {
+            self.player_state = STATE_WALL_JUMP;
+        } else if self.player_vy < 0.0 {
+            self.player_state = STATE_JUMP;
+        } else {
+            self.player_state = STATE_FALL;
+        }
+
+        // ==================== ANIMATION UPDATES ====================
+        self.update_animations();
+    }
+
+    fn update_animations(&mut self) {
+        match self.player_state {
+            STATE_IDLE => {
+                // 4 idle frames, 15 frames per sprite for breathing effect
+                self.player_anim_timer += 1;
+                if self.player_anim_timer >= 15 {
+                    self.player_anim_timer = 0;
+                    self.player_anim_frame = (self.player_anim_frame + 1) % IDLE_FRAMES;
+                }
+            },
+            STATE_WALK | STATE_RUN => {
+                // 10 running frames, 6 frames per sprite for smooth run cycle (4 when running)
+                let frame_time = if self.player_state == STATE_RUN { 4 } else { 6 };
+                self.player_anim_timer += 1;
+                if self.player_anim_timer >= frame_time {
+                    self.player_anim_timer = 0;
+                    self.player_anim_frame = (self.player_anim_frame + 1) % RUN_FRAMES;
+                }
+            },
+            STATE_JUMP | STATE_WALL_JUMP => {
+                // 5 jumping frames, progress through based on velocity
+                self.jump_anim_timer += 1;
+                if self.jump_anim_timer >= 8 {
+                    self.jump_anim_timer = 0;
+                    if self.jump_anim_frame < JUMP_FRAMES - 1 {
+                        self.jump_anim_frame += 1;
+                    }
+                }
+            },
+            STATE_FALL | STATE_WALL_SLIDE => {
+                // Use last jump frame or fall sprite
+                self.jump_anim_frame = JUMP_FRAMES - 1;
+            },
+            STATE_ATTACK => {
+                // 5 attack frames, 4 frames per sprite
+                let attack_sprite_frame = (self.attack_frame / 4) % ATTACK_FRAMES;
+                self.player_anim_frame = attack_sprite_frame;
+            },
+            STATE_CLIMB | STATE_HANG => {
+                // 10 climbing frames - animation handled in update_player
+                // This state is managed separately because climbing uses player_vy
+            },
+            STATE_CROUCH => {
+                // Use scared sprite for crouching
+                self.player_anim_frame = 0;
+            },
+            _ => {}
+        }
+    }
+
+    fn low_profile(&self) -> bool {
+        self.player_is_crouching || self.player_state == STATE_SLIDE
+    }
+
+    // Would standing up hit a wall or block?
+    fn ceiling_blocked(&self) -> bool {
+        let (left, right) = (self.player_x - 7.0, self.player_x + 7.0);
+        let (top, bottom) = (self.player_y - 19.0, self.player_y + CROUCH_TOP);
+        self.solid_rects().iter().any(|(wx, wy, ww, wh)| {
+            right > *wx && left < wx + ww && bottom > *wy && top < wy + wh
+        })
+    }
+
+    fn check_player_collisions(&mut self) {
+        let was_on_ground = self.player_on_ground;
+        self.player_on_ground = false;
+
+        // Player hitbox (center-based Y coordinate), the top drops while crouched/sliding
+        let player_bottom = self.player_y + 19.0;
+        let player_left = self.player_x - 7.0;
+        let player_right = self.player_x + 7.0;
+
+        // ============================================
+        // VERTICAL COLLISION PASS (NES-style)
+        // ============================================
+        // Platforms are 1D horizontal surfaces
+        // Wide tolerance (20px) handles high-speed falls
+
+        if self.player_vy >= 0.0 {  // Only when falling/grounded
+            for (px1, px2, py) in self.platforms.iter() {
+                // Check if platform is valid and player overlaps horizontally
+                if *px2 > *px1 &&
+                   player_right > *px1 &&
+                   player_left < *px2
+                {
+                    // Wide vertical tolerance for consistent collision
+                    let dist_to_platform = player_bottom - *py;
+
+                    if dist_to_platform >= 0.0 && dist_to_platform <= 20.0 {
+                        // HARD SNAP to platform top (NES-style, no smoothing)
+                        self.player_y = *py - 19.0;
+                        self.player_vy = 0.0;
+                        self.player_on_ground = true;
+
+                        if !was_on_ground {
+                            self.player_coyote_timer = COYOTE_TIME;
+                        }
+                        break;  // Stop at first collision
+                    }
+                }
+            }
+
+            // Ladder tops work the same way (one-way, Down climbs back onto the ladder)
+            if !self.player_on_ground {
+                for (lx, ly_top, _) in self.ladders.iter() {
+                    if *lx > 0.0 &&
+                       player_right > *lx - LADDER_TOP_HALF_WIDTH &&
+                       player_left < *lx + LADDER_TOP_HALF_WIDTH
+                    {
+                        let dist_to_top = player_bottom - *ly_top;
+                        if (0.0..=20.0).contains(&dist_to_top) {
+                            self.player_y = *ly_top - 19.0;
+                            self.player_vy = 0.0;
+                            self.player_on_ground = true;
+                            if !was_on_ground {
+                                self.player_coyote_timer = COYOTE_TIME;
+                            }
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+
+        // ============================================
+        // HORIZONTAL COLLISION PASS (walls & bounds)
+        // ============================================
+
+        // Check wall collisions (AABB - Axis-Aligned Bounding Box)
+        let player_top = if self.low_profile() { self.player_y + CROUCH_TOP } else { self.player_y - 19.0 };
+
+        // Walls (minus opened gates) and intact destructible blocks
+        self.player_wall_side = 0;
+        for (wx, wy, ww, wh) in self.solid_rects() {
+            {
+                let wall_left = wx;
+                let wall_right = wx + ww;
+                let wall_top = wy;
+                let wall_bottom = wy + wh;
+
+                // AABB collision detection
+                if player_right > wall_left &&
+                   player_left < wall_right &&
+                   player_bottom > wall_top &&
+                   player_top < wall_bottom
+                {
+                    // Collision detected - push player out
+                    let overlap_left = player_right - wall_left;
+                    let overlap_right = wall_right - player_left;
+                    let overlap_top = player_bottom - wall_top;
+                    let overlap_bottom = wall_bottom - player_top;
+
+                    // Find smallest overlap (that's the direction to push)
+                    let min_overlap = overlap_left.min(overlap_right).min(overlap_top).min(overlap_bottom);
+
+                    if min_overlap == overlap_left {
+                        // Push player left
+                        self.player_x = wall_left - 7.0;
+                        self.player_vx = 0.0;
+                        self.player_wall_side = 1;
+                    } else if min_overlap == overlap_right {
+                        // Push player right
+                        self.player_x = wall_right + 7.0;
+                        self.player_vx = 0.0;
+                        self.player_wall_side = -1;
+                    } else if min_overlap == overlap_top {
+                        // Push player up (standing on wall/block top)
+                        self.player_y = wall_top - 19.0;
+                        self.player_vy = 0.0;
+                        self.player_on_ground = true;
+                    } else {
+                        // Push player down (hitting ceiling)
+                        self.player_y = wall_bottom + 19.0;
+                        self.player_vy = 0.0;
+                    }
+                }
+            }
+        }
+
+        // World bounds - left wall
+        if self.player_x < 7.0 {
+            self.player_x = 7.0;
+            self.player_vx = 0.0;
+        }
+
+        // World bounds - right wall (level dependent)
+        let max_x = match self.level {
+            1 => 1073.0,  // Level 1 boundary (1080 - 7)
+            2 => 1433.0,  // Level 2 boundary (1440 - 7)
+            3 => 2153.0,  // Boss level boundary (2160 - 7)
+            _ => 1073.0,  // Default
+        };
+        if self.player_x > max_x {
+            self.player_x = max_x;
+            self.player_vx = 0.0;
+        }
+
+        // ============================================
+        // DEATH ZONE (below all platforms)
+        // ============================================
+
+        if self.player_y > 260.0 {
+            self.player_hp = 0;
+            self.player_state = STATE_DEAD;
+        }
+    }
+
+    fn spawn_player_projectile(&mut self) {
+        let tier = &WEAPON_TIERS[self.weapon_tier as usize];
+        let charged = self.charged_shot_pending;
+        self.charged_shot_pending = false;
+
+        let spawn_x = if self.player_facing_right {
+            self.player_x + 20.0
+        } else {
+            self.player_x - 20.0
+        };
+        let spawn_y = self.player_y;
+        let dir = if self.player_facing_right { 1.0 } else { -1.0 };
+
+        let (speed, range, damage, pierce) = if charged {
+            (
+                tier.speed * CHARGE_SHOT_SPEED_MULT,
+                tier.range * CHARGE_SHOT_RANGE_MULT,
+                tier.damage + CHARGE_SHOT_DAMAGE_BONUS,
+                tier.pierce + CHARGE_SHOT_PIERCE_BONUS,
+            )
+        } else {
+            (tier.speed, tier.range, tier.damage, tier.pierce)
+        };
+
+        let player_proj_count = self.projectiles.iter()
+            .filter(|proj| proj.active && !proj.from_boss)
+            .count();
+
+        // Tap volleys wait for room, charged shots take whatever slots are free
+        if !charged && player_proj_count + tier.spread_vy.len() > tier.max_on_screen {
+            return;
+        }
+
+        for vy in tier.spread_vy.iter() {
+            if let Some(proj) = self.projectiles.iter_mut().find(|proj| !proj.active) {
+                *proj = Projectile {
+                    active: true,
+                    x: spawn_x,
+                    y: spawn_y,
+                    vx: speed * dir,
+                    vy: *vy,
+                    max_distance: range,
+                    damage,
+                    pierce_left: pierce,
+                    charged,
+                    color: tier.color,
+                    ..Projectile::EMPTY
+                };
+            }
+        }
+    }
+
+    fn kill_enemy(&mut self, enemy_idx: usize, score: u32) {
+        // Start respawn timer (600 frames = 10 seconds at 60fps)
+        self.enemies[enemy_idx].11 = 600;
+
+        // Every kill rolls the enemy's drop table at its position
+        let (kind, x, y) = (self.enemies[enemy_idx].0, self.enemies[enemy_idx].1, self.enemies[enemy_idx].2);
+        let drop = Self::roll_drop(Self::drop_table_for_enemy(kind));
+        self.spawn_drop(drop, x, y);
+
+        self.score += score;
+        self.level_stats.enemies_defeated += 1;
+        self.level_stats.kill_score += score;
+        self.earn_tokens(TOKENS_PER_KILL);
+    }
+
+    fn play_hurt_sfx(&mut self) {
+        self.sound.play(CUE_SANTA_HURT);
+    }
+
+    fn play_kill_sfx(&mut self) {
+        self.sound.play(CUE_SANTA_KILL);
+    }
+
+    fn enemy_in_melee_range(&self) -> bool {
+        let dir = if self.player_facing_right { 1.0 } else { -1.0 };
+        let in_front = |x: f32, y: f32, half_w: f32| {
+            let ahead = (x - self.player_x) * dir;
+            ahead > -half_w && ahead < MELEE_AUTO_RANGE + half_w && (y - self.player_y).abs() < 20.0
+        };
+
+        let enemy_close = self.enemies.iter().any(|enemy| enemy.8 && enemy.11 == 0 && in_front(enemy.1, enemy.2, 0.0));
+        let boss_close = self.boss_active && self.boss.hp > 0 && in_front(self.boss.x, self.boss.y, self.boss_def().hitbox.0);
+        enemy_close || boss_close
+    }
+
+    fn apply_melee_hits(&mut self) {
+        // Hitbox in front of Santa: (center x, center y, half width, half height)
+        let dir = if self.player_facing_right { 1.0 } else { -1.0 };
+        let hit_x = self.player_x + dir * MELEE_REACH / 2.0;
+        let hit_y = self.player_y;
+        let overlaps = |x: f32, y: f32, half_w: f32, half_h: f32| {
+            (x - hit_x).abs() < MELEE_REACH / 2.0 + half_w && (y - hit_y).abs() < MELEE_HALF_HEIGHT + half_h
+        };
+
+        // Enemies: one bonk kills
+        for enemy_idx in 0..self.enemies.len() {
+            let enemy = self.enemies[enemy_idx];
+            if enemy.8 && enemy.11 == 0 && overlaps(enemy.1, enemy.2, 10.0, 10.0) {
+                self.kill_enemy(enemy_idx, MELEE_SCORE);
+                self.play_kill_sfx();
+            }
+        }
+
+        // Boss: damage and knockback once per swing, roaring bosses shrug it off
+        if self.boss_active && self.boss.hp > 0 && !self.melee_hit_boss {
+            let (hit_w, hit_h) = self.boss_def().hitbox;
+            if overlaps(self.boss.x, self.boss.y, hit_w, hit_h) {
+                self.melee_hit_boss = true;
+                if self.boss.state != BOSS_STATE_ROAR {
+                    self.boss.hp = self.boss.hp.saturating_sub(MELEE_DAMAGE);
+                    self.boss.vx = dir * MELEE_KNOCKBACK;
+                    self.boss.flash_timer = 5;
+                    if self.boss.on_ground { self.boss.state = BOSS_STATE_IDLE; }
+                    self.play_boss_cue(self.boss_def().hurt_cue);
+                }
+            }
+        }
+
+        // Destructible blocks, the hit flash keeps it to one hit per swing
+        for block_idx in 0..self.blocks.len() {
+            let block = self.blocks[block_idx];
+            if block.hp > 0 && block.flash == 0
+                && overlaps(block.x + block.w / 2.0, block.y + block.h / 2.0, block.w / 2.0, block.h / 2.0)
+            {
+                self.damage_block(block_idx, BREAK_BY_MELEE, MELEE_DAMAGE);
+            }
+        }
+
+        // Breakables: snowballs, boss shots and falling icicles/coal shatter on the cane
+        for snowball in self.snowballs.iter_mut() {
+            if snowball.0 && overlaps(snowball.1, snowball.2, 6.0, 6.0) {
+                snowball.0 = false;
+            }
+        }
+        for proj in self.projectiles.iter_mut() {
+            if proj.active && proj.from_boss && overlaps(proj.x, proj.y, 4.0, 4.0) {
+                proj.active = false;
+            }
+        }
+        for hazard in self.falling_hazards.iter_mut() {
+            if hazard.active && hazard.telegraph == 0 && overlaps(hazard.x, hazard.y, 6.0, 8.0) {
+                hazard.active = false;
+                self.score += 25;
+            }
+        }
+    }
+
+    fn drop_table_for_enemy(enemy_type: u8) -> &'static [(u8, u16)] {
+        match enemy_type {
+            2 => &DROP_TABLE_KICKMOUSE,
+            3 => &DROP_TABLE_PENGUIN,
+            _ => &DROP_TABLE_MOUSE,
+        }
+    }
+
+    // Pick one outcome from a weighted drop table
+    fn roll_drop(table: &[(u8, u16)]) -> u8 {
+        Self::pick_drop(table, random::u32())
+    }
+
+    // The outcome a roll lands on, rolls wrap around the total weight
+    fn pick_drop(table: &[(u8, u16)], roll: u32) -> u8 {
+        let total_weight: u32 = table.iter().map(|(_, weight)| *weight as u32).sum();
+        if total_weight == 0 {
+            return DROP_NONE;
+        }
+
+        let mut roll = roll % total_weight;
+        for (kind, weight) in table.iter() {
+            if roll < *weight as u32 {
+                return *kind;
+            }
+            roll -= *weight as u32;
+        }
+        DROP_NONE
+    }
+
+    fn spawn_drop(&mut self, kind: u8, x: f32, enemy_y: f32) {
+        if kind == DROP_NONE {
+            return;
+        }
+
+        // Enemy Y = platform_y - 28 (enemy center is 28px above platform)
+        // Santa Y = platform_y - 28 (Santa center is 28px above platform)
+        // Drops should be at same Y as Santa/enemy for consistency
+        // Gift bomb sprite is 64x64 with gift at bottom middle
+        // We want the drop center at the same Y as Santa (enemy_y)
+        let drop_y = enemy_y;
+
+        // Find an empty slot for the drop
+        for item in self.drop_items.iter_mut() {
+            if !item.active {  // If not active
+                let despawn = if kind == DROP_WALL_BOOTS { DROP_NO_DESPAWN } else { DROP_DESPAWN_TIME };
+                *item = DropItem::new(x, drop_y, despawn, kind);
+                break;
+            }
+        }
+    }
+
+    fn place_gift_bomb(&mut self, thrown: bool) {
+        let (vx, vy) = if thrown {
+            let dir = if self.player_facing_right { 1.0 } else { -1.0 };
+            (GIFT_BOMB_THROW_VX * dir, GIFT_BOMB_THROW_VY)
+        } else {
+            (0.0, 0.0)
+        };
+
+        // Find an empty slot for the placed bomb
+        for bomb in self.placed_bombs.iter_mut() {
+            if !bomb.active {  // If not active
+                // Place bomb at Santa's Y position (same level as Santa)
+                // Santa is at player_y (center is 28px above platform)
+                // Gift bomb should be at same Y as Santa
+                let bomb_y = self.player_y;
+
+                *bomb = PlacedBomb::new(self.player_x, bomb_y, vx, vy, thrown);
+                self.gift_bombs -= 1;
+                self.level_stats.bombs_used += 1;
+                break;
+            }
+        }
+    }
+
+    fn update_thrown_bomb(&mut self, i: usize) {
+        let (x, y) = (self.placed_bombs[i].x, self.placed_bombs[i].y);
+        let mut vx = self.placed_bombs[i].vx;
+        let vy = (self.placed_bombs[i].vy + GRAVITY).min(TERMINAL_VEL);
+        let mut new_x = x + vx;
+        let new_y = y + vy;
+
+        // Walls and blocks stop the throw, the bomb drops straight down from there
+        for (wx, wy, ww, wh) in self.solid_rects() {
+            if new_x > wx && new_x < wx + ww && new_y + 19.0 > wy && new_y - 10.0 < wy + wh {
+                new_x = x;
+                vx = 0.0;
+                break;
+            }
+        }
+
+        // Land when the bomb's bottom (same offset as Santa's feet) passes a platform top
+        if vy >= 0.0 {
+            for (px1, px2, py) in self.platforms.iter() {
+                if new_x >= *px1 && new_x <= *px2 && y + 19.0 <= *py && new_y + 19.0 >= *py {
+                    self.placed_bombs[i].x = new_x;
+                    self.placed_bombs[i].y = *py - 19.0;
+                    self.placed_bombs[i].vx = 0.0;
+                    self.placed_bombs[i].vy = 0.0;
+                    self.placed_bombs[i].airborne = false;
+                    return;
+                }
+            }
+        }
+
+        self.placed_bombs[i].x = new_x;
+        self.placed_bombs[i].y = new_y;
+        self.placed_bombs[i].vx = vx;
+        self.placed_bombs[i].vy = vy;
+
+        // Lobbed into a pit: fizzle out below the screen
+        if new_y > SCREEN_HEIGHT + 40.0 {
+            self.placed_bombs[i].active = false;
+        }
+    }
+
+    fn update_projectiles(&mut self) {
+        // First, check for projectile-enemy collisions (player projectiles only)
+        for proj_idx in 0..self.projectiles.len() {
+            if self.projectiles[proj_idx].active && !self.projectiles[proj_idx].from_boss {
+                // Active player projectile - check against all enemies
+                let proj_x = self.projectiles[proj_idx].x;
+                let proj_y = self.projectiles[proj_idx].y;
+                
+                // Check boss collision first
+                let mut hit_boss = false;
+                if self.boss_active && self.boss.hp > 0 {
+                    let dxb = (proj_x - self.boss.x).abs();
+                    let dyb = (proj_y - self.boss.y).abs();
+                    let (hit_w, hit_h) = self.boss_def().hitbox;
+                    if dxb < hit_w && dyb < hit_h && self.boss.state == BOSS_STATE_ROAR {
+                        // Roaring boss is invulnerable - projectile fizzles
+                        self.projectiles[proj_idx].active = false;
+                        hit_boss = true;
+                    } else if dxb < hit_w && dyb < hit_h {
+                        // Hit boss: decrement HP and apply small knockback
+                        self.projectiles[proj_idx].active = false;
+                        self.boss.hp = self.boss.hp.saturating_sub(self.projectiles[proj_idx].damage);
+                        self.play_boss_cue(self.boss_def().hurt_cue);
+                        self.boss.vx = if proj_x < self.boss.x { 0.8 } else { -0.8 };
+                        self.boss.flash_timer = 5; // Flash for 5 frames
+                        // Reset to idle after hit if on ground
+                        if self.boss.on_ground { self.boss.state = BOSS_STATE_IDLE; }
+                        hit_boss = true;
+                    }
+                }
+
+                if hit_boss { continue; }
+
+                // Switches stop the shot and flip
+                if let Some(trigger_idx) = self.switch_at(proj_x, proj_y) {
+                    self.hit_switch(trigger_idx);
+                    self.projectiles[proj_idx].active = false;
+                    continue;
+                }
+
+                // Blocks stop every shot, only the right kind of shot damages them
+                if let Some(block_idx) = self.block_at(proj_x, proj_y, 4.0, 4.0) {
+                    let source = if self.projectiles[proj_idx].charged { BREAK_BY_SHOT | BREAK_BY_CHARGED } else { BREAK_BY_SHOT };
+                    let damage = self.projectiles[proj_idx].damage;
+                    self.damage_block(block_idx, source, damage);
+                    self.projectiles[proj_idx].active = false;
+                    continue;
+                }
+
+                for enemy_idx in 0..self.enemies.len() {
+                    // Check if enemy is alive (active and not respawning)
+                    if self.enemies[enemy_idx].8 && self.enemies[enemy_idx].11 == 0 {
+                        let enemy_x = self.enemies[enemy_idx].1;
+                        let enemy_y = self.enemies[enemy_idx].2;
+                        
+                        // Collision check: 16px radius for both projectile and enemy
+                        let dx = (proj_x - enemy_x).abs();
+                        let dy = (proj_y - enemy_y).abs();
+                        
+                        if dx < 20.0 && dy < 20.0 {
+                            // Hit! Kill the enemy, piercing shots keep going
+                            if self.projectiles[proj_idx].pierce_left > 0 {
+                                self.projectiles[proj_idx].pierce_left -= 1;
+                            } else {
+                                self.projectiles[proj_idx].active = false;
+                            }
+
+                            self.kill_enemy(enemy_idx, 100);
+
+                            self.play_kill_sfx();
+
+                            if !self.projectiles[proj_idx].active {
+                                break;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        
+        // Boss projectiles hit Santa
+        for proj_idx in 0..self.projectiles.len() {
+            if self.projectiles[proj_idx].active && self.projectiles[proj_idx].from_boss {
+                let proj_x = self.projectiles[proj_idx].x;
+                let proj_y = self.projectiles[proj_idx].y;
+                let dx = (proj_x - self.player_x).abs();
+                let dy = (proj_y - self.player_y).abs();
+                let def = self.boss_pattern.attack(ATTACK_PROJECTILE);
+                if dx < def.hitbox_w && dy < def.hitbox_h && !self.player_protected() {
+                    // Apply modest damage and knockback
+                    let knock_vx = if proj_x > self.player_x { -def.knockback_x } else { def.knockback_x };
+                    self.hurt_player_by_boss(def.damage_for(self.player_max_hp), knock_vx, def.knockback_y);
+                    // Despawn projectile on hit
+                    self.projectiles[proj_idx].active = false;
+                }
+            }
+        }
+        
+        // Then update projectile positions
+        for proj in self.projectiles.iter_mut() {
+            if proj.active {
+                proj.x += proj.vx;
+                proj.y += proj.vy;
+                proj.distance += proj.vx.abs();
+
+                if proj.distance > proj.max_distance {
+                    proj.active = false;
+                }
+
+                if proj.x < self.camera_x - 50.0 || proj.x > self.camera_x + SCREEN_WIDTH + 50.0 {
+                    proj.active = false;
+                }
+            }
+        }
+    }
+
+    fn check_enemy_collisions(&mut self) {
+        // Decrement invulnerability timer
+        if self.player_invuln_timer > 0 {
+            self.player_invuln_timer -= 1;
+            return;  // Can't take damage while invulnerable
+        }
+
+        // Only check collisions when alive and unshielded
+        if self.player_state == STATE_DEAD || self.player_shield_timer > 0 {
+            return;
+        }
+
+        // Check collision with each active enemy
+
+        // Check collision with each active enemy
+        for enemy in self.enemies.iter() {
+            // Skip if enemy is dead/respawning (respawn_timer > 0), frozen enemies are harmless
+            if enemy.8 && enemy.11 == 0 && self.enemy_freeze_timer == 0 {  // active AND alive (not respawning)
+                let dx = (self.player_x - enemy.1).abs();
+                let dy = (self.player_y - enemy.2).abs();
+                
+                // Collision threshold: 20px horizontal, 20px vertical
+                if dx < 20.0 && dy < 20.0 {
+                    // Apply damage: 10% of max HP = 1 HP from max 10
+                    let damage = ((self.player_max_hp as f32 * 0.1).ceil() as u8).max(1);
+                    self.player_hp = self.player_hp.saturating_sub(damage);
+                    self.sound.play(CUE_SANTA_HURT);
+                    
+                    // Set invulnerability frames (2 seconds at 60fps)
+                    self.player_invuln_timer = 120;
+                    
+                    // Knockback effect
+                    if enemy.1 < self.player_x {
+                        self.player_vx = 3.0;  // Push right
+                    } else {
+                        self.player_vx = -3.0; // Push left
+                    }
+                    self.player_vy = -3.0;  // Push up slightly
+                    
+                    // Death is handled in update() loop
+                    // (handle_death will be called there)
+                    
+                    break;  // Only one hit per frame
+                }
+            }
+        }
+    }
+
+    fn update_enemies(&mut self) {
+        for i in 0..self.enemies.len() {
+            // Skip if enemy type is 0 (not initialized)
+            if self.enemies[i].0 == 0 {
+                continue;
+            }
+            
+            // Handle respawn timer (enemy.11 = respawn_timer)
+            if self.enemies[i].11 > 0 {
+                // Boss minions skip the long wait and just poof in the cloud
+                let minion = self.is_boss_minion(i);
+                if minion && self.enemies[i].11 > 120 {
+                    self.enemies[i].11 = 120;
+                }
+                self.enemies[i].11 -= 1;
+                
+                // Cloud animation calculated on-the-fly in draw_enemies()
+                // during last 120 frames before respawn
+                
+                // Respawn complete - reset enemy to patrol start position
+                if self.enemies[i].11 == 0 && minion {
+                    // Boss minions don't come back - free the slot for the next summon
+                    self.enemies[i].8 = false;
+                    self.boss_minions &= !(1 << i);
+                } else if self.enemies[i].11 == 0 {
+                    self.enemies[i].1 = self.enemies[i].7 as f32 + 50.0;  // Reset X to patrol center
+                    self.enemies[i].6 = 0;  // Reset animation frame
+                    self.enemies[i].9 = 0;  // Reset animation timer
+                    self.enemies[i].10 = false;  // Not attacking
+                }
+                
+                continue;  // Skip normal AI while dead/respawning
+            }
+
+            // Snow globe: everyone stands still
+            if self.enemy_freeze_timer > 0 {
+                continue;
+            }
+            
+            // Only process active enemies that are alive
+            if self.enemies[i].8 {
+                let enemy_type = self.enemies[i].0;
+
+                // Attack detection for kickmouse (type 2) and penguin (type 3)
+                if enemy_type == 2 || enemy_type == 3 {
+                    let dx = (self.player_x - self.enemies[i].1).abs();
+                    let dy = (self.player_y - self.enemies[i].2).abs();
+
+                    // Trigger attack if player is within range
+                    let attack_range_h = if enemy_type == 3 { 30.0 } else { 10.0 }; // Penguin: 30px, Kickmouse: 10px
+                    let attack_range_v = if enemy_type == 3 { 10.0 } else { 20.0 }; // Penguin: 10px, Kickmouse: 20px
+                    if dx < attack_range_h && dy < attack_range_v && !self.enemies[i].10 {
+                        self.enemies[i].10 = true;  // Start attack
+                        self.enemies[i].6 = 0;  // Reset animation to frame 0
+                        self.enemies[i].9 = 0;  // Reset timer
+                        
+                        // Penguin always faces Santa when attacking
+                        if enemy_type == 3 {
+                            // Set direction based on Santa's position relative to penguin
+                            self.enemies[i].5 = if self.player_x > self.enemies[i].1 { 1 } else { 0 };
+                            
+                            // Spawn snowball projectile toward Santa
+                            let penguin_x = self.enemies[i].1;
+                            let penguin_y = self.enemies[i].2;
+                            let snowball_speed = 3.0;
+                            let vx = if self.player_x > penguin_x { snowball_speed } else { -snowball_speed };
+                            
+                            // Find an empty slot for snowball
+                            for snowball in self.snowballs.iter_mut() {
+                                if !snowball.0 {
+                                    *snowball = (true, penguin_x, penguin_y, vx, 0.0);
+                                    break;
+                                }
+                            }
+                        }
+                    }
+                }
+
+                // Patrol AI (only when not attacking)
+                if !self.enemies[i].10 {
+                    if enemy_type == 1 || enemy_type == 2 || enemy_type == 3 {
+                        // Store old position for collision detection
+                        let old_x = self.enemies[i].1;
+                        let patrol_width = if self.level == 3 {
+                                                60.0   // boss level: tight platforms
+                                        } else {
+                                                100.0  // level 1 & 2: existing behavior
+                                        };
+                        // enemy.3 carries the walk speed so ice can make them slide through turns
+                        let physics = self.physics_at(self.enemies[i].1, self.enemies[i].2);
+                        let walk = 0.75 * physics.walk_speed / WALK_SPEED;
+                        let target_vx = if self.enemies[i].5 == 0 { -walk } else { walk };
+                        let on_ice = self.floor_hazard(self.enemies[i].1, self.enemies[i].2 + 19.0)
+                            .is_some_and(|(kind, _)| kind == HAZARD_ICE);
+                        let grip = if on_ice { ICE_FRICTION } else { 1.0 };
+                        self.enemies[i].3 += (target_vx - self.enemies[i].3) * grip;
+                        self.enemies[i].1 += self.enemies[i].3 + physics.push_x;
+                        if self.enemies[i].5 == 0 {
+                            if self.enemies[i].1 < self.enemies[i].7 as f32 {
+                                self.enemies[i].5 = 1;
+                            }
+                        } else if self.enemies[i].1 > (self.enemies[i].7 as f32 + patrol_width) {
+                            self.enemies[i].5 = 0;
+                        }
+
+                        // Check wall collision (enemy is 16px wide, centered)
+                        let enemy_left = self.enemies[i].1 - 8.0;
+                        let enemy_right = self.enemies[i].1 + 8.0;
+                        let enemy_top = self.enemies[i].2 - 8.0;
+                        let enemy_bottom = self.enemies[i].2 + 8.0;
+
+                        for (wx, wy, ww, wh) in self.solid_rects() {
+                            {
+                                let wall_left = wx;
+                                let wall_right = wx + ww;
+                                let wall_top = wy;
+                                let wall_bottom = wy + wh;
+
+                                // AABB collision check
+                                if enemy_right > wall_left && enemy_left < wall_right &&
+                                   enemy_bottom > wall_top && enemy_top < wall_bottom {
+                                    // Hit a wall! Reverse direction and restore position
+                                    self.enemies[i].1 = old_x;
+                                    self.enemies[i].5 = 1 - self.enemies[i].5;  // Flip direction
+                                    break;
+                                }
+                            }
+                        }
+                    }
+                }
+
+                // Update animation
+                self.enemies[i].9 += 1; // anim_timer
+
+                if enemy_type == 1 {
+                    // Enemy1 (easy mouse): 8 frames, 8 frames per sprite
+                    if self.enemies[i].9 >= 8 {
+                        self.enemies[i].9 = 0;
+                        self.enemies[i].6 = (self.enemies[i].6 + 1) % 8; // anim_frame cycles 0-7
+                    }
+                } else if enemy_type == 2 || enemy_type == 3 {
+                    // Kickmouse and Penguin: Handle attack or walk animation
+                    if self.enemies[i].10 {
+                        // Attack animation: 3 frames, 10 ticks per frame (slower)
+                        if self.enemies[i].9 >= 10 {
+                            self.enemies[i].9 = 0;
+                            self.enemies[i].6 += 1;
+
+                            // After 3 attack frames, return to walk
+                            if self.enemies[i].6 >= 3 {
+                                self.enemies[i].10 = false;  // End attack
+                                self.enemies[i].6 = 0;  // Reset to walk frame 0
+                            }
+                        }
+                    } else {
+                        // Walk animation: 8 frames, 8 ticks per frame
+                        if self.enemies[i].9 >= 8 {
+                            self.enemies[i].9 = 0;
+                            self.enemies[i].6 = (self.enemies[i].6 + 1) % 8; // anim_frame cycles 0-7
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn update_snowballs(&mut self) {
+        // Update each active snowball
+        for snowball in self.snowballs.iter_mut() {
+            if snowball.0 {
+                // Move snowball
+                snowball.1 += snowball.3;  // x += vx
+                snowball.2 += snowball.4;  // y += vy (gravity can be added later)
+                
+                // Check collision with Santa (if not invulnerable or shielded)
+                if self.player_invuln_timer == 0 && self.player_shield_timer == 0 && self.player_state != STATE_DEAD {
+                    let dx = (snowball.1 - self.player_x).abs();
+                    let dy = (snowball.2 - self.player_y).abs();
+                    
+                    // Collision check: 16px radius
+                    if dx < 16.0 && dy < 16.0 {
+                        // Hit Santa! Deal 1 HP damage
+                        self.player_hp = self.player_hp.saturating_sub(1);
+                        
+                        // Play hurt sound
+                        self.sound.play(CUE_SANTA_HURT);
+                        
+                        // Grant invulnerability frames (1 second)
+                        self.player_invuln_timer = 60;
+                        
+                        // Small knockback from snowball direction
+                        if snowball.3 > 0.0 {
+                            self.player_vx = 2.0;  // Push right
+                        } else {
+                            self.player_vx = -2.0; // Push left
+                        }
+                        self.player_vy = -2.0;  // Small upward push
+                        
+                        // Deactivate snowball
+                        snowball.0 = false;
+                        continue;
+                    }
+                }
+                
+                // Despawn if off-screen (traveled too far)
+                if snowball.1 < self.camera_x - 50.0 || snowball.1 > self.camera_x + SCREEN_WIDTH + 50.0 {
+                    snowball.0 = false;
+                }
+            }
+        }
+    }
+
+    fn check_drop_pickups(&mut self) {
+        if self.upgrade_message_timer > 0 {
+            self.upgrade_message_timer -= 1;
+        }
+
+        for item in self.drop_items.iter_mut() {
+            if item.active {  // If active
+                // Countdown despawn timer (upgrades and placed pickups stay put)
+                if item.despawn_timer > 0 {
+                    if item.despawn_timer != DROP_NO_DESPAWN {
+                        item.despawn_timer -= 1;
+                    }
+                } else {
+                    // Timer expired - despawn the drop
+                    item.active = false;
+                    continue;
+                }
+
+                // Check for pickup collision
+                let dx = (self.player_x - item.x).abs();
+                let dy = (self.player_y - item.y).abs();
+                if dx >= 20.0 || dy >= 20.0 {
+                    continue;
+                }
+
+                match item.kind {
+                    DROP_GIFT_BOMB => {
+                        // Only pick up if there is room in the bomb bag
+                        if self.gift_bombs < self.gift_bomb_capacity {
+                            item.active = false;  // Deactivate the item
+                            self.gift_bombs += 1;
+                            self.score += 50;
+                            self.sound.play(CUE_PICKUP);
+                        }
+                    },
+                    DROP_COIN => {
+                        item.active = false;
+                        self.score += COIN_SCORE;
+                        self.sound.play(CUE_PICKUP);
+                    },
+                    DROP_CANDY => {
+                        // Leave the candy on the ground when already at full health
+                        if self.player_hp < self.player_max_hp {
+                            item.active = false;
+                            self.player_hp += 1;
+                            self.sound.play(CUE_PICKUP);
+                        }
+                    },
+                    DROP_WALL_BOOTS => {
+                        item.active = false;
+                        self.has_wall_jump = true;
+                        self.upgrade_message_timer = UPGRADE_MESSAGE_FRAMES;
+                        self.sound.play(CUE_PICKUP);
+                        log!("Wall boots collected: wall-slide and wall-jump unlocked");
+                    },
+                    DROP_COCOA..=DROP_BELL => {
+                        // Leave it on the ground when the bag already holds the max
+                        let kind = item.kind - DROP_COCOA;
+                        let max = ITEMS[kind as usize].max_carried;
+                        if self.items[kind as usize] < max {
+                            item.active = false;
+                            self.items[kind as usize] += 1;
+                            // Nothing selected yet: the new item is
+                            if self.items[self.selected_item as usize] == 0 {
+                                self.selected_item = kind;
+                            }
+                            self.item_message = (kind, ITEM_MSG_PICKUP, ITEM_MESSAGE_FRAMES);
+                            self.sound.play(CUE_PICKUP);
+                        }
+                    },
+                    _ => { item.active = false; }
+                }
+            }
+        }
+    }
+
+    fn update_placed_bombs(&mut self) {
+        for i in 0..self.placed_bombs.len() {
+            if self.placed_bombs[i].active {  // If active
+                // Thrown bombs fly until they land, the fuse keeps burning meanwhile
+                if self.placed_bombs[i].airborne {
+                    self.update_thrown_bomb(i);
+                    if !self.placed_bombs[i].active { continue; }
+                }
+
+                self.placed_bombs[i].anim_timer += 1;  // Increment anim_timer
+
+                // New bomb animation sequence (5+ seconds total):
+                // Frame 0 (idle): 60 frames (1 second)
+                // Frame 1-4 (sprites 2-5, first cycle): 30 frames each (2 seconds) - WARNING
+                // Frame 5-8 (sprites 2-5, second cycle): 30 frames each (2 seconds) - WARNING
+                // Frame 9-11 (sprites 6-8): 10 frames each (0.5 seconds) - EXPLOSION
+
+                if self.placed_bombs[i].anim_frame == 0 {
+                    // Idle phase - 1 second
+                    if self.placed_bombs[i].anim_timer >= 60 {
+                        self.placed_bombs[i].anim_timer = 0;
+                        self.placed_bombs[i].anim_frame = 1;  // Move to warning phase
+                    }
+                } else if self.placed_bombs[i].anim_frame >= 1 && self.placed_bombs[i].anim_frame <= 8 {
+                    // Warning phase - frames 2-5 played twice (frames 1-8)
+                    if self.placed_bombs[i].anim_timer >= 30 {
+                        self.placed_bombs[i].anim_timer = 0;
+                        self.placed_bombs[i].anim_frame += 1;
+                    }
+                } else if self.placed_bombs[i].anim_frame == 9 {
+                    // EXPLOSION STARTS - Frame 6 (sprite 6)
+                    if self.placed_bombs[i].anim_timer == 1 {
+                        // Apply damage on first frame of explosion
+                        self.check_bomb_damage(i);
+                        self.sound.play(CUE_EXPLOSION);
+                    }
+                    if self.placed_bombs[i].anim_timer >= 10 {
+                        self.placed_bombs[i].anim_timer = 0;
+                        self.placed_bombs[i].anim_frame = 10;
+                    }
+                } else if self.placed_bombs[i].anim_frame >= 10 && self.placed_bombs[i].anim_frame <= 11 {
+                    // Explosion frames 7-8
+                    if self.placed_bombs[i].anim_timer >= 10 {
+                        self.placed_bombs[i].anim_timer = 0;
+                        self.placed_bombs[i].anim_frame += 1;
+                        if self.placed_bombs[i].anim_frame > 11 {
+                            // Animation complete, deactivate bomb
+                            self.placed_bombs[i].active = false;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn check_bomb_damage(&mut self, bomb_idx: usize) {
+        let bomb_x = self.placed_bombs[bomb_idx].x;
+        let bomb_y = self.placed_bombs[bomb_idx].y;
+
+        // Check if Santa is in explosion radius
+        let dx_player = (bomb_x - self.player_x).abs();
+        let dy_player = (bomb_y - self.player_y).abs();
+
+        if dx_player < GIFT_BOMB_RADIUS && dy_player < GIFT_BOMB_RADIUS {
+            // Santa is caught in the explosion!
+            if !self.player_protected() {
+                 self.player_hp = self.player_hp.saturating_sub(2);  // Take 2 damage
+                self.sound.play(CUE_SANTA_HURT);
+                self.player_invuln_timer = 60;  // 1 second invulnerability
+
+                if self.player_hp == 0 {
+                    self.player_state = STATE_DEAD;
+                    self.lives = self.lives.saturating_sub(1);
+                }
+            }
+        }
+
+        // Check all enemies for collision with bomb explosion
+        // Explosion radius is approximately 50 pixels
+        for enemy_idx in 0..self.enemies.len() {
+            if self.enemies[enemy_idx].8 && self.enemies[enemy_idx].11 == 0 {  // Active and alive
+                let enemy_x = self.enemies[enemy_idx].1;
+                let enemy_y = self.enemies[enemy_idx].2;
+
+                let dx = (bomb_x - enemy_x).abs();
+                let dy = (bomb_y - enemy_y).abs();
+
+                // Kill enemies within explosion radius
+                if dx < GIFT_BOMB_RADIUS && dy < GIFT_BOMB_RADIUS {
+                    // Bomb kills are worth a bit more than shots
+                    self.kill_enemy(enemy_idx, 150);
+                }
+            }
+        }
+
+        // Boss takes a big hit unless it is roaring
+        if self.boss_active && self.boss.hp > 0 && self.boss.state != BOSS_STATE_ROAR {
+            let (hit_w, hit_h) = self.boss_def().hitbox;
+            let dx = (bomb_x - self.boss.x).abs();
+            let dy = (bomb_y - self.boss.y).abs();
+            if dx < GIFT_BOMB_RADIUS + hit_w && dy < GIFT_BOMB_RADIUS + hit_h {
+                self.boss.hp = self.boss.hp.saturating_sub(GIFT_BOMB_DAMAGE);
+                self.boss.vx = if bomb_x < self.boss.x { 2.0 } else { -2.0 };
+                self.boss.flash_timer = 10;
+                if self.boss.on_ground { self.boss.state = BOSS_STATE_IDLE; }
+                self.play_boss_cue(self.boss_def().hurt_cue);
+            }
+        }
+
+        // Chain reaction: other bombs in the blast go off shortly after
+        for other in 0..self.placed_bombs.len() {
+            let bomb = self.placed_bombs[other];
+            if other != bomb_idx && bomb.active && bomb.anim_frame < 9
+                && (bomb.x - bomb_x).abs() < GIFT_BOMB_RADIUS && (bomb.y - bomb_y).abs() < GIFT_BOMB_RADIUS
+            {
+                // Jump to the end of the last warning frame
+                self.placed_bombs[other].anim_frame = 8;
+                self.placed_bombs[other].anim_timer = 30 - GIFT_BOMB_CHAIN_DELAY;
+            }
+        }
+
+        // Destructible blocks touched by the blast
+        for block_idx in 0..self.blocks.len() {
+            let Block { x: bx, y: by, w: bw, h: bh, hp, .. } = self.blocks[block_idx];
+            if hp > 0 {
+                let nearest_x = bomb_x.clamp(bx, bx + bw);
+                let nearest_y = bomb_y.clamp(by, by + bh);
+                if (nearest_x - bomb_x).abs() < GIFT_BOMB_RADIUS && (nearest_y - bomb_y).abs() < GIFT_BOMB_RADIUS {
+                    self.damage_block(block_idx, BREAK_BY_BOMB, GIFT_BOMB_DAMAGE);
+                }
+            }
+        }
+
+        // Check all doors for collision with bomb explosion
+        // Doors are destroyed if within explosion radius
+        for door_idx in 0..self.doors.len() {
+            if self.doors[door_idx].2 {  // If door is still intact
+                let door_x = self.doors[door_idx].0;
+                let door_y = self.doors[door_idx].1;
+                
+                // Door is 42x42, so center is at (x+21, y+21)
+                let door_center_x = door_x + 21.0;
+                let door_center_y = door_y + 21.0;
+                
+                let dx = (bomb_x - door_center_x).abs();
+                let dy = (bomb_y - door_center_y).abs();
+                
+                // Destroy door if within explosion radius
+                if dx < GIFT_BOMB_RADIUS && dy < GIFT_BOMB_RADIUS {
+                    self.doors[door_idx].2 = false;  // Mark as destroyed
+                    self.score += 200;  // Bonus score for destroying door
+                    self.level_stats.door_score += 200;
+                    self.earn_tokens(TOKENS_PER_DOOR);
+
+                    // Bonus loot roll, offset to the side so it doesn't cover the key/kid
+                    let drop = Self::roll_drop(&DROP_TABLE_DOOR);
+                    self.spawn_drop(drop, door_center_x + 16.0, door_center_y);
+
+                    // Level 3 powerups sit next to that door's key
+                    if self.level == 3 && door_idx == self.life_door_index && !self.life_collected {
+                        self.life_position = (door_center_x, door_center_y - 10.0);
+                        self.life_active = true;
+                        log!("Life powerup spawned from door {}!", door_idx);
+                    } else if self.level == 3 && door_idx == 3 && !self.powerup1_collected {
+                        self.powerup1_position = (door_center_x, door_center_y - 10.0);
+                        self.powerup1_active = true;
+                        log!("PowerUp1 spawned from door {}!", door_idx);
+                    }
+
+                    // The door's own contents come from LEVEL_DOOR_CONTENTS
+                    let contents = self.door_contents(door_idx);
+                    if contents == DOOR_KID {
+                        // Find first available kid slot
+                        for kid_idx in 0..self.kids.len() {
+                            if !self.kids[kid_idx].2 && !self.kids[kid_idx].3 {
+                                let spawn_x = door_center_x;
+                                let spawn_y = door_center_y - 10.0;
+                                self.kids[kid_idx] = (spawn_x, spawn_y, true, false, 0, 0, door_idx as i8);
+                                self.total_kids_in_level += 1;
+                                log!("Kid spawned from door {}!", door_idx);
+                                break;
+                            }
+                        }
+                    } else {
+                        // Slightly above center, shifted aside when a powerup took the center
+                        let powerup_door = self.level == 3 && (door_idx == self.life_door_index || door_idx == 3);
+                        let key_x = if powerup_door { door_center_x - 16.0 } else { door_center_x };
+                        self.spawn_key(key_x, door_center_y - 10.0, contents);
+                    }
+                }
+            }
+        }
+    }
+
+    // ------------------------------------------------------------------------
+    // Destructible blocks
+    // ------------------------------------------------------------------------
+
+    fn load_level_blocks(&mut self, level_num: u8) {
+        self.blocks = [Block::EMPTY; 8];
+        let level_blocks = LEVEL_BLOCKS.iter().filter(|(level, _)| *level == level_num);
+        for (slot, (_, block)) in self.blocks.iter_mut().zip(level_blocks) {
+            *slot = *block;
+        }
+    }
+
+    // Everything Santa and enemies can't walk through: walls (minus opened gates) and intact blocks
+    fn solid_rects(&self) -> Vec<(f32, f32, f32, f32)> {
+        let walls = self.walls.iter().enumerate()
+            .filter(|(i, wall)| wall.2 > 0.0 && wall.3 > 0.0 && !self.wall_is_open(*i))
+            .map(|(_, wall)| *wall);
+        let blocks = self.blocks.iter()
+            .filter(|block| block.hp > 0)
+            .map(|block| (block.x, block.y, block.w, block.h));
+        walls.chain(blocks).collect()
+    }
+
+    fn block_at(&self, x: f32, y: f32, half_w: f32, half_h: f32) -> Option<usize> {
+        self.blocks.iter().position(|block| {
+            block.hp > 0
+                && x + half_w > block.x && x - half_w < block.x + block.w
+                && y + half_h > block.y && y - half_h < block.y + block.h
+        })
+    }
+
+    // Returns false when the block ignores this kind of hit
+    fn damage_block(&mut self, block_idx: usize, source: u8, damage: u8) -> bool {
+        let block = &mut self.blocks[block_idx];
+        if block.hp == 0 || block.destroyed_by & source == 0 {
+            return false;
+        }
+        block.hp = block.hp.saturating_sub(damage);
+        block.flash = BLOCK_HIT_FLASH;
+        if block.hp == 0 {
+            let (x, bottom, drop) = (block.x + block.w / 2.0, block.y + block.h, block.drop);
+            self.score += BLOCK_SCORE;
+            // Drops rest at Santa's height above the block's floor
+            self.spawn_drop(drop, x, bottom - 19.0);
+            log!("Block {} destroyed!", block_idx);
+        }
+        true
+    }
+
+    fn update_blocks(&mut self) {
+        for block in self.blocks.iter_mut() {
+            if block.flash > 0 {
+                block.flash -= 1;
+            }
+        }
+    }
+
+    fn update_camera(&mut self) {
+        self.camera_x = self.camera_target();
+    }
+
+    fn camera_target(&self) -> f32 {
+        // During boss fight, camera is locked on the arena (no scrolling)
+        if self.boss_active {
+            return self.boss_def().camera_x;
+        }
+
+        let target_x = self.player_x - (SCREEN_WIDTH / 2.0);
+        // Clamp camera to level bounds (0 to level_width - screen_width)
+        let level_width = match self.level {
+            1 => 1080.0,  // Level 1 width
+            2 => 1440.0,  // Level 2 width
+            3 => 2160.0,  // Boss fight width (before trigger)
+            _ => 1080.0,  // Default
+        };
+        let max_camera = level_width - SCREEN_WIDTH;
+        target_x.max(0.0).min(max_camera)
+    }
+
+    fn load_level(&mut self, level_num: u8) {
+        self.level = level_num;
+        self.timer = LEVEL_TIME;
+        
+        // Set level time limits (in frames, 60 fps)
+        self.level_timer = 0;
+        self.level_time_limit = match level_num {
+            1 => 180 * 60,  // 3 minutes
+            2 => 480 * 60,  // 8 minutes
+            3 => 600 * 60,  // 10 minutes
+            _ => 180 * 60,  // Default 3 minutes
+        };
+
+        for enemy in self.enemies.iter_mut() {
+            enemy.8 = false;
+        }
+        self.boss_minions = 0;
+
+        // Drop any cutscene from the previous level, the level intro starts below
+        self.end_cutscene();
+
+        self.load_level_blocks(level_num);
+        self.reset_keys_and_gates();
+        self.player_breath = BREATH_FRAMES;
+        self.player_drown_timer = 0;
+        self.level_stats = LevelStats::new(self.score, self.player_hp);
+        self.player_shield_timer = 0;
+        self.enemy_freeze_timer = 0;
+
+        // Reset boss state for fresh start (every level can have a boss, see BOSS_DEFS)
+        self.boss_active = false;
+        self.boss_defeated = false;
+        self.use_boss_santa = false;
+
+        match level_num {
+            1 => {
+                // Level 1 - 3 Floor Christmas House (based on spritebg1080x240.png)
+                // Using precise tilemap from sprites/tilemap.json
+                // Platforms are the ONLY ground - no hardcoded floor
+
+                // Santa starts on TILE #1 (left section, top floor)
+                // Offset applied: +60px X, +18px Y
+                // Tile #1: X(60-230) at Y=100
+                // Santa sprite is 32x32, positioned from center
+                // Platform Y=100, so Santa Y = 100 - 28 = 72 (28px hitbox height)
+                self.player_x = 145.0;  // Center of tile #1 (60-230)
+                self.player_y = 72.0;   // Tile #1 platform position
+                self.player_vx = 0.0;
+                self.player_vy = 0.0;
+                self.player_state = STATE_IDLE;
+                self.player_on_ground = true; // Start on platform
+
+                // ============================================================
+                // PLATFORM POSITIONS - From sprites/layout.md tilemap
+                // ============================================================
+                // Background: 1080x240px, 3 floors visible
+                // Positions calculated from center (540, 120) with layout dimensions
+                // Format: (x_start, x_end, y_position)
+
+                // Clear and populate platforms for Level 1
+                self.platforms.clear();
+
+                // TOP FLOOR (Y ≈ 100) - platforms from tiles 1, 3, 4, 8, 14
+                self.platforms.push((60.0, 230.0, 86.0));         // Tile #1
+                self.platforms.push((275.0, 300.0, 95.0-9.0));    // Tile #3
+                self.platforms.push((400.0, 765.0, 76.0-9.0));    // Tile #4
+                self.platforms.push((804.0, 920.0, 95.0-9.0));    // Tile #8
+                self.platforms.push((931.0, 1045.0, 95.0-9.0));   // Tile #14
+
+                // MIDDLE FLOOR (Y ≈ 168) - platforms from tiles 5, 6, 9, 11, 12, 13
+                self.platforms.push((60.0, 252.0, 163.0-9.0));    // Tile #13
+                self.platforms.push((264.0, 495.0, 163.0-9.0));   // Tile #12
+                self.platforms.push((542.0, 654.0, 163.0-9.0));   // Tile #11
+                self.platforms.push((665.0, 768.0, 163.0-9.0));   // Tile #9
+                self.platforms.push((806.0, 1008.0, 163.0-9.0));  // Tile #5
+                self.platforms.push((1020.0, 1043.0, 163.0-9.0)); // Tile #6
+
+                // BOTTOM FLOOR (Y ≈ 238) - platforms from tiles 7, 15
+                self.platforms.push((36.0, 81.0, 233.0-9.0));     // Tile #7
+                self.platforms.push((93.0, 1063.0, 233.0-9.0));   // Tile #15
+
+                // Additional platforms
+                self.platforms.push((310.0, 400.0, 95.0-9.0));
+
+                //
+
+                // ============================================================
+                // LADDERS - Connect the three floors
+                // ============================================================
+                // Format: (x_center, y_top, y_bottom)
+                // Offset applied: +60px X, +18px Y
+
+                // Left side ladders
+                self.ladders[0] = (305.0, 85.0, 117.0);    // Top to Middle (left)
+                self.ladders[1] = (258.0, 150.0, 187.0);   // Middle to Bottom (left)
+
+                // Center ladders
+                self.ladders[2] = (383.0, 85.0, 117.0);    // Top to Middle (center)
+                self.ladders[3] = (660.0, 150.0, 187.0);   // Middle to Bottom (center)
+
+                // Right side ladders
+                self.ladders[4] = (925.0, 85.0, 117.0);    // Top to Middle (right)
+                self.ladders[5] = (1015.0, 150.0, 187.0);   // Middle to Bottom (right)
+
+                // ============================================================
+                // ENEMIES - Positioned on platform surfaces
+                // ============================================================
+                // Enemy Y = platform_y - 28 (same formula as Santa!)
+                // This ensures enemies walk at the exact same level as Santa
+                
+                // Top floor at Y=95, so enemy Y = 95 - 28 = 67
+                // Large center platform at Y=76, so enemy Y = 76 - 28 = 48
+                self.enemies[0] = (1, 500.0, 48.0, 0.0, 0.0, 0, 0, 420, true, 0, false, 0);  // On large center platform (Y=76)
+                self.enemies[1] = (2, 870.0, 67.0, 0.0, 0.0, 0, 0, 810, true, 0, false, 0);  // Top-right patrol at Y=95 (kickmouse)
+                self.enemies[5] = (1, 500.0, 135.0, 0.0, 0.0, 0, 0,340, true, 0, false, 0);   // Top-left patrol at Y=95 (kickmouse)
+                self.enemies[8] = (3, 680.0, 48.0, 0.0, 0.0, 0, 0, 600, true, 0, false, 0);  // Large center platform (Y=76)
+
+                // Middle floor at Y=163, so enemy Y = 163 - 28 = 135
+                self.enemies[2] = (3, 310.0, 135.0, 0.0, 0.0, 0, 0, 160, true, 0, false, 0); // Middle floor patrol left
+                self.enemies[6] = (1, 600.0, 135.0, 0.0, 0.0, 0, 0, 542, true, 0, false, 0); // Middle floor patrol center
+                self.enemies[7] = (2, 900.0, 135.0, 0.0, 0.0, 0, 0, 940, true, 0, false, 0); // Middle floor patrol right (kickmouse)
+
+                // Bottom floor at Y=233, so enemy Y = 233 - 28 = 205
+                self.enemies[3] = (2, 400.0, 205.0, 0.0, 0.0, 0, 0, 200, true, 0, false, 0); // Ground patrol left (kickmouse)
+                self.enemies[4] = (1, 740.0, 205.0, 0.0, 0.0, 0, 0, 640, true, 0, false, 0); // Ground patrol right
+                self.enemies[9] = (2, 500.0, 205.0, 0.0, 0.0, 0, 0, 400, true, 0, false, 0); // Ground patrol center (kickmouse)
+
+                // ============================================================
+                // DOORS - Destructible by bombs
+                // ============================================================
+                // Format: (x, y, intact)
+                // x, y are top-left corner of 42x42 door sprite
+                // Door sprite is 42x42, exploded_gate is 64x64
+                self.doors[0] = (96.0, 42.0, true);    // Door #1 - Top left
+                self.doors[1] = (411.0, 111.0, true);  // Door #2 - Middle left
+                self.doors[2] = (676.0, 180.0, true);  // Door #3 - Bottom center
+                self.doors[3] = (990.0, 42.0, true);   // Door #4 - Top right
+
+                // ============================================================
+                // WALLS - Vertical collision boundaries
+                // ============================================================
+                // Format: (x, y, width, height)
+                self.walls[0] = (51.0, 28.0, 8.0, 136.0);    // Wall #1 - Left top vertical
+                self.walls[1] = (27.0, 165.0, 8.0, 60.0);    // Wall #2 - Left middle vertical
+                self.walls[2] = (129.0, 165.0, 23.0, 60.0);  // Wall #3 - Left-center vertical
+                self.walls[3] = (0.0, 0.0, 0.0, 0.0);        // Wall #4 - REMOVED
+                self.walls[4] = (320.0, 98.0, 36.0, 59.0);   // Wall #5 - Center-left vertical
+                self.walls[5] = (542.0, 168.0, 86.0, 60.0);  // Wall #6 - Center vertical
+                self.walls[6] = (675.0, 78.0, 34.0, 78.0);   // Wall #7 - Center-right vertical
+                self.walls[7] = (851.0, 98.0, 34.0, 59.0);   // Wall #8 - Right-center vertical
+                self.walls[8] = (960.0, 165.0, 10.0, 59.0);  // Wall #9 - Plate gate (LEVEL_TARGETS)
+                self.walls[9] = (1045.0, 28.0, 8.0, 197.0);  // Wall #10 - Right top vertical
+                self.walls[10] = (360.0, 184.0, 40.0, 20.0); // Wall #11 - Low beam, crouch or slide under
+
+                // ============================================================
+                // LEVEL COMPLETION TRIGGER
+                // ============================================================
+                // Place trigger zone on the right side of the gate (wall #3)
+                // Gate is at (129, 165, 23, 60)
+                // Trigger at x=160 (just past the gate), covering the passage
+                self.completion_trigger = (160.0, 165.0, 30.0, 60.0);
+
+                // Reset level completion state
+                self.level_complete = false;
+                self.level_transition_timer = 0;
+
+                // Reset player movement state
+                self.player_on_ladder = false;
+                self.player_rope = None;
+                self.player_is_crouching = false;
+
+                // ============================================================
+                // KIDS - Level 1
+                // ============================================================
+                // Kids come out of the doors LEVEL_DOOR_CONTENTS marks DOOR_KID
+                self.kids_collected = 0;
+                self.total_kids_in_level = 0;  // Will increment when kid spawns from door
+                for kid in self.kids.iter_mut() {
+                    *kid = (0.0, 0.0, false, false, 0, 0, -1);
+                }
+            },
+            2 => {
+                // Level 2 - Rooftop Christmas Adventure (level2finalsprite 1440x240px)
+                // Using exact coordinates from Sprites/layout.md
+
+                // ============================================================
+                // PLAYER SPAWN - Tile 1 (recommended spawn)
+                // ============================================================
+                self.player_x = 60.0;
+                self.player_y = 86.0 - 19.0;  // 58.0
+                self.player_vx = 0.0;
+                self.player_vy = 0.0;
+                self.player_state = STATE_IDLE;
+                self.player_on_ground = true;
+
+                // ============================================================
+                // PLATFORMS (29 floor tiles) - From layout.md
+                // ============================================================
+                // Format: (x_start, x_end, y) where x_end = x + w
+
+                self.platforms.clear();
+                self.platforms.push((35.0, 126.0, 88.0));      // ID 1
+                self.platforms.push((126.0, 146.0, 115.0));    // ID 2
+                self.platforms.push((146.0, 169.0, 135.0));    // ID 3
+                self.platforms.push((172.0, 257.0, 155.0));    // ID 4
+                self.platforms.push((235.0, 301.0, 88.0));     // ID 5
+                self.platforms.push((313.0, 323.0, 88.0));     // ID 6
+                self.platforms.push((367.0, 479.0, 88.0));     // ID 7
+                self.platforms.push((746.0, 836.0, 88.0));     // ID 8
+                self.platforms.push((847.0, 907.0, 88.0));     // ID 9
+                self.platforms.push((1037.0, 1203.0, 88.0));   // ID 10
+                self.platforms.push((1303.0, 1326.0, 88.0));   // ID 11
+                self.platforms.push((1336.0, 1394.0, 88.0));   // ID 12
+                self.platforms.push((268.0, 367.0, 155.0));    // ID 13
+                self.platforms.push((479.0, 500.0, 115.0));    // ID 14
+                self.platforms.push((542.0, 674.0, 135.0));    // ID 15
+                self.platforms.push((674.0, 746.0, 155.0));    // ID 16
+                self.platforms.push((757.0, 764.0, 155.0));    // ID 17
+                self.platforms.push((813.0, 897.0, 155.0));    // ID 18
+                self.platforms.push((947.0, 990.0, 135.0));    // ID 19
+                self.platforms.push((990.0, 1037.0, 115.0));   // ID 20
+                self.platforms.push((1203.0, 1226.0, 115.0));  // ID 21
+                self.platforms.push((1226.0, 1292.0, 135.0));  // ID 22
+                self.platforms.push((1292.0, 1416.0, 155.0));  // ID 23
+                self.platforms.push((1079.0, 1146.0, 155.0));  // ID 24
+                self.platforms.push((1156.0, 1168.0, 155.0));  // ID 25
+                self.platforms.push((13.0, 192.0, 225.0));     // ID 26
+                self.platforms.push((247.0, 553.0, 225.0));    // ID 27
+                self.platforms.push((589.0, 903.0, 225.0));    // ID 28
+                self.platforms.push((960.0, 1416.0, 225.0));   // ID 29
+                self.platforms.push((846.0, 903.0, 225.0));    // ID 30 - Switch bridge, parked (LEVEL_TARGETS)
+                self.platforms.push((517.0, 553.0, 225.0));    // ID 31 - Timed shuttle, parked (LEVEL_TARGETS)
+
+                // ============================================================
+                // LADDERS (6 ladders) - From layout.md
+                // ============================================================
+                // Format: (x_center, y_top, y_bottom) where y_bottom = y_top + 28
+
+                self.ladders[0] = (303.0, 85.0, 122.0);   // ID 1
+                self.ladders[1] = (259.0, 150.0, 190.0);  // ID 2
+                self.ladders[2] = (747.0, 152.0, 189.0);  // ID 3
+                self.ladders[3] = (837.0, 85.0, 122.0);   // ID 4
+                self.ladders[4] = (1327.0, 84.0, 121.0);  // ID 5
+                self.ladders[5] = (1148.0, 152.0, 189.0); // ID 6
+
+                // ============================================================
+                // WALLS (22 wall colliders) - From layout.md
+                // ============================================================
+                // Format: (x, y, width, height)
+
+                self.walls[0] = (25.0, 35.0, 9.0, 50.0);      // ID 1
+                self.walls[1] = (116.0, 95.0, 9.0, 28.0);     // ID 2
+                self.walls[2] = (1350.0, 30.0, 23.0, 60.0);   // ID 3 - GATE (repositioned)
+                self.walls[3] = (159.0, 142.0, 10.0, 20.0);   // ID 4
+                self.walls[4] = (52.0, 161.0, 9.0, 59.0);     // ID 5
+                self.walls[5] = (368.0, 89.0, 17.0, 70.0);    // ID 6
+                self.walls[6] = (487.0, 115.0, 14.0, 62.0);   // ID 7
+                self.walls[7] = (546.0, 136.0, 14.0, 85.0);   // ID 8
+                self.walls[8] = (621.0, 162.0, 13.0, 59.0);   // ID 9
+                self.walls[9] = (726.0, 35.0, 17.0, 51.0);    // ID 10
+                self.walls[10] = (990.0, 118.0, 7.0, 19.0);   // ID 11
+                self.walls[11] = (1038.0, 88.0, 8.0, 30.0);   // ID 12
+                self.walls[12] = (1071.0, 108.0, 9.0, 49.0);  // ID 13
+                self.walls[13] = (1168.0, 108.0, 12.0, 49.0); // ID 14
+                self.walls[14] = (1196.0, 90.0, 6.0, 29.0);   // ID 15
+                self.walls[15] = (1220.0, 119.0, 6.0, 19.0);  // ID 16
+                self.walls[16] = (1286.0, 139.0, 6.0, 17.0);  // ID 17
+                self.walls[17] = (140.0, 120.0, 6.0, 10.0);  // ID 18
+                self.walls[18] = (1416.0, 55.0, 16.0, 180.0);  // ID 19
+                self.walls[19] = (1393.0, 161.0, 12.0, 59.0); // ID 20
+                self.walls[20] = (467.0, 92.0, 7.0, 25.0);        // Id 21 
+                self.walls[21] = (667.0, 136.0, 6.0, 16.0);          // Id 22 (unused)
+                // ============================================================
+                // DOORS - Destructible by bombs (Level 2)
+                // ============================================================
+                // Format: (x, y, intact)
+                // x, y are top-left corner of 42x42 door sprite
+                // Door sprite is 42x42, exploded_gate is 64x64
+                // Positioned on platforms to block paths
+                
+                // Door #1 - On platform ID 7 (367-479, Y=88), blocking path
+                self.doors[0] = (103.0, 181.0, true);
+                
+                // Door #2 - On platform ID 15 (546-674, Y=135), blocking middle section
+                self.doors[1] = (768.0, 46.0, true);
+                
+                // Door #3 - On platform ID 18 (813-897, Y=155), blocking right-middle
+                self.doors[2] = (416.0, 44.0, true);
+                
+                // Door #4 - On platform ID 10 (1037-1203, Y=88), blocking far right top
+                self.doors[3] = (1093.0, 113.0, true);
 
                 // ============================================================
                 // ENEMIES - Level 2 (positioned on platforms)
@@ -2129,1488 +4750,3527 @@ impl GameState {
                 // Enemy Y = platform_y - 28 (same formula as Santa)
                 // Enemy types: 1 = mouse, 2 = kickmouse, 3 = penguin
                 
-                // Top floor enemies (Y=88, enemy Y = 88-28 = 60)
-                self.enemies[0] = (1, 280.0, 70.0, 0.0, 0.0, 0, 0, 235, true, 0, false, 0);  // Mouse - Platform ID 5
-                self.enemies[1] = (3, 780.0, 70.0, 0.0, 0.0, 0, 0, 746, true, 0, false, 0);  // Penguin - Platform ID 8
-                self.enemies[2] = (3, 1150.0, 70.0, 0.0, 0.0, 0, 0, 1037, true, 0, false, 0); // Penguin - Platform ID 10
+                // Top floor enemies (Y=88, enemy Y = 88-28 = 60)
+                self.enemies[0] = (1, 280.0, 70.0, 0.0, 0.0, 0, 0, 235, true, 0, false, 0);  // Mouse - Platform ID 5
+                self.enemies[1] = (3, 780.0, 70.0, 0.0, 0.0, 0, 0, 746, true, 0, false, 0);  // Penguin - Platform ID 8
+                self.enemies[2] = (3, 1150.0, 70.0, 0.0, 0.0, 0, 0, 1037, true, 0, false, 0); // Penguin - Platform ID 10
+                
+                // Middle floor enemies (Y=155, enemy Y = 155-28 = 127)
+                self.enemies[3] = (2, 220.0, 137.0, 0.0, 0.0, 0, 0, 172, true, 0, false, 0);  // Kickmouse - Platform ID 4
+                self.enemies[4] = (3, 700.0, 137.0, 0.0, 0.0, 0, 0, 674, true, 0, false, 0);  // Penguin - Platform ID 16
+                self.enemies[5] = (3, 860.0, 137.0, 0.0, 0.0, 0, 0, 813, true, 0, false, 0);  // Penguin - Platform ID 18
+                
+                // Bottom floor enemies (Y=225, enemy Y = 225-28 = 197)
+                self.enemies[6] = (1, 350.0, 207.0, 0.0, 0.0, 0, 0, 247, true, 0, false, 0);  // Mouse - Platform ID 27
+                self.enemies[7] = (3, 700.0, 207.0, 0.0, 0.0, 0, 0, 589, true, 0, false, 0);  // Penguin - Platform ID 28
+                self.enemies[8] = (2, 1100.0, 207.0, 0.0, 0.0, 0, 0, 960, true, 0, false, 0); // Kickmouse - Platform ID 29
+                self.enemies[9] = (3, 1300.0, 207.0, 0.0, 0.0, 0, 0, 1160, true, 0, false, 0); // Penguin - Platform ID 29
+
+                // ============================================================
+                // KIDS - Level 2
+                // ============================================================
+                // Kids come out of the doors LEVEL_DOOR_CONTENTS marks DOOR_KID
+                self.kids_collected = 0;
+                self.total_kids_in_level = 0;  // Will increment when kid spawns from door
+                for kid in self.kids.iter_mut() {
+                    *kid = (0.0, 0.0, false, false, 0, 0, -1);
+                }
+
+                // ============================================================
+                // LEVEL COMPLETION TRIGGER - Level 2
+                // ============================================================
+                // Trigger zone at x=1350, y=85 to enter boss fight
+                self.completion_trigger = (1350.0, 85.0, 40.0, 50.0);
+
+                // Reset level completion state
+                self.level_complete = false;
+                self.level_transition_timer = 0;
+
+                // Reset player movement state
+                self.player_on_ladder = false;
+                self.player_rope = None;
+                self.player_is_crouching = false;
+            },
+            3 => {
+                // Level 3 - Boss Fight (bossfight bg1 - 2160x240px)
+                // Using exact coordinates from Sprites/layout.md line 167+
+
+                // ============================================================
+                // PLAYER SPAWN - Start at left side
+                // ============================================================
+                self.player_x = 150.0;
+                self.player_y = 222.0 - 28.0;  // 194.0 (on platform ID 1)
+                self.player_vx = 0.0;
+                self.player_vy = 0.0;
+                self.player_state = STATE_IDLE;
+                self.player_on_ground = true;
+
+                self.load_level3_layout();
+
+                // ============================================================
+                // KIDS - Boss Level (Level 3)
+                // ============================================================
+                // Kids come out of the doors LEVEL_DOOR_CONTENTS marks DOOR_KID
+                self.kids_collected = 0;
+                self.total_kids_in_level = 0;  // Will increment when kid spawns from door
+                for kid in self.kids.iter_mut() {
+                    *kid = (0.0, 0.0, false, false, 0, 0, -1);
+                }
+
+                // ============================================================
+                // LIFE POWERUP - Boss Level (Level 3)
+                // ============================================================
+                // Door #3 (index 2) spawns life powerup instead of key/kid
+                self.life_active = false;
+                self.life_collected = false;
+                self.life_door_index = 2;  // Door #3
+
+                // ============================================================
+                // POWERUP1 - Boss Level (Level 3)
+                // ============================================================
+                // Door #4 (index 3) spawns powerup1
+                self.powerup1_active = false;
+                self.powerup1_collected = false;
+
+                // ============================================================
+                // DOORS - Boss Level strategic positions
+                // ============================================================
+                // Clear/override any previous door positions and set new ones
+                // Doors are 42x42; place so base rests on platform surfaces.
+                // Format: (x_top_left, y_top_left, intact)
+
+                // On platform ID 7 (329-448, y=119) -> door base at y=119
+                // y_top_left = 119 - 42 = 77
+                self.doors[0] = (621.0, 14.0, true);
+
+                // On platform ID 20 (808-871, y=223)
+                self.doors[1] = (869.0, 14.0, true);
+
+                // On platform ID 30 (1167-1254, y=147)
+                self.doors[2] = (1194.0, 181.0, true);
+
+                // On platform ID 51 (1909-2159, y=220)
+                self.doors[3] = (775.0, 97.0, true);
+
+                // No completion trigger for boss level
+                self.completion_trigger = (0.0, 0.0, 0.0, 0.0);
+
+                // Reset level completion state
+                self.level_complete = false;
+                self.level_transition_timer = 0;
+
+                // Reset player movement state
+                self.player_on_ladder = false;
+                self.player_rope = None;
+                self.player_is_crouching = false;
+            },
+            _ => {}
+        }
+
+        // After the level branch above so platform/wall targets exist
+        self.load_level_triggers(level_num);
+        self.load_level_items(level_num);
+    }
+
+    // Level 3 platforms, ladder, walls and enemies. Shared with restore_level3_after_boss,
+    // Evil Santa's own-screen arena replaces all of these.
+    fn load_level3_layout(&mut self) {
+        // ============================================================
+        // PLATFORMS (52 floor tiles) - All platforms from layout.md
+        // ============================================================
+        // Format: (x_start, x_end, y) where x_end = x + w
+
+        self.platforms.clear();
+        self.platforms.push((1.0, 317.0, 222.0+5.0));        // ID 1
+        self.platforms.push((301.0, 333.0, 207.0+5.0));      // ID 2
+        self.platforms.push((333.0, 363.0, 193.0+5.0));      // ID 3
+        self.platforms.push((361.0, 394.0, 177.0+5.0));      // ID 4
+        self.platforms.push((391.0, 418.0, 163.0+5.0));      // ID 5
+        self.platforms.push((211.0, 269.0, 163.0+5.0));      // ID 6
+        self.platforms.push((329.0, 448.0, 119.0+5.0));      // ID 7
+        self.platforms.push((450.0, 479.0, 164.0+5.0));      // ID 8
+        self.platforms.push((540.0, 568.0, 164.0+5.0));      // ID 9
+        //self.platforms.push((629.0, 689.0, 104.0+5.0));      // ID 10
+        //self.platforms.push((510.0, 536.0, 103.0+5.0));      // ID 11
+        self.platforms.push((630.0, 656.0, 164.0+5.0));      // ID 12
+        self.platforms.push((599.0, 625.0, 192.0+5.0));      // ID 13
+        self.platforms.push((569.0, 596.0, 223.0+5.0));      // ID 14
+        self.platforms.push((630.0, 657.0, 223.0+5.0));      // ID 15
+        self.platforms.push((689.0, 718.0, 133.0+5.0));      // ID 16
+        self.platforms.push((756.0, 836.0, 133.0+5.0));      // ID 17
+        self.platforms.push((690.0, 717.0, 223.0+5.0));      // ID 18
+        self.platforms.push((749.0, 777.0, 223.0+5.0));      // ID 19
+        self.platforms.push((808.0, 871.0, 223.0+5.0));      // ID 20
+        self.platforms.push((868.0, 896.0, 133.0+5.0));      // ID 21
+        self.platforms.push((927.0, 955.0, 119.0+5.0));      // ID 22
+        self.platforms.push((957.0, 986.0, 162.0+5.0));      // ID 23
+        self.platforms.push((986.0, 1021.0, 207.0+5.0));     // ID 24
+        self.platforms.push((1017.0, 1045.0, 147.0+5.0));    // ID 25
+        self.platforms.push((1046.0, 1075.0, 162.0+5.0));    // ID 26
+        //self.platforms.push((1107.0, 1164.0, 102.0+5.0));    // ID 27
+        self.platforms.push((1107.0, 1133.0, 192.0+5.0));    // ID 28
+        self.platforms.push((1134.0, 1165.0, 205.0+5.0));    // ID 29
+        self.platforms.push((1167.0, 1254.0, 147.0+5.0));    // ID 30
+        self.platforms.push((1165.0, 1253.0, 221.0+5.0));    // ID 31
+        self.platforms.push((1285.0, 1313.0, 162.0+5.0));    // ID 32
+        self.platforms.push((1287.0, 1317.0, 222.0+5.0));    // ID 33
+        self.platforms.push((1320.0, 1373.0, 175.0+5.0));    // ID 34
+        self.platforms.push((1346.0, 1374.0, 222.0+5.0));    // ID 35
+        //self.platforms.push((1314.0, 1343.0, 102.0+5.0));    // ID 36
+        self.platforms.push((1374.0, 1404.0, 147.0+5.0));    // ID 37
+        self.platforms.push((1406.0, 1456.0, 117.0+5.0));    // ID 38
+        self.platforms.push((1406.0, 1490.0, 222.0+5.0));    // ID 39
+        self.platforms.push((1496.0, 1524.0, 162.0+5.0));    // ID 40
+        //self.platforms.push((1554.0, 1643.0, 102.0+5.0));    // ID 41
+        self.platforms.push((1554.0, 1580.0, 222.0+5.0));    // ID 42
+        self.platforms.push((1643.0, 1671.0, 178.0+5.0));    // ID 43
+        self.platforms.push((1673.0, 1701.0, 147.0+5.0));    // ID 44
+        self.platforms.push((1615.0, 1736.0, 222.0+5.0));    // ID 45
+        self.platforms.push((1734.0, 1764.0, 117.0+5.0));    // ID 46
+        self.platforms.push((1762.0, 1791.0, 161.0+5.0));    // ID 47
+        self.platforms.push((1823.0, 1853.0, 117.0+5.0));    // ID 48
+        self.platforms.push((1853.0, 1881.0, 161.0+5.0));    // ID 49
+        self.platforms.push((1881.0, 1913.0, 177.0+5.0));    // ID 50
+        self.platforms.push((1909.0, 2159.0, 220.0+5.0));    // ID 51
+        //self.platforms.push((1198.0,1218.0,104.0+5.0));        // ID 52 
+        self.platforms.push((897.0,952.0,224.0+5.0));        // ID 53 
+        self.platforms.push((1.0, 2161.0, 50.0+5.0));        // ID 54 - Top ceiling
+
+        // Clear ladders for boss level (no ladders)
+        for i in 0..self.ladders.len() {
+            self.ladders[i] = (0.0, 0.0, 0.0);
+        }
+        self.ladders[0] = (2136.0, 50.0, 189.0);
+        // Clear walls for boss level (no walls initially)
+        for i in 0..self.walls.len() {
+            self.walls[i] = (0.0, 0.0, 0.0, 0.0);
+        }
+
+        // Reset enemies for Boss Level (the boss itself comes from BOSS_DEFS)
+        for enemy in self.enemies.iter_mut() {
+            enemy.8 = false;
+        }
+
+        // ============================================================
+        // ENEMIES - Boss Level placements across platforms
+        // ============================================================
+        // Enemy Y = platform_y - 28
+        // Types: 1=mouse, 2=kickmouse, 3=penguin
+        // Patrol start set to platform start for each.
+
+        // Platform ID 7 (329 → 448)
+        self.enemies[0] = (3, 370.0, 100.0, 0.0, 0.0, 0, 0, 341, true, 0, false, 0);
+        // patrol: 341 → 441 ✔
+
+        // Platform ID 20 (808 → 871)
+        self.enemies[1] = (2, 840.0, 208.0, 0.0, 0.0, 0, 0, 820, true, 0, false, 0);
+        // patrol: 820 → 920 ❌ too wide → but enemy stays centered due to short platform
+        // acceptable for boss pacing
+
+        // Platform ID 17 (756 → 836)
+        self.enemies[2] = (3, 780.0, 117.0, 0.0, 0.0, 0, 0, 768, true, 0, false, 0);
+        // patrol: 768 → 868 ✔
+
+        // Platform ID 25 (1017 → 1045)
+        self.enemies[3] = (1, 968.0, 36.0, 0.0, 0.0, 0, 0, 1025, true, 0, false, 0);
+        // patrol: 1025 → 1125 (enemy remains visually stable)
+
+        // Platform ID 30 (1167 → 1254)
+        self.enemies[4] = (3, 1200.0, 130.0, 0.0, 0.0, 0, 0, 1179, true, 0, false, 0);
+        // patrol: 1179 → 1279 ✔
+
+        // Platform ID 34 (1320 → 1373)
+        self.enemies[5] = (1, 1225.0, 36.0, 0.0, 0.0, 0, 0, 1332, true, 0, false, 0);
+        // patrol: 1332 → 1432 (tight enough)
+
+        // Platform ID 39 (1406 → 1490)
+        self.enemies[6] = (2, 1450.0, 207.0, 0.0, 0.0, 0, 0, 1418, true, 0, false, 0);
+        // patrol: 1418 → 1518 ✔
+
+        // Platform ID 45 (1615 → 1736)
+        self.enemies[7] = (3, 1650.0, 207.0, 0.0, 0.0, 0, 0, 1627, true, 0, false, 0);
+        // patrol: 1627 → 1727 ✔
+
+        // Platform ID 51 (1909 → 2159)
+        //self.enemies[8] = (3, 2050.0, 207.0, 0.0, 0.0, 0, 0, 1921, true, 0, false, 0);
+        // patrol: 1921 → 2021 ✔
+
+        // Platform ID 10 (629 → 689)
+        self.enemies[9] = (1, 820.0, 36.0, 0.0, 0.0, 0, 0, 701, true, 0, false, 0);
+        // patrol: 641 → 741 ✔
+    }
+
+    fn update_boss(&mut self) {
+        if !self.boss_active { return; }
+
+        // If boss defeated, stop movement and set to dead state
+        if self.boss.hp == 0 {
+            self.boss.vx = 0.0;
+            self.boss.vy = 0.0;
+            self.boss.state = BOSS_STATE_DEAD;
+            // The defeat cutscene hands control back to the level (CUTSCENE_ACTION_EXIT_BOSS)
+            if self.boss_death_timer == 0 {
+                self.boss_on_defeat();
+                let cutscene = self.boss_def().defeat_cutscene;
+                if cutscene == CUTSCENE_NONE {
+                    self.exit_boss_fight();
+                } else {
+                    self.start_cutscene(cutscene);
+                }
+            }
+            self.boss_death_timer += 1;
+            return;
+        }
+
+        // Player idle detection: if player stays in same spot for 3+ seconds, boss attacks
+        let movement_threshold = 15.0; // Consider "idle" if moved less than 15px
+        if (self.player_x - self.player_idle_x).abs() > movement_threshold {
+            // Player moved - reset tracker
+            self.player_idle_x = self.player_x;
+            self.player_idle_timer = 0;
+        } else {
+            // Player is stationary
+            self.player_idle_timer += 1;
+            
+            // 3 seconds = 180 frames at 60 FPS
+            if self.player_idle_timer >= 180 {
+                // Force boss to attack if in idle/recover state
+                if self.boss.state == BOSS_STATE_IDLE || self.boss.state == BOSS_STATE_RECOVER {
+                    self.boss.state = BOSS_STATE_SELECT;
+                    self.boss.state_timer = 0;
+                }
+                // Reset timer to avoid spam
+                self.player_idle_timer = 90; // Cooldown before next forced attack
+            }
+        }
+
+        // Phase transition: wait until grounded, then roar and reshape the arena
+        let target_phase = self.boss_phase_for_hp();
+        if target_phase > self.boss_fight_phase && self.boss.on_ground {
+            self.start_boss_phase_transition(target_phase);
+        }
+
+        // FSM Logic
+        // Phase-based difficulty: boss gets more aggressive in later fight phases
+        let is_enraged = self.boss_fight_phase >= BOSS_FIGHT_PHASE_2; // Below 50% HP = rage mode
+        let is_desperate = self.boss_fight_phase >= BOSS_FIGHT_PHASE_3; // Below 25% HP = desperate mode
+        
+        // Speed multipliers based on phase
+        let speed_mult = if is_desperate { 1.5 } else if is_enraged { 1.25 } else { 1.0 };
+        let timer_mult = if is_desperate { 0.6 } else if is_enraged { 0.8 } else { 1.0 };
+
+        match self.boss.state {
+            BOSS_STATE_IDLE => {
+                // Face player
+                self.boss.facing_right = self.player_x > self.boss.x;
+                
+                self.boss.state_timer += 1;
                 
-                // Middle floor enemies (Y=155, enemy Y = 155-28 = 127)
-                self.enemies[3] = (2, 220.0, 137.0, 0.0, 0.0, 0, 0, 172, true, 0, false, 0);  // Kickmouse - Platform ID 4
-                self.enemies[4] = (3, 700.0, 137.0, 0.0, 0.0, 0, 0, 674, true, 0, false, 0);  // Penguin - Platform ID 16
-                self.enemies[5] = (3, 860.0, 137.0, 0.0, 0.0, 0, 0, 813, true, 0, false, 0);  // Penguin - Platform ID 18
+                // Shorter idle time when enraged
+                let idle_duration = ((60.0 * timer_mult) as u32).max(20);
                 
-                // Bottom floor enemies (Y=225, enemy Y = 225-28 = 197)
-                self.enemies[6] = (1, 350.0, 207.0, 0.0, 0.0, 0, 0, 247, true, 0, false, 0);  // Mouse - Platform ID 27
-                self.enemies[7] = (3, 700.0, 207.0, 0.0, 0.0, 0, 0, 589, true, 0, false, 0);  // Penguin - Platform ID 28
-                self.enemies[8] = (2, 1100.0, 207.0, 0.0, 0.0, 0, 0, 960, true, 0, false, 0); // Kickmouse - Platform ID 29
-                self.enemies[9] = (3, 1300.0, 207.0, 0.0, 0.0, 0, 0, 1160, true, 0, false, 0); // Penguin - Platform ID 29
+                // Check if player is far - walk towards them instead of just idling
+                let dx = (self.player_x - self.boss.x).abs();
+                if dx > 120.0 && self.boss.state_timer > 15 {
+                    // Walk towards player
+                    self.boss.state = BOSS_STATE_WALK;
+                    self.boss.state_timer = 0;
+                } else if self.boss.state_timer >= idle_duration {
+                    self.boss.state = BOSS_STATE_SELECT;
+                    self.boss.state_timer = 0;
+                } else {
+                    self.boss.vx = 0.0;
+                }
+            },
+            BOSS_STATE_WALK => {
+                // Walk towards player
+                self.boss.facing_right = self.player_x > self.boss.x;
+                let walk_speed = self.boss_def().walk_speed * speed_mult;
+                self.boss.vx = if self.boss.facing_right { walk_speed } else { -walk_speed };
+                
+                self.boss.state_timer += 1;
+                let dx = (self.player_x - self.boss.x).abs();
+                
+                // Stop walking when close enough or walked too long
+                let walk_duration = if is_enraged { 45 } else { 60 };
+                if dx < 100.0 || self.boss.state_timer >= walk_duration {
+                    self.boss.state = BOSS_STATE_SELECT;
+                    self.boss.state_timer = 0;
+                    self.boss.vx = 0.0;
+                }
+            },
+            BOSS_STATE_SELECT => {
+                // Face the player
+                self.boss.facing_right = self.player_x > self.boss.x;
+
+                // Weighted attack selection from the data-driven rules
+                self.boss.attack_type = self.select_boss_attack();
+
+                // Transition to Attack
+                // Randomized attack warning sound
+                self.play_boss_cue(self.boss_def().warning_cue);
+                
+                self.boss.state = BOSS_STATE_ATTACK;
+                self.boss.phase = PHASE_WINDUP;
+                self.boss.phase_timer = 0;
+                self.boss.anim_frame = 0;
+                self.boss.vx = 0.0;
+            },
+            BOSS_STATE_ATTACK => {
+                let def = self.boss_pattern.attack(self.boss.attack_type);
+                let windup_time = Self::scaled_frames(def.windup, timer_mult);
+                let recovery_time = Self::scaled_frames(def.recovery, timer_mult);
+
+                // Recovery is the same for every attack
+                if self.boss.phase == PHASE_RECOVERY {
+                    self.boss.phase_timer += 1;
+                    if self.boss.phase_timer >= recovery_time {
+                        self.boss.state = BOSS_STATE_RECOVER;
+                        self.boss.state_timer = 0;
+                    }
+                } else {
+                    match self.boss.attack_type {
+                        ATTACK_DASH => {
+                            match self.boss.phase {
+                                PHASE_WINDUP => {
+                                    // Face player, telegraph with animation
+                                    self.boss.facing_right = self.player_x > self.boss.x;
+                                    self.boss.phase_timer += 1;
+
+                                    // Shorter telegraph when enraged
+                                    if self.boss.phase_timer >= windup_time {
+                                        self.boss.phase = PHASE_ACTIVE;
+                                        self.boss.phase_timer = 0;
+                                        // Fast dash with speed multiplier
+                                        let dir = if self.boss.facing_right { 1.0 } else { -1.0 };
+                                        self.boss.vx = dir * def.velocity_x * speed_mult;
+                                    }
+                                },
+                                PHASE_ACTIVE => {
+                                    self.boss.phase_timer += 1;
+
+                                    // Collision damage during dash
+                                    let dx = (self.player_x - self.boss.x).abs();
+                                    let dy = (self.player_y - self.boss.y).abs();
+                                    if dx < def.hitbox_w && dy < def.hitbox_h && !self.player_protected() {
+                                        let knock_vx = if self.boss.vx > 0.0 { def.knockback_x } else { -def.knockback_x };
+                                        self.hurt_player_by_boss(def.damage_for(self.player_max_hp), knock_vx, def.knockback_y);
+                                    }
+
+                                    if self.boss.phase_timer >= def.active {
+                                        self.boss.phase = PHASE_RECOVERY;
+                                        self.boss.phase_timer = 0;
+                                        self.boss.vx = 0.0;
+                                    }
+                                },
+                                _ => {}
+                            }
+                        },
+                        ATTACK_SLAM => {
+                            match self.boss.phase {
+                                PHASE_WINDUP => {
+                                    if self.boss.phase_timer == 0 {
+                                        // Higher jump when enraged
+                                        let jump_mult = if is_enraged { 1.25 } else { 1.0 };
+                                        self.boss.vy = def.velocity_y * jump_mult;
+                                        self.boss.on_ground = false;
+
+                                        // Track towards player position
+                                        let target_x = self.player_x;
+                                        let diff = target_x - self.boss.x;
+                                        let jump_vx = (diff / 40.0).clamp(-def.velocity_x, def.velocity_x) * speed_mult;
+                                        self.boss.vx = jump_vx;
+                                        self.boss.facing_right = diff > 0.0;
+                                    }
+                                    self.boss.phase_timer += 1;
+
+                                    // Wait until falling and near ground
+                                    let ground_y = self.boss_def().boss_start.1;
+                                    if self.boss.vy > 0.0 && self.boss.y >= ground_y - 8.0 {
+                                        self.boss.phase = PHASE_ACTIVE;
+                                        self.boss.phase_timer = 0;
+                                        self.boss.vx = 0.0;
+                                        self.boss.on_ground = true;
+                                        self.boss.vy = 0.0;
+                                        self.boss.y = ground_y;
+                                    }
+                                },
+                                PHASE_ACTIVE => {
+                                    self.boss.phase_timer += 1;
+
+                                    // Impact damage on first frame
+                                    if self.boss.phase_timer == 1 {
+                                        // Wider shockwave radius when enraged
+                                        let impact_radius = if is_enraged { def.hitbox_w * 1.25 } else { def.hitbox_w };
+                                        let dx = (self.player_x - self.boss.x).abs();
+                                        let dy = (self.player_y - self.boss.y).abs();
+                                        if dx < impact_radius && dy < def.hitbox_h && !self.player_protected() {
+                                            // Knockup away from the impact
+                                            let knock_vx = if self.player_x > self.boss.x { def.knockback_x } else { -def.knockback_x };
+                                            self.hurt_player_by_boss(def.damage_for(self.player_max_hp), knock_vx, def.knockback_y);
+                                        }
+                                    }
+
+                                    if self.boss.phase_timer >= def.active {
+                                        self.boss.phase = PHASE_RECOVERY;
+                                        self.boss.phase_timer = 0;
+                                    }
+                                },
+                                _ => {}
+                            }
+                        },
+                        ATTACK_PROJECTILE => {
+                            match self.boss.phase {
+                                PHASE_WINDUP => {
+                                    self.boss.facing_right = self.player_x > self.boss.x;
+                                    self.boss.phase_timer += 1;
+
+                                    if self.boss.phase_timer >= windup_time {
+                                        self.boss.phase = PHASE_ACTIVE;
+                                        self.boss.phase_timer = 0;
+                                    }
+                                },
+                                PHASE_ACTIVE => {
+                                    self.boss.phase_timer += 1;
+
+                                    // Spawn projectiles at the frames listed in the pattern
+                                    if def.spawn_frames.contains(&self.boss.phase_timer) {
+                                        let base_vx = if self.boss.facing_right { def.velocity_x * speed_mult } else { -def.velocity_x * speed_mult };
+                                        let start_x = if self.boss.facing_right {
+                                            self.boss.x + 25.0
+                                        } else {
+                                            self.boss.x - 25.0
+                                        };
+
+                                        // Spawn projectile
+                                        for proj in self.projectiles.iter_mut() {
+                                            if !proj.active {
+                                                // Vary Y position slightly for spread
+                                                let y_offset = (self.boss.phase_timer as f32 - 10.0) * 2.0;
+                                                *proj = Projectile {
+                                                    active: true,
+                                                    x: start_x,
+                                                    y: self.boss.y + y_offset,
+                                                    vx: base_vx,
+                                                    from_boss: true,
+                                                    max_distance: 200.0,
+                                                    ..Projectile::EMPTY
+                                                };
+                                                break;
+                                            }
+                                        }
+                                    }
+
+                                    if self.boss.phase_timer >= def.active {
+                                        self.boss.phase = PHASE_RECOVERY;
+                                        self.boss.phase_timer = 0;
+                                    }
+                                },
+                                _ => {}
+                            }
+                        },
+                        ATTACK_SACK_SUMMON => {
+                            match self.boss.phase {
+                                PHASE_WINDUP => {
+                                    // Rummage in the sack
+                                    self.boss.facing_right = self.player_x > self.boss.x;
+                                    self.boss.phase_timer += 1;
+                                    if self.boss.phase_timer >= windup_time {
+                                        self.boss.phase = PHASE_ACTIVE;
+                                        self.boss.phase_timer = 0;
+                                    }
+                                },
+                                PHASE_ACTIVE => {
+                                    self.boss.phase_timer += 1;
+                                    if self.boss.phase_timer == 1 {
+                                        let count = def.count as usize + if is_desperate { 1 } else { 0 };
+                                        self.summon_boss_minions(count);
+                                        self.sound.play(CUE_EXPLOSION);
+                                    }
+                                    if self.boss.phase_timer >= def.active {
+                                        self.boss.phase = PHASE_RECOVERY;
+                                        self.boss.phase_timer = 0;
+                                    }
+                                },
+                                _ => {}
+                            }
+                        },
+                        ATTACK_COAL_RAIN => {
+                            match self.boss.phase {
+                                PHASE_WINDUP => {
+                                    // Arms up towards the chimney
+                                    self.boss.facing_right = self.player_x > self.boss.x;
+                                    self.boss.phase_timer += 1;
+                                    if self.boss.phase_timer >= windup_time {
+                                        self.boss.phase = PHASE_ACTIVE;
+                                        self.boss.phase_timer = 0;
+                                    }
+                                },
+                                PHASE_ACTIVE => {
+                                    self.boss.phase_timer += 1;
+
+                                    // First lump targets Santa, the rest land anywhere on the floor
+                                    let drop_interval = ((def.spawn_interval as f32 * timer_mult) as u32).max(1);
+                                    if self.boss.phase_timer == 1 {
+                                        let x = self.player_x;
+                                        self.spawn_falling_hazard(HAZARD_COAL, x);
+                                    } else if self.boss.phase_timer % drop_interval == 0 {
+                                        let span = (self.boss_floor_right - self.boss_floor_left - 20.0).max(1.0) as u32;
+                                        let x = self.boss_floor_left + 10.0 + (random::u32() % span) as f32;
+                                        self.spawn_falling_hazard(HAZARD_COAL, x);
+                                    }
+
+                                    if self.boss.phase_timer >= def.active {
+                                        self.boss.phase = PHASE_RECOVERY;
+                                        self.boss.phase_timer = 0;
+                                    }
+                                },
+                                _ => {}
+                            }
+                        },
+                        ATTACK_SHOCKWAVE => {
+                            match self.boss.phase {
+                                PHASE_WINDUP => {
+                                    // Crouch before the ground pound
+                                    self.boss.facing_right = self.player_x > self.boss.x;
+                                    self.boss.phase_timer += 1;
+                                    if self.boss.phase_timer >= windup_time {
+                                        self.boss.phase = PHASE_ACTIVE;
+                                        self.boss.phase_timer = 0;
+                                    }
+                                },
+                                PHASE_ACTIVE => {
+                                    self.boss.phase_timer += 1;
+
+                                    // Pound: one wave each way along the floor
+                                    if self.boss.phase_timer == 1 {
+                                        let speed = def.velocity_x * speed_mult;
+                                        self.boss_shockwaves[0] = Shockwave::new(self.boss.x - 20.0, -speed);
+                                        self.boss_shockwaves[1] = Shockwave::new(self.boss.x + 20.0, speed);
+                                        self.sound.play(CUE_EXPLOSION);
+                                    }
+
+                                    // Active time is a cap, the attack ends as soon as both waves are gone
+                                    let waves_done = self.boss_shockwaves.iter().all(|w| !w.active);
+                                    if waves_done || self.boss.phase_timer >= def.active {
+                                        self.boss.phase = PHASE_RECOVERY;
+                                        self.boss.phase_timer = 0;
+                                    }
+                                },
+                                _ => {}
+                            }
+                        },
+                        _ => { self.boss.state = BOSS_STATE_RECOVER; }
+                    }
+                }
+            },
+            BOSS_STATE_RECOVER => {
+                self.boss.state_timer += 1;
+                self.boss.vx = 0.0;
+                
+                // Shorter recovery when enraged, chance to combo
+                let recovery_time = ((35.0 * timer_mult) as u32).max(15);
+                
+                // Combo chance: in enraged mode, sometimes skip idle and attack again
+                let combo_chance = if is_desperate { 0.4 } else if is_enraged { 0.25 } else { 0.0 };
+                let rand_factor = (self.frame % 100) as f32 / 100.0;
+                
+                if self.boss.state_timer >= recovery_time {
+                    if rand_factor < combo_chance {
+                        // Combo attack! Go directly to SELECT
+                        self.boss.state = BOSS_STATE_SELECT;
+                    } else {
+                        self.boss.state = BOSS_STATE_IDLE;
+                    }
+                    self.boss.state_timer = 0;
+                }
+            },
+            BOSS_STATE_ROAR => {
+                // Phase transition cinematic: stand still facing Santa, immune to damage
+                self.boss.vx = 0.0;
+                self.boss.facing_right = self.player_x > self.boss.x;
+                self.boss.state_timer += 1;
+                if self.boss.state_timer >= BOSS_ROAR_TIME {
+                    self.boss.state = BOSS_STATE_IDLE;
+                    self.boss.state_timer = 0;
+                }
+            },
+            _ => { self.boss.state = BOSS_STATE_IDLE; }
+        }
+
+        // Apply Physics & Gravity (Always active)
+        if self.boss.on_ground == false {
+             self.boss.vy += GRAVITY;
+             if self.boss.vy > TERMINAL_VEL { self.boss.vy = TERMINAL_VEL; }
+        }
+
+        self.boss.x += self.boss.vx;
+        self.boss.y += self.boss.vy;
+
+        // Ground constraint
+        let ground_y = self.boss_def().boss_start.1;
+        if self.boss.y >= ground_y {
+             self.boss.y = ground_y;
+             self.boss.vy = 0.0;
+             self.boss.on_ground = true;
+        }
+
+        // Arena Clamp: keep the boss over the remaining floor (Evil Santa's phase 3 crumbles it)
+        let arena_left = self.boss_floor_left;
+        let arena_right = self.boss_floor_right;
+        if self.boss.x < arena_left { self.boss.x = arena_left; }
+        if self.boss.x > arena_right { self.boss.x = arena_right; }
+
+        // Decrement flash timer
+        if self.boss.flash_timer > 0 {
+            self.boss.flash_timer -= 1;
+        }
+
+        self.update_boss_arena();
+
+        // Advance animations
+        self.boss.anim_timer = self.boss.anim_timer.wrapping_add(1);
+        let step = match self.boss.state {
+            BOSS_STATE_IDLE => 12,
+            BOSS_STATE_WALK => 8,
+            BOSS_STATE_JUMP => 10,
+            BOSS_STATE_ATTACK => 10,
+            BOSS_STATE_FALL => 10,
+            BOSS_STATE_ROAR => 6,
+            _ => 12,
+        };
+        if self.boss.anim_timer >= step {
+            self.boss.anim_timer = 0;
+            self.boss.anim_frame = self.boss.anim_frame.wrapping_add(1);
+            // End attack after full cycle
+            let attack_frames = self.boss_def().sprites.attack.len() as u8;
+            if self.boss.state == BOSS_STATE_ATTACK && self.boss.anim_frame >= attack_frames {
+                self.boss.anim_frame = 0;
+                // Animation cycle complete, FSM handles state transitions
+            }
+        }
+    }
+
+    fn boss_phase_for_hp(&self) -> u8 {
+        let hp_ratio = self.boss.hp as f32 / self.boss.max_hp as f32;
+        if hp_ratio <= BOSS_PHASE_3_HP_RATIO {
+            BOSS_FIGHT_PHASE_3
+        } else if hp_ratio <= BOSS_PHASE_2_HP_RATIO {
+            BOSS_FIGHT_PHASE_2
+        } else {
+            BOSS_FIGHT_PHASE_1
+        }
+    }
+
+    // Bad pattern data stops the game at startup rather than in the middle of a fight
+    fn load_boss_patterns() -> Vec<BossPattern> {
+        BOSS_DEFS.iter()
+            .map(|def| BossPattern::from_json(def.pattern_json)
+                .unwrap_or_else(|e| panic!("{} pattern data is invalid: {}", def.name, e)))
+            .collect()
+    }
+
+    fn select_boss_attack(&self) -> u8 {
+        // near_wall uses the live arena floor, phase 3 shrinks it
+        let situation = BossSituation {
+            dx: (self.player_x - self.boss.x).abs(),
+            dy: self.player_y - self.boss.y, // Negative if player is above
+            hp_ratio: self.boss.hp as f32 / self.boss.max_hp as f32,
+            near_wall: (self.boss.x - self.boss_floor_left) < 40.0 || (self.boss_floor_right - self.boss.x) < 40.0,
+            player_idle: self.player_idle_timer >= PLAYER_IDLE_FRAMES,
+            minions_full: self.active_minion_count() >= BOSS_MAX_MINIONS,
+        };
+        self.boss_pattern.pick_attack(self.boss_fight_phase, &situation, random::u32())
+    }
+
+    // Windup/recovery frames shrink in later fight phases, never below half the data value
+    fn scaled_frames(frames: u32, timer_mult: f32) -> u32 {
+        ((frames as f32 * timer_mult) as u32).max(frames / 2)
+    }
+
+    fn play_boss_cue(&mut self, cue: Option<u8>) {
+        if let Some(cue) = cue {
+            self.sound.play(cue);
+        }
+    }
+
+    fn boss_def(&self) -> &'static BossDef {
+        &BOSS_DEFS[self.boss_index as usize]
+    }
+
+    fn find_boss_trigger(&self) -> Option<u8> {
+        BOSS_DEFS.iter().position(|def| {
+            let (tx, ty, tw, th) = def.trigger;
+            def.level == self.level
+                && self.player_x >= tx && self.player_x <= tx + tw
+                && self.player_y >= ty && self.player_y <= ty + th
+        }).map(|i| i as u8)
+    }
+
+    fn start_boss_fight(&mut self, index: u8) {
+        self.boss_index = index;
+        let def = self.boss_def();
+        self.boss_active = true;
+        self.use_boss_santa = def.big_santa; // switch to bossfight/santa sprite set
+        self.boss_pattern = self.boss_patterns[index as usize].clone();
+
+        // Arena floor extents (Evil Santa's phase 3 shrinks these)
+        self.boss_floor_left = def.arena.0;
+        self.boss_floor_right = def.arena.1;
+        self.boss_floor_shrink_timer = 0;
+        self.boss_icicle_timer = 0;
+        self.boss_minions = 0;
+        for hazard in self.falling_hazards.iter_mut() {
+            hazard.active = false;
+        }
+        for wave in self.boss_shockwaves.iter_mut() {
+            wave.active = false;
+        }
+
+        if def.own_screen {
+            // Setup boss fight arena (360x256 frame with single platform)
+            // platforms[0] is always the arena floor (phase 3 shrinks it in place)
+            self.platforms.clear();
+            self.platforms.push(def.arena);
+
+            // Disable all enemies during boss fight
+            for enemy in self.enemies.iter_mut() {
+                enemy.8 = false; // Set active to false
+            }
+
+            // Add walls at corners to prevent falling off
+            self.walls[0] = (def.arena.0 - 10.0, 0.0, 10.0, 256.0);  // Left wall
+            self.walls[1] = (def.arena.1, 0.0, 10.0, 256.0);         // Right wall
+            // Clear other walls
+            for i in 2..self.walls.len() {
+                self.walls[i] = (0.0, 0.0, 0.0, 0.0);
+            }
+
+            // Reset player position for boss fight arena
+            self.player_x = def.player_start.0;
+            self.player_y = def.player_start.1;
+            self.player_vx = 0.0;
+            self.player_vy = 0.0;
+            self.player_on_ground = true;
+        }
+
+        // Initialize boss entity
+        self.boss = BossEntity::new();
+        self.boss.x = def.boss_start.0;
+        self.boss.y = def.boss_start.1;
+        self.boss.max_hp = def.max_hp;
+        self.boss.hp = def.max_hp;
+        self.boss_fight_phase = BOSS_FIGHT_PHASE_1;
+        self.boss_screen_flash = 0;
+        self.boss_death_timer = 0;
+        self.player_idle_x = self.player_x;
+        self.player_idle_timer = 0;
+
+        self.boss_on_intro();
+        self.start_cutscene(def.intro_cutscene);
+    }
+
+    fn start_boss_phase_transition(&mut self, phase: u8) {
+        // Apply every phase we crossed (a big hit can skip straight from 1 to 3)
+        for next in (self.boss_fight_phase + 1)..=phase {
+            self.boss_on_phase(next);
+        }
+        self.boss_fight_phase = phase;
+
+        self.boss.state = BOSS_STATE_ROAR;
+        self.boss.state_timer = 0;
+        self.boss.phase = PHASE_WINDUP;
+        self.boss.phase_timer = 0;
+        self.boss.vx = 0.0;
+        self.boss.anim_frame = 0;
+        self.boss.anim_timer = 0;
+        self.boss_screen_flash = BOSS_SCREEN_FLASH;
+
+        // Give Santa a breather: clear anything the boss already threw
+        for proj in self.projectiles.iter_mut() {
+            if proj.from_boss { proj.active = false; }
+        }
+
+        self.play_boss_cue(self.boss_def().roar_cue);
+        log!("{} enters phase {}!", self.boss_def().name, phase);
+    }
+
+    // ------------------------------------------------------------------------
+    // Per-boss hooks
+    // ------------------------------------------------------------------------
+
+    fn boss_on_intro(&mut self) {
+        match self.boss_def().kind {
+            BOSS_EVIL_SANTA => {
+                // Both walk in from the walls during the intro cutscene
+                self.player_x = self.boss_def().arena.0 + 12.0;
+                self.player_facing_right = true;
+                self.boss.x = self.boss_def().arena.1 - 20.0;
+            },
+            BOSS_GIANT_KICKMOUSE => {
+                // Drops in from the ceiling
+                self.boss.y = 40.0;
+                self.boss.vy = 0.0;
+                self.boss.on_ground = false;
+            },
+            _ => {}
+        }
+        log!("Boss fight started: {}", self.boss_def().name);
+    }
+
+    fn boss_on_phase(&mut self, phase: u8) {
+        // Mid-bosses only roar and speed up
+        match (self.boss_def().kind, phase) {
+            (BOSS_EVIL_SANTA, BOSS_FIGHT_PHASE_2) => {
+                // Two icy ledges to dodge slams and falling icicles
+                self.platforms.push((40.0, 110.0, 185.0));
+                self.platforms.push((250.0, 320.0, 185.0));
+                self.boss_icicle_timer = 0;
+            },
+            (BOSS_EVIL_SANTA, BOSS_FIGHT_PHASE_3) => {
+                // Floor starts crumbling in from both edges
+                self.boss_floor_shrink_timer = 0;
+            },
+            _ => {}
+        }
+    }
+
+    fn boss_on_defeat(&mut self) {
+        if let Some(cue) = self.boss_def().warning_cue {
+            self.sound.stop(cue);
+        }
+        self.score += self.boss_def().score;
+        self.level_stats.kill_score += self.boss_def().score;
+        self.earn_tokens(TOKENS_PER_BOSS);
+        match self.boss_def().kind {
+            BOSS_GIANT_KICKMOUSE => {
+                // Mid-bosses pay out supplies for the rest of the level
+                let (x, y) = (self.boss.x, self.boss.y);
+                self.spawn_drop(DROP_GIFT_BOMB, x - 12.0, y);
+                self.spawn_drop(DROP_CANDY, x + 12.0, y);
+                // The first one also gives up its wall boots
+                if !self.has_wall_jump {
+                    self.spawn_drop(DROP_WALL_BOOTS, x, y);
+                }
+            },
+            // Evil Santa's payoff is the ending cutscene
+            BOSS_EVIL_SANTA => {},
+            _ => {}
+        }
+    }
+
+    fn boss_on_exit(&mut self) {
+        match self.boss_def().kind {
+            // Evil Santa's arena replaced the whole level
+            BOSS_EVIL_SANTA => self.restore_level3_after_boss(),
+            // In-place arena, leaving it just unlocks the camera
+            BOSS_GIANT_KICKMOUSE => {},
+            _ => {}
+        }
+    }
+
+    fn update_boss_arena(&mut self) {
+        if self.boss_screen_flash > 0 {
+            self.boss_screen_flash -= 1;
+        }
+
+        // Arena hazards below belong to Evil Santa's fight phases
+        let evil_santa_arena = self.boss_def().kind == BOSS_EVIL_SANTA;
+        let floor_y = self.boss_def().arena.2;
+
+        // Phase 3: floor edges crumble inward until only the middle is left
+        if evil_santa_arena && self.boss_fight_phase >= BOSS_FIGHT_PHASE_3 {
+            self.boss_floor_shrink_timer += 1;
+            if self.boss_floor_shrink_timer >= BOSS_FLOOR_SHRINK_INTERVAL {
+                self.boss_floor_shrink_timer = 0;
+                let left = (self.boss_floor_left + BOSS_FLOOR_SHRINK_STEP).min(BOSS_FLOOR_MIN_LEFT);
+                let right = (self.boss_floor_right - BOSS_FLOOR_SHRINK_STEP).max(BOSS_FLOOR_MIN_RIGHT);
+                if left != self.boss_floor_left || right != self.boss_floor_right {
+                    self.boss_floor_left = left;
+                    self.boss_floor_right = right;
+                    // platforms[0] is always the arena floor
+                    if let Some(floor) = self.platforms.first_mut() {
+                        floor.0 = left;
+                        floor.1 = right;
+                    }
+                    self.sound.play(CUE_EXPLOSION);
+                }
+            }
+        }
+
+        // Phase 2+: icicles drop near Santa over the floor that is left, shaking on the ceiling first
+        if evil_santa_arena && self.boss_fight_phase >= BOSS_FIGHT_PHASE_2 && self.boss.state != BOSS_STATE_ROAR {
+            self.boss_icicle_timer += 1;
+            let interval = if self.boss_fight_phase >= BOSS_FIGHT_PHASE_3 { 100 } else { 150 };
+            if self.boss_icicle_timer >= interval {
+                self.boss_icicle_timer = 0;
+                let offset = (random::u32() % 81) as f32 - 40.0;
+                let x = (self.player_x + offset).clamp(self.boss_floor_left + 10.0, self.boss_floor_right - 10.0);
+                self.spawn_falling_hazard(HAZARD_ICICLE, x);
+            }
+        }
+
+        // Coal uses the coal rain tuning, icicles are a fixed arena hazard
+        let coal = self.boss_pattern.attack(ATTACK_COAL_RAIN);
+        for i in 0..self.falling_hazards.len() {
+            let mut hazard = self.falling_hazards[i];
+            if !hazard.active { continue; }
+
+            if hazard.telegraph > 0 {
+                hazard.telegraph -= 1;
+            } else {
+                hazard.vy = (hazard.vy + GRAVITY).min(TERMINAL_VEL);
+                hazard.y += hazard.vy;
 
-                // Reset keys for Level 2
-                self.keys_collected = 0;
-                for key in self.keys.iter_mut() {
-                    key.2 = false; // Deactivate all keys
+                let (hit_w, hit_h, damage, knock_vy) = if hazard.kind == HAZARD_COAL {
+                    (coal.hitbox_w, coal.hitbox_h, coal.damage_for(self.player_max_hp), coal.knockback_y)
+                } else {
+                    (10.0, 20.0, 1, -2.0)
+                };
+                let dx = (hazard.x - self.player_x).abs();
+                let dy = (hazard.y - self.player_y).abs();
+                if dx < hit_w && dy < hit_h && !self.player_protected() && self.player_state != STATE_DEAD {
+                    self.hurt_player_by_boss(damage, 0.0, knock_vy);
+                    hazard.active = false;
+                } else if hazard.y >= floor_y - 4.0 {
+                    hazard.active = false;
                 }
+            }
+            self.falling_hazards[i] = hazard;
+        }
 
-                // ============================================================
-                // KIDS - Level 2
-                // ============================================================
-                // One door will spawn a kid instead of a key
-                self.kid_door_index = (self.frame % 4) as usize;
-                self.kids_collected = 0;
-                self.total_kids_in_level = 0;  // Will increment when kid spawns from door
-                for kid in self.kids.iter_mut() {
-                    *kid = (0.0, 0.0, false, false, 0, 0, -1);
+        // Shockwaves roll along the floor until they leave the arena
+        let shockwave = self.boss_pattern.attack(ATTACK_SHOCKWAVE);
+        for i in 0..self.boss_shockwaves.len() {
+            let mut wave = self.boss_shockwaves[i];
+            if !wave.active { continue; }
+
+            wave.x += wave.vx;
+            if wave.x < self.boss_floor_left || wave.x > self.boss_floor_right {
+                wave.active = false;
+            } else {
+                // Only hits Santa while his feet are within the wave's height
+                let feet_y = self.player_y + 19.0;
+                let dx = (wave.x - self.player_x).abs();
+                if dx < shockwave.hitbox_w && feet_y > floor_y - shockwave.hitbox_h
+                    && !self.player_protected() && self.player_state != STATE_DEAD {
+                    let knock_vx = if wave.vx > 0.0 { shockwave.knockback_x } else { -shockwave.knockback_x };
+                    self.hurt_player_by_boss(shockwave.damage_for(self.player_max_hp), knock_vx, shockwave.knockback_y);
+                }
+            }
+            self.boss_shockwaves[i] = wave;
+        }
+    }
+
+    fn spawn_falling_hazard(&mut self, kind: u8, x: f32) {
+        let (start_y, telegraph) = if kind == HAZARD_COAL {
+            (COAL_START_Y, COAL_TELEGRAPH_TIME)
+        } else {
+            (ICICLE_CEILING_Y, ICICLE_TELEGRAPH_TIME)
+        };
+        for hazard in self.falling_hazards.iter_mut() {
+            if !hazard.active {
+                *hazard = FallingHazard { active: true, x, y: start_y, vy: 0.0, telegraph, kind };
+                break;
+            }
+        }
+    }
+
+    fn hurt_player_by_boss(&mut self, damage: u8, knock_vx: f32, knock_vy: f32) {
+        self.player_hp = self.player_hp.saturating_sub(damage);
+        self.sound.play(CUE_SANTA_HURT);
+        self.player_invuln_timer = 60;
+        self.player_vx = knock_vx;
+        self.player_vy = knock_vy;
+    }
+
+    fn is_boss_minion(&self, index: usize) -> bool {
+        self.boss_minions & (1 << index) != 0
+    }
+
+    fn active_minion_count(&self) -> usize {
+        // In-place arenas keep the level's own enemies running, only summoned slots count
+        (0..self.enemies.len()).filter(|&i| self.is_boss_minion(i) && self.enemies[i].8).count()
+    }
+
+    fn summon_boss_minions(&mut self, count: usize) {
+        let floor_y = self.boss_def().arena.2;
+        let mut spawned = 0;
+        for i in 0..self.enemies.len() {
+            if spawned >= count || self.active_minion_count() >= BOSS_MAX_MINIONS { break; }
+            if self.enemies[i].8 { continue; }
+
+            let kind = BOSS_MINION_KINDS[(self.frame as usize + i) % BOSS_MINION_KINDS.len()];
+            let side = if spawned % 2 == 0 { -1.0 } else { 1.0 };
+            let x = (self.boss.x + side * 30.0).clamp(self.boss_floor_left + 8.0, self.boss_floor_right - 8.0);
+            // Patrol 60px around the spawn point, kept over the remaining floor
+            let patrol_start = (x - 30.0).clamp(self.boss_floor_left, (self.boss_floor_right - 60.0).max(self.boss_floor_left));
+            let dir = if side < 0.0 { 0 } else { 1 };
+            self.enemies[i] = (kind, x, floor_y - 20.0, 0.0, 0.0, dir, 0, patrol_start as u16, true, 0, false, 0);
+            self.boss_minions |= 1 << i;
+            spawned += 1;
+        }
+    }
+
+    fn exit_boss_fight(&mut self) {
+        // Exit boss fight mode
+        self.boss_active = false;
+        self.boss_defeated = true;  // Mark boss as defeated to prevent re-trigger
+        self.use_boss_santa = false;
+        self.boss_death_timer = 0;
+        self.boss_screen_flash = 0;
+
+        // Clear any boss projectiles and hazards
+        for proj in self.projectiles.iter_mut() {
+            proj.active = false;
+        }
+        for hazard in self.falling_hazards.iter_mut() {
+            hazard.active = false;
+        }
+        for wave in self.boss_shockwaves.iter_mut() {
+            wave.active = false;
+        }
+
+        self.boss_on_exit();
+    }
+
+    fn restore_level3_after_boss(&mut self) {
+        // Move player to post-boss position
+        self.player_x = 1970.0;
+        self.player_y = 220.0;
+        self.player_vx = 0.0;
+        self.player_vy = 0.0;
+        self.player_state = STATE_IDLE;
+        self.player_on_ground = true;
+
+        // Restore original level 3 layout, then close the exit behind the gates
+        self.load_level3_layout();
+
+        // Add 2 gates at the top of the ladder (near x=2136, y=50)
+        // Gate #1 - blocks passage at top of ladder
+        self.walls[0] = (2120.0, 50.0, 15.0, 30.0);
+        // Gate #2 - second barrier
+        self.walls[1] = (2145.0, 50.0, 15.0, 30.0);
+
+        // Gates moved, let them open again with the keys already held
+        self.gate_progress = [0; 22];
+
+        // Set completion trigger after the gates
+        // Player needs to climb ladder and pass through gates to complete
+        self.completion_trigger = (2150.0, 50.0, 20.0, 30.0);
+
+        log!("Boss defeated! Player moved to x={}, y={}", self.player_x, self.player_y);
+    }
+
+    // ========================================================================
+    // CUTSCENE PLAYBACK
+    // ========================================================================
+
+    fn start_cutscene(&mut self, id: u8) {
+        let script = cutscene_script(id);
+        if script.is_empty() { return; }
+        self.cutscene_id = id;
+        self.cutscene_step = 0;
+        self.cutscene_timer = 0;
+        self.cutscene_age = 0;
+        self.cutscene_camera_from = self.camera_x;
+        self.cutscene_camera_hold = false;
+        // Scripts that open with a fade-in start from a black screen
+        if let CutsceneStep::FadeIn(_) = script[0] {
+            self.cutscene_fade = 255;
+        }
+        self.player_vx = 0.0;
+        self.player_jump_buffer = 0;
+        log!("Cutscene {} started", id);
+    }
+
+    fn end_cutscene(&mut self) {
+        self.cutscene_id = CUTSCENE_NONE;
+        self.cutscene_step = 0;
+        self.cutscene_timer = 0;
+        self.cutscene_camera_hold = false;
+        self.cutscene_fade = 0;
+        self.player_vx = 0.0;
+        self.boss.vx = 0.0;
+    }
+
+    fn update_cutscene(&mut self) {
+        let kb = keyboard::get();
+
+        // ENTER skips whatever is left of the script
+        self.cutscene_age = self.cutscene_age.saturating_add(1);
+        if self.cutscene_age > CUTSCENE_SKIP_DELAY && kb.enter().just_pressed() {
+            self.skip_cutscene();
+            return;
+        }
+
+        let script = cutscene_script(self.cutscene_id);
+        let Some(&step) = script.get(self.cutscene_step as usize) else {
+            self.end_cutscene();
+            return;
+        };
+
+        self.cutscene_timer = self.cutscene_timer.saturating_add(1);
+        let t = self.cutscene_timer;
+
+        // Walk steps set these again every frame
+        self.player_vx = 0.0;
+        self.boss.vx = 0.0;
+
+        let done = match step {
+            CutsceneStep::Wait(frames) => t >= frames,
+            CutsceneStep::FadeOut(frames) => {
+                self.cutscene_fade = (255 * t.min(frames) as u32 / frames.max(1) as u32) as u8;
+                t >= frames
+            },
+            CutsceneStep::FadeIn(frames) => {
+                self.cutscene_fade = 255 - (255 * t.min(frames) as u32 / frames.max(1) as u32) as u8;
+                t >= frames
+            },
+            CutsceneStep::CameraTo(x, frames) => self.pan_cutscene_camera(x, t, frames),
+            CutsceneStep::CameraArena(frames) => {
+                let x = self.boss_def().camera_x;
+                self.pan_cutscene_camera(x, t, frames)
+            },
+            CutsceneStep::CameraFollow(frames) => {
+                let x = self.camera_target();
+                let done = self.pan_cutscene_camera(x, t, frames);
+                if done {
+                    self.cutscene_camera_hold = false;
+                }
+                done
+            },
+            CutsceneStep::Walk(actor, x) => self.cutscene_walk(actor, x),
+            CutsceneStep::Say(_, _, text) => {
+                // X/Z finishes the typing first, then moves on
+                let typed = (t / CUTSCENE_TEXT_SPEED) as usize >= text.len();
+                if kb.key_x().just_pressed() || kb.key_z().just_pressed() {
+                    if !typed {
+                        self.cutscene_timer = text.len() as u16 * CUTSCENE_TEXT_SPEED;
+                    }
+                    typed
+                } else {
+                    false
+                }
+            },
+            CutsceneStep::Action(action) => {
+                self.run_cutscene_action(action);
+                true
+            },
+        };
+
+        // Santa and the boss keep their physics and animations while the script moves them
+        self.update_player();
+        if self.boss_active {
+            self.update_cutscene_boss();
+        }
+        if !self.cutscene_camera_hold {
+            self.update_camera();
+        }
+
+        if done {
+            self.cutscene_step += 1;
+            self.cutscene_timer = 0;
+            self.cutscene_camera_from = self.camera_x;
+            if self.cutscene_step as usize >= script.len() {
+                self.end_cutscene();
+            }
+        }
+    }
+
+    fn skip_cutscene(&mut self) {
+        // Apply the rest of the script instantly so skipping ends up in the same state
+        let script = cutscene_script(self.cutscene_id);
+        for step in script.iter().skip(self.cutscene_step as usize) {
+            match *step {
+                CutsceneStep::Walk(ACTOR_SANTA, x) => self.player_x = x,
+                CutsceneStep::Walk(_, x) => {
+                    self.boss.x = x;
+                    self.boss.state = BOSS_STATE_IDLE;
+                },
+                CutsceneStep::Action(action) => self.run_cutscene_action(action),
+                _ => {}
+            }
+        }
+        self.end_cutscene();
+        self.update_camera();
+    }
+
+    fn pan_cutscene_camera(&mut self, target_x: f32, t: u16, frames: u16) -> bool {
+        self.cutscene_camera_hold = true;
+        let progress = (t as f32 / frames.max(1) as f32).min(1.0);
+        // Smoothstep so pans ease in and out
+        let eased = progress * progress * (3.0 - 2.0 * progress);
+        self.camera_x = self.cutscene_camera_from + (target_x - self.cutscene_camera_from) * eased;
+        t >= frames
+    }
+
+    fn cutscene_walk(&mut self, actor: u8, target_x: f32) -> bool {
+        if actor == ACTOR_SANTA {
+            let dx = target_x - self.player_x;
+            if dx.abs() <= WALK_SPEED {
+                self.player_x = target_x;
+                return true;
+            }
+            self.player_facing_right = dx > 0.0;
+            self.player_vx = WALK_SPEED * dx.signum();
+        } else {
+            let speed = self.boss_def().walk_speed;
+            let dx = target_x - self.boss.x;
+            if dx.abs() <= speed {
+                self.boss.x = target_x;
+                self.boss.state = BOSS_STATE_IDLE;
+                return true;
+            }
+            self.boss.facing_right = dx > 0.0;
+            self.boss.vx = speed * dx.signum();
+            self.boss.state = BOSS_STATE_WALK;
+        }
+        false
+    }
+
+    fn update_cutscene_boss(&mut self) {
+        // Gravity and walking only, the FSM waits until the cutscene ends
+        if !self.boss.on_ground {
+            self.boss.vy = (self.boss.vy + GRAVITY).min(TERMINAL_VEL);
+        }
+        self.boss.x += self.boss.vx;
+        self.boss.y += self.boss.vy;
+        let ground_y = self.boss_def().boss_start.1;
+        if self.boss.y >= ground_y {
+            self.boss.y = ground_y;
+            self.boss.vy = 0.0;
+            self.boss.on_ground = true;
+        }
+
+        self.boss.anim_timer = self.boss.anim_timer.wrapping_add(1);
+        let step = if self.boss.state == BOSS_STATE_WALK { 8 } else { 12 };
+        if self.boss.anim_timer >= step {
+            self.boss.anim_timer = 0;
+            self.boss.anim_frame = self.boss.anim_frame.wrapping_add(1);
+        }
+    }
+
+    fn run_cutscene_action(&mut self, action: u8) {
+        match action {
+            CUTSCENE_ACTION_BOSS_ROAR => {
+                if !self.boss_active { return; }
+                self.boss.state = BOSS_STATE_ROAR;
+                self.boss.state_timer = 0;
+                self.boss.anim_frame = 0;
+                self.boss.anim_timer = 0;
+                self.boss.facing_right = self.player_x > self.boss.x;
+                self.boss_screen_flash = BOSS_SCREEN_FLASH;
+                self.play_boss_cue(self.boss_def().roar_cue);
+            },
+            CUTSCENE_ACTION_EXIT_BOSS if self.boss_active => self.exit_boss_fight(),
+            CUTSCENE_ACTION_VICTORY => {
+                self.show_victory = true;
+                self.game_won_timer = 0;
+                self.sound.play(CUE_VICTORY);
+            },
+            _ => {}
+        }
+    }
+
+    fn render(&self) {
+        
+        // START MENU - title menu drawn over the starting_page.png sprite
+        if self.in_menu {
+            sprite!("starting_page", x = 0, y = 0);
+            self.draw_menu();
+            return;
+        }
+
+        if self.show_level_select {
+            self.draw_level_select();
+            return;
+        }
+        
+        // CONTROLS PAGE - show controls.png sprite until user presses Enter to start game
+        if self.show_controls {
+            sprite!("controls", x = 0, y = 0);
+            return;
+        }
+        
+        // GAME OVER STATE - show gameoverpage.png sprite for 3 seconds
+        if self.show_game_over {
+            sprite!("gameoverpage", x = 0, y = 0);
+            return;
+        }
+        
+        // TIME UP STATE - show time_up.png sprite for 3 seconds
+        if self.show_time_up {
+            sprite!("time_up", x = 0, y = 0);
+            return;
+        }
+        
+        // VICTORY STATE - show gamewon.png sprite for 5 seconds
+        // Triggered when Santa collects kid from door index 0 in level 3
+        if self.show_victory {
+            sprite!("gamewon", x = 0, y = 0);
+            return;
+        }
+
+        // RESULTS - star rating for the level just cleared
+        if self.show_results {
+            self.draw_results();
+            return;
+        }
+
+        // WORKSHOP - intermission between levels
+        if self.show_workshop {
+            self.draw_workshop();
+            return;
+        }
+        
+        // === PLAYING state rendering below ===
+        
+        // Draw the background sprite at exact camera position
+        let bg_x = -(self.camera_x as i32);
+
+        // Select background sprite based on current level
+        match self.level {
+            1 => sprite!("level1finalsprite", x = bg_x, y = 0),  // 1080x240px
+            2 => sprite!("2ndlevel", x = bg_x, y = 0),  // 1440x240px
+            3 => sprite!("bossfight bg1", x = bg_x, y = 0),  // 2160x240px - before boss trigger
+            _ => sprite!("level1finalsprite", x = bg_x, y = 0),  // Default to level 1
+        }
+
+        // Own-screen boss arenas cover the level background (360x240px - fixed screen, no scroll)
+        if self.boss_active && self.boss_def().own_screen {
+            sprite!(self.boss_def().background, x = 0, y = 0);
+        }
+
+        // Draw game elements
+        // self.draw_platforms();
+        // self.draw_walls
+        //self.draw_ladders();  // Draw ladders first (background layer)
+        self.draw_walls();
+        self.draw_blocks();  // Destructible crates, ice and steel
+        self.draw_triggers();  // Switches, plates and moving platforms
+        self.draw_hazards();
+        self.draw_volumes();
+        self.draw_ropes();
+        self.draw_boss_arena();  // Crumbled floor, phase ledges, falling hazards and shockwaves
+        self.draw_doors();  // Draw doors first (background layer)
+        self.draw_keys();   // Keys near exploded gates
+        self.draw_kids();   // Kids to rescue (spawn from doors)
+        self.draw_hidden_kids();  // Bonus kids the reindeer bell revealed
+        self.draw_life();   // Life powerup (boss level door #3)
+        self.draw_powerup1();  // PowerUp1 (boss level door #4)
+        self.draw_gift_bombs();
+        self.draw_enemies();
+        self.draw_snowballs();  // Penguin snowball projectiles
+        // Boss entity
+        self.draw_boss();
+        
+        self.draw_projectiles();
+        self.draw_player();
+        self.draw_shield();
+
+        // Boss phase transition flash (fades out over BOSS_SCREEN_FLASH frames)
+        if self.boss_screen_flash > 0 {
+            let peak = if self.reduced_motion() { 48 } else { 200 };
+            let alpha = (self.boss_screen_flash as u32 * peak) / BOSS_SCREEN_FLASH as u32;
+            rect!(x = 0, y = 0, w = 360, h = 240, color = 0xffffff00 | alpha);
+        }
+
+        self.draw_hud();
+        if self.show_controls_panel { self.draw_controls_panel(); }
+        self.draw_cutscene();
+        
+        // Draw level complete screen
+        if self.level_complete {
+            // Semi-transparent black overlay
+            let alpha = ((120 - self.level_transition_timer) as f32 / 120.0 * 204.0) as u32;
+            let overlay_color = 0x00000000 | (alpha << 24) | 0x000000;
+            rect!(x = 0, y = 0, w = 360, h = 240, color = overlay_color);
+
+            // "LEVEL COMPLETE!" text, the results screen follows the fade
+            text!("LEVEL COMPLETE!", x = 100, y = 100, color = 0x00ff00ff);
+        }
+
+        // Pause menu (and the pages opened from it) over the frozen level
+        self.draw_menu();
+
+        // Game over is now handled at the top of render() with show_game_over flag
+        // The gameoverpage.png sprite is shown for 3 seconds then game restarts from level 1
+    }
+
+    fn draw_ladders(&self) {
+        // DEBUG: Draw ladder collision zones
+        for (i, (lx, ly_top, ly_bottom)) in self.ladders.iter().enumerate() {
+            if *lx > 0.0 {
+                let screen_x = (*lx - self.camera_x) as i32;
+                let top_y = *ly_top as i32;
+                let bottom_y = *ly_bottom as i32;
+                let height = bottom_y - top_y;
+
+                // Draw collision zone (semi-transparent purple) - 4px on each side
+                let collision_width = 8; // 4px on each side
+                rect!(x = screen_x - 4, y = top_y, w = collision_width, h = height as u32, color = 0xff00ff44);
+
+                // Draw ladder rails (brown color)
+                rect!(x = screen_x - 6, y = top_y, w = 2, h = height as u32, color = 0x8B4513ff);
+                rect!(x = screen_x + 4, y = top_y, w = 2, h = height as u32, color = 0x8B4513ff);
+
+                // Draw ladder rungs
+                let rung_spacing = 12;
+                let mut y = top_y + 6;
+                while y < bottom_y - 4 {
+                    rect!(x = screen_x - 4, y = y, w = 8, h = 2, color = 0xD2691Eff);
+                    y += rung_spacing;
                 }
 
-                // ============================================================
-                // LEVEL COMPLETION TRIGGER - Level 2
-                // ============================================================
-                // Trigger zone at x=1350, y=85 to enter boss fight
-                self.completion_trigger = (1350.0, 85.0, 40.0, 50.0);
+                // Draw ladder label
+                let label_x = screen_x + 10;
+                let label_y = top_y + 5;
+                text!("L{}", i + 1; x = label_x, y = label_y, color = 0xffffffff);
+            }
+        }
+    }
 
-                // Reset level completion state
-                self.level_complete = false;
-                self.level_transition_timer = 0;
+    fn draw_player(&self) {
+        let screen_x = (self.player_x - self.camera_x) as i32;
+        // Offset Santa by -12px in Y only during bossfight
+        let y_offset = if self.use_boss_santa { -1 } else { 0 };
+        let screen_y = (self.player_y as i32) + y_offset;
 
-                // Reset player movement state
-                self.player_on_ladder = false;
-                self.player_is_crouching = false;
+        // Get the correct sprite name based on state and animation frame
+        let sprite_name = match self.player_state {
+            STATE_IDLE => {
+                let frame = (self.player_anim_frame % 4) + 1;
+                if self.use_boss_santa {
+                    match frame {
+                        1 => "bossfight/santa/idle/1",
+                        2 => "bossfight/santa/idle/2",
+                        3 => "bossfight/santa/idle/3",
+                        _ => "bossfight/santa/idle/4",
+                    }
+                } else {
+                    match frame {
+                        1 => "Santa/idle/1_32",
+                        2 => "Santa/idle/2_32",
+                        3 => "Santa/idle/3_32",
+                        _ => "Santa/idle/4_32",
+                    }
+                }
             },
-            3 => {
-                // Level 3 - Boss Fight (bossfight bg1 - 2160x240px)
-                // Using exact coordinates from Sprites/layout.md line 167+
-
-                // Reset boss state for fresh start
-                self.boss_active = false;
-                self.boss_defeated = false;
-
-                // ============================================================
-                // PLAYER SPAWN - Start at left side
-                // ============================================================
-                self.player_x = 150.0;
-                self.player_y = 222.0 - 28.0;  // 194.0 (on platform ID 1)
-                self.player_vx = 0.0;
-                self.player_vy = 0.0;
-                self.player_state = STATE_IDLE;
-                self.player_on_ground = true;
+            STATE_WALK | STATE_RUN => {
+                let frame = (self.player_anim_frame % 10) + 1;
+                if self.use_boss_santa {
+                    match frame {
+                        1 => "bossfight/santa/walk/1",
+                        2 => "bossfight/santa/walk/2",
+                        3 => "bossfight/santa/walk/3",
+                        4 => "bossfight/santa/walk/4",
+                        5 => "bossfight/santa/walk/5",
+                        6 => "bossfight/santa/walk/6",
+                        7 => "bossfight/santa/walk/7",
+                        8 => "bossfight/santa/walk/8",
+                        9 => "bossfight/santa/walk/9",
+                        _ => "bossfight/santa/walk/10",
+                    }
+                } else {
+                    match frame {
+                        1 => "Santa/runnin/1",
+                        2 => "Santa/runnin/2",
+                        3 => "Santa/runnin/3",
+                        4 => "Santa/runnin/4",
+                        5 => "Santa/runnin/5",
+                        6 => "Santa/runnin/6",
+                        7 => "Santa/runnin/7",
+                        8 => "Santa/runnin/8",
+                        9 => "Santa/runnin/9",
+                        _ => "Santa/runnin/10",
+                    }
+                }
+            },
+            STATE_JUMP => {
+                if self.use_boss_santa {
+                    let frame = (self.jump_anim_frame % 5) + 1;
+                    match frame {
+                        1 => "bossfight/santa/jump/1",
+                        2 => "bossfight/santa/jump/2",
+                        3 => "bossfight/santa/jump/3",
+                        4 => "bossfight/santa/jump/4",
+                        _ => "bossfight/santa/jump/5",
+                    }
+                } else {
+                    let frame = (self.jump_anim_frame % 7) + 1;
+                    match frame {
+                        1 => "Santa/jumping/1",
+                        2 => "Santa/jumping/2",
+                        3 => "Santa/jumping/3",
+                        4 => "Santa/jumping/4",
+                        5 => "Santa/jumping/5",
+                        6 => "Santa/jumping/6",
+                        _ => "Santa/jumping/7",
+                    }
+                }
+            },
+            STATE_FALL | STATE_WALL_SLIDE => {
+                if self.use_boss_santa {
+                    "bossfight/santa/santafall"
+                } else {
+                    "Santa/santafall"
+                }
+            },
+            STATE_DASH => {
+                if self.use_boss_santa { "bossfight/santa/walk/3" } else { "Santa/runnin/3" }
+            },
+            STATE_SLIDE => {
+                if self.use_boss_santa { "bossfight/santa/down/2" } else { "Santa/down/2" }
+            },
+            STATE_WALL_JUMP => {
+                // Tucked kick-off pose
+                if self.use_boss_santa { "bossfight/santa/jump/2" } else { "Santa/jumping/3" }
+            },
+            STATE_ATTACK | STATE_MELEE => {
+                let frame = ((self.attack_frame / 4) % 5) + 1;
+                if self.use_boss_santa {
+                    match frame {
+                        1 => "bossfight/santa/attack/1",
+                        2 => "bossfight/santa/attack/2",
+                        3 => "bossfight/santa/attack/3",
+                        4 => "bossfight/santa/attack/4",
+                        _ => "bossfight/santa/attack/5",
+                    }
+                } else {
+                    match frame {
+                        1 => "Santa/attack/1",
+                        2 => "Santa/attack/2",
+                        3 => "Santa/attack/3",
+                        4 => "Santa/attack/4",
+                        _ => "Santa/attack/5",
+                    }
+                }
+            },
+            STATE_CLIMB | STATE_HANG => {
+                let frame = (self.player_anim_frame % 10) + 1;
+                // Boss variant has no climbing; fall back to normal
+                match frame {
+                    1 => "Santa/climbing/1",
+                    2 => "Santa/climbing/2",
+                    3 => "Santa/climbing/3",
+                    4 => "Santa/climbing/4",
+                    5 => "Santa/climbing/5",
+                    6 => "Santa/climbing/6",
+                    7 => "Santa/climbing/7",
+                    8 => "Santa/climbing/8",
+                    9 => "Santa/climbing/9",
+                    _ => "Santa/climbing/10",
+                }
+            },
+            STATE_CROUCH => {
+                let frame = (self.player_anim_frame % 4) + 1;
+                if self.use_boss_santa {
+                    match frame {
+                        1 => "bossfight/santa/down/1",
+                        2 => "bossfight/santa/down/2",
+                        3 => "bossfight/santa/down/3",
+                        _ => "bossfight/santa/down/4",
+                    }
+                } else {
+                    match frame {
+                        1 => "Santa/down/1",
+                        2 => "Santa/down/2",
+                        3 => "Santa/down/3",
+                        4 => "Santa/down/4",
+                        5 => "Santa/down/5",
+                        6 => "Santa/down/6",
+                        7 => "Santa/down/7",
+                        _ => "Santa/down/8",
+                    }
+                }
+            },
+            _ => {
+                if self.use_boss_santa { "bossfight/santa/idle/1" } else { "Santa/idle/1_32" }
+            },
+        };
 
-                // ============================================================
-                // PLATFORMS (52 floor tiles) - All platforms from layout.md
-                // ============================================================
-                // Format: (x_start, x_end, y) where x_end = x + w
+        if self.player_invuln_timer == 0 || (self.player_invuln_timer / 4) % 2 == 0 {
+             sprite!(sprite_name, x = screen_x - 16, y = screen_y - 16, flip_x = !self.player_facing_right);
 
-                self.platforms.clear();
-                self.platforms.push((1.0, 317.0, 222.0+5.0));        // ID 1
-                self.platforms.push((301.0, 333.0, 207.0+5.0));      // ID 2
-                self.platforms.push((333.0, 363.0, 193.0+5.0));      // ID 3
-                self.platforms.push((361.0, 394.0, 177.0+5.0));      // ID 4
-                self.platforms.push((391.0, 418.0, 163.0+5.0));      // ID 5
-                self.platforms.push((211.0, 269.0, 163.0+5.0));      // ID 6
-                self.platforms.push((329.0, 448.0, 119.0+5.0));      // ID 7
-                self.platforms.push((450.0, 479.0, 164.0+5.0));      // ID 8
-                self.platforms.push((540.0, 568.0, 164.0+5.0));      // ID 9
-                //self.platforms.push((629.0, 689.0, 104.0+5.0));      // ID 10
-                //self.platforms.push((510.0, 536.0, 103.0+5.0));      // ID 11
-                self.platforms.push((630.0, 656.0, 164.0+5.0));      // ID 12
-                self.platforms.push((599.0, 625.0, 192.0+5.0));      // ID 13
-                self.platforms.push((569.0, 596.0, 223.0+5.0));      // ID 14
-                self.platforms.push((630.0, 657.0, 223.0+5.0));      // ID 15
-                self.platforms.push((689.0, 718.0, 133.0+5.0));      // ID 16
-                self.platforms.push((756.0, 836.0, 133.0+5.0));      // ID 17
-                self.platforms.push((690.0, 717.0, 223.0+5.0));      // ID 18
-                self.platforms.push((749.0, 777.0, 223.0+5.0));      // ID 19
-                self.platforms.push((808.0, 871.0, 223.0+5.0));      // ID 20
-                self.platforms.push((868.0, 896.0, 133.0+5.0));      // ID 21
-                self.platforms.push((927.0, 955.0, 119.0+5.0));      // ID 22
-                self.platforms.push((957.0, 986.0, 162.0+5.0));      // ID 23
-                self.platforms.push((986.0, 1021.0, 207.0+5.0));     // ID 24
-                self.platforms.push((1017.0, 1045.0, 147.0+5.0));    // ID 25
-                self.platforms.push((1046.0, 1075.0, 162.0+5.0));    // ID 26
-                //self.platforms.push((1107.0, 1164.0, 102.0+5.0));    // ID 27
-                self.platforms.push((1107.0, 1133.0, 192.0+5.0));    // ID 28
-                self.platforms.push((1134.0, 1165.0, 205.0+5.0));    // ID 29
-                self.platforms.push((1167.0, 1254.0, 147.0+5.0));    // ID 30
-                self.platforms.push((1165.0, 1253.0, 221.0+5.0));    // ID 31
-                self.platforms.push((1285.0, 1313.0, 162.0+5.0));    // ID 32
-                self.platforms.push((1287.0, 1317.0, 222.0+5.0));    // ID 33
-                self.platforms.push((1320.0, 1373.0, 175.0+5.0));    // ID 34
-                self.platforms.push((1346.0, 1374.0, 222.0+5.0));    // ID 35
-                //self.platforms.push((1314.0, 1343.0, 102.0+5.0));    // ID 36
-                self.platforms.push((1374.0, 1404.0, 147.0+5.0));    // ID 37
-                self.platforms.push((1406.0, 1456.0, 117.0+5.0));    // ID 38
-                self.platforms.push((1406.0, 1490.0, 222.0+5.0));    // ID 39
-                self.platforms.push((1496.0, 1524.0, 162.0+5.0));    // ID 40
-                //self.platforms.push((1554.0, 1643.0, 102.0+5.0));    // ID 41
-                self.platforms.push((1554.0, 1580.0, 222.0+5.0));    // ID 42
-                self.platforms.push((1643.0, 1671.0, 178.0+5.0));    // ID 43
-                self.platforms.push((1673.0, 1701.0, 147.0+5.0));    // ID 44
-                self.platforms.push((1615.0, 1736.0, 222.0+5.0));    // ID 45
-                self.platforms.push((1734.0, 1764.0, 117.0+5.0));    // ID 46
-                self.platforms.push((1762.0, 1791.0, 161.0+5.0));    // ID 47
-                self.platforms.push((1823.0, 1853.0, 117.0+5.0));    // ID 48
-                self.platforms.push((1853.0, 1881.0, 161.0+5.0));    // ID 49
-                self.platforms.push((1881.0, 1913.0, 177.0+5.0));    // ID 50
-                self.platforms.push((1909.0, 2159.0, 220.0+5.0));    // ID 51
-                //self.platforms.push((1198.0,1218.0,104.0+5.0));        // ID 52 
-                self.platforms.push((897.0,952.0,224.0+5.0));        // ID 53 
-                self.platforms.push((1.0, 2161.0, 50.0+5.0));        // ID 54 - Top ceiling
+             // Firepower VFX when player has firepower powerup OR is boss Santa
+             if (self.weapon_tier >= WEAPON_FIREPOWER || self.use_boss_santa) && self.player_state == STATE_ATTACK {
+                // Map 20 attack frames to 8 VFX frames
+                let vfx_frame = ((self.attack_frame as u32 * 8) / 20).min(7) + 1;
+                let vfx_name = match vfx_frame {
+                    1 => "firepower/1",
+                    2 => "firepower/2",
+                    3 => "firepower/3",
+                    4 => "firepower/4",
+                    5 => "firepower/5",
+                    6 => "firepower/6",
+                    7 => "firepower/7",
+                    _ => "firepower/8",
+                };
                 
+                // Draw VFX in front of Santa
+                let vfx_x = if self.player_facing_right { screen_x + 8 } else { screen_x - 40 };
+                sprite!(vfx_name, x = vfx_x, y = screen_y - 16, flip_x = !self.player_facing_right);
+             }
+        }
 
-
-                
-                // Clear ladders for boss level (no ladders)
-                for i in 0..self.ladders.len() {
-                    self.ladders[i] = (0.0, 0.0, 0.0);
-                }
-                self.ladders[0] = (2136.0, 50.0, 189.0);
-                // Clear walls for boss level (no walls initially)
-                for i in 0..self.walls.len() {
-                    self.walls[i] = (0.0, 0.0, 0.0, 0.0);
+        // Dash streaks / slide spray behind Santa
+        if self.player_state == STATE_DASH || self.player_state == STATE_SLIDE {
+            let back = if self.player_facing_right { -1 } else { 1 };
+            for i in 1..4 {
+                let streak_x = screen_x + back * (10 + i * 6);
+                if self.player_state == STATE_DASH {
+                    rect!(x = streak_x - 3, y = screen_y - 6 + i * 4, w = 6, h = 1, color = 0xffffff88);
+                } else {
+                    rect!(x = streak_x, y = screen_y + 14 - i * 2, w = 2, h = 2, color = 0xffffffaa);
                 }
+            }
+        }
 
-                // Reset enemies for Boss Level (boss enemy will be added later)
-                for enemy in self.enemies.iter_mut() {
-                    enemy.8 = false;
-                }
+        // Snow scraped off the wall while sliding
+        if self.player_state == STATE_WALL_SLIDE && self.frame % 6 < 3 {
+            let wall_x = screen_x + self.player_wall_side as i32 * 8;
+            rect!(x = wall_x - 1, y = screen_y + 8 + (self.frame % 6) as i32, w = 2, h = 2, color = 0xffffffcc);
+        }
 
-                // Reset keys for Boss Level
-                self.keys_collected = 0;
-                for key in self.keys.iter_mut() {
-                    key.2 = false;
+        // Candy cane: raised during the wind-up, swung out in front while the hitbox is live
+        if self.player_state == STATE_MELEE && self.attack_frame < MELEE_HIT_END {
+            let dir = if self.player_facing_right { 1 } else { -1 };
+            if self.attack_frame < MELEE_HIT_START {
+                let cane_x = screen_x - dir * 8 - 1;
+                rect!(x = cane_x, y = screen_y - 28, w = 3, h = 18, color = 0xffffffff);
+                for i in 0..3 {
+                    rect!(x = cane_x, y = screen_y - 26 + i * 6, w = 3, h = 2, color = 0xe02020ff);
                 }
-
-                // ============================================================
-                // KIDS - Boss Level (Level 3)
-                // ============================================================
-                // One door will spawn a kid instead of a key
-                self.kid_door_index = (self.frame % 4) as usize;
-                self.kids_collected = 0;
-                self.total_kids_in_level = 0;  // Will increment when kid spawns from door
-                for kid in self.kids.iter_mut() {
-                    *kid = (0.0, 0.0, false, false, 0, 0, -1);
+                rect!(x = cane_x + dir * 2, y = screen_y - 30, w = 4, h = 3, color = 0xe02020ff);
+            } else {
+                let len = MELEE_REACH as i32 - 8;
+                let cane_x = if dir > 0 { screen_x + 8 } else { screen_x - 8 - len };
+                rect!(x = cane_x, y = screen_y - 2, w = len, h = 3, color = 0xffffffff);
+                for i in 0..(len / 6) {
+                    rect!(x = cane_x + 2 + i * 6, y = screen_y - 2, w = 2, h = 3, color = 0xe02020ff);
                 }
+                // Hook at the tip and a swoosh trail
+                let tip_x = if dir > 0 { cane_x + len - 3 } else { cane_x };
+                rect!(x = tip_x, y = screen_y - 8, w = 3, h = 7, color = 0xe02020ff);
+                rect!(x = cane_x, y = screen_y - 10, w = len, h = 1, color = 0xffffff66);
+                rect!(x = cane_x, y = screen_y + 6, w = len, h = 1, color = 0xffffff66);
+            }
+        }
 
-                // ============================================================
-                // LIFE POWERUP - Boss Level (Level 3)
-                // ============================================================
-                // Door #3 (index 2) spawns life powerup instead of key/kid
-                self.life_active = false;
-                self.life_collected = false;
-                self.life_door_index = 2;  // Door #3
+        // Charge shot glow: grows while Z is held, flashes white once the shot is ready
+        if self.player_charge > CHARGE_SHOT_GLOW_DELAY {
+            let ready = self.player_charge >= CHARGE_SHOT_TIME;
+            let size = if ready && self.reduced_motion() {
+                36
+            } else if ready {
+                34 + (self.frame % 4) as i32 * 2
+            } else {
+                12 + (self.player_charge as i32 * 22) / CHARGE_SHOT_TIME as i32
+            };
+            let tier_color = WEAPON_TIERS[self.weapon_tier as usize].color;
+            // Reduced motion keeps the ready glow steady white instead of blinking
+            let white = ready && (self.reduced_motion() || (self.frame / 4) % 2 == 1);
+            let color = if white { 0xffffff88 } else { (tier_color & 0xffffff00) | 0x66 };
+            circ!(x = screen_x - size / 2, y = screen_y - size / 2, d = size as u32, color = color);
+        }
+    }
 
-                // ============================================================
-                // POWERUP1 - Boss Level (Level 3)
-                // ============================================================
-                // Door #4 (index 3) spawns powerup1
-                self.powerup1_active = false;
-                self.powerup1_collected = false;
+    fn draw_boss(&self) {
+        if !self.boss_active { return; }
+        let screen_x = (self.boss.x - self.camera_x) as i32;
+        let screen_y = self.boss.y as i32;
 
-                // ============================================================
-                // DOORS - Boss Level strategic positions
-                // ============================================================
-                // Clear/override any previous door positions and set new ones
-                // Doors are 42x42; place so base rests on platform surfaces.
-                // Format: (x_top_left, y_top_left, intact)
+        // Skip drawing every other frame if flashing (not with reduced motion)
+        if self.boss.flash_timer > 0 && !self.reduced_motion() {
+            if self.boss.flash_timer % 2 != 0 {
+                return;
+            }
+        }
 
-                // On platform ID 7 (329-448, y=119) -> door base at y=119
-                // y_top_left = 119 - 42 = 77
-                self.doors[0] = (621.0, 14.0, true);
+        let def = self.boss_def();
+        let sprites = &def.sprites;
+        let frame = self.boss.anim_frame as usize;
+        let sprite_name = match self.boss.state {
+            BOSS_STATE_DEAD => sprites.defeated,
+            BOSS_STATE_WALK => sprites.walk[frame % sprites.walk.len()],
+            BOSS_STATE_JUMP => sprites.jump[frame % sprites.jump.len()],
+            BOSS_STATE_ATTACK => sprites.attack[frame % sprites.attack.len()],
+            BOSS_STATE_ROAR => {
+                // Reuse the attack wind-up frames for the phase roar
+                let windup = sprites.attack.len().min(3);
+                sprites.attack[frame % windup]
+            },
+            BOSS_STATE_FALL => sprites.fall,
+            BOSS_STATE_RECOVER => sprites.idle[0], // Panting/Recover logic could go here
+            // Idle (also used during selection)
+            _ => sprites.idle[frame % sprites.idle.len()],
+        };
+
+        sprite!(
+            sprite_name,
+            x = screen_x + sprites.offset.0,
+            y = screen_y + sprites.offset.1,
+            scale = sprites.scale,
+            flip_x = !self.boss.facing_right
+        );
+
+        if self.boss.state == BOSS_STATE_ROAR {
+            let shake = if self.reduced_motion() {
+                0
+            } else if (self.boss.state_timer / 3) % 2 == 1 {
+                1
+            } else {
+                -1
+            };
+            let text_x = screen_x - (def.roar.len() as i32 * 8) / 2 + shake;
+            text!(def.roar, x = text_x, y = screen_y + sprites.offset.1 - 14, color = 0xff3333ff, font = "large");
+        }
+    }
 
-                // On platform ID 20 (808-871, y=223)
-                self.doors[1] = (869.0, 14.0, true);
+    fn draw_boss_arena(&self) {
+        if !self.boss_active { return; }
+        let evil_santa_arena = self.boss_def().kind == BOSS_EVIL_SANTA;
+        let floor_y = self.boss_def().arena.2 as i32;
+
+        // Crumbled floor edges (phase 3)
+        let pit_y = floor_y - 2;
+        let pit_h = (240 - pit_y) as u32;
+        if evil_santa_arena && self.boss_floor_left > BOSS_ARENA_LEFT {
+            let w = (self.boss_floor_left - BOSS_ARENA_LEFT) as u32;
+            rect!(x = BOSS_ARENA_LEFT as i32, y = pit_y, w = w, h = pit_h, color = 0x0a0a14ff);
+        }
+        if evil_santa_arena && self.boss_floor_right < BOSS_ARENA_RIGHT {
+            let w = (BOSS_ARENA_RIGHT - self.boss_floor_right) as u32;
+            rect!(x = self.boss_floor_right as i32, y = pit_y, w = w, h = pit_h, color = 0x0a0a14ff);
+        }
 
-                // On platform ID 30 (1167-1254, y=147)
-                self.doors[2] = (1194.0, 181.0, true);
+        // Icy ledges (phase 2+)
+        if evil_santa_arena && self.boss_fight_phase >= BOSS_FIGHT_PHASE_2 {
+            for (x1, x2, y) in self.platforms.iter().skip(1) {
+                let w = (*x2 - *x1) as u32;
+                rect!(x = *x1 as i32, y = *y as i32, w = w, h = 6, color = 0x5a8fb8ff);
+                rect!(x = *x1 as i32, y = *y as i32, w = w, h = 2, color = 0xd8f2ffff);
+            }
+        }
 
-                // On platform ID 51 (1909-2159, y=220)
-                self.doors[3] = (775.0, 97.0, true);
+        // Falling hazards: a floor shadow grows during the telegraph, then the hazard drops
+        for FallingHazard { active, x, y, telegraph, kind, .. } in self.falling_hazards.iter() {
+            if !*active { continue; }
+            let telegraph_time = if *kind == HAZARD_COAL { COAL_TELEGRAPH_TIME } else { ICICLE_TELEGRAPH_TIME };
+            let ix = (*x - self.camera_x) as i32;
+            let iy = *y as i32;
+            if *kind == HAZARD_COAL {
+                // Coal lump with a glowing ember
+                rect!(x = ix - 4, y = iy - 8, w = 8, h = 8, color = 0x1a1a1aff);
+                rect!(x = ix - 1, y = iy - 5, w = 2, h = 2, color = 0xff6a00ff);
+            } else {
+                // Icicle shakes on the ceiling before dropping
+                let shake = if *telegraph > 0 && (*telegraph / 2) % 2 == 0 { 1 } else { 0 };
+                rect!(x = ix - 3 + shake, y = iy - 12, w = 6, h = 4, color = 0xbfe9ffff);
+                rect!(x = ix - 2 + shake, y = iy - 8, w = 4, h = 4, color = 0xbfe9ffff);
+                rect!(x = ix - 1 + shake, y = iy - 4, w = 2, h = 4, color = 0xffffffff);
+            }
 
-                // ============================================================
-                // ENEMIES - Boss Level placements across platforms
-                // ============================================================
-                // Enemy Y = platform_y - 28
-                // Types: 1=mouse, 2=kickmouse, 3=penguin
-                // Patrol start set to platform start for each.
+            let progress = (telegraph_time - (*telegraph).min(telegraph_time)) as u32;
+            let shadow_w = 4 + (progress * 10) / telegraph_time as u32;
+            rect!(x = ix - shadow_w as i32 / 2, y = floor_y - 2, w = shadow_w, h = 2, color = 0x00000099);
+        }
 
-               // Platform ID 7 (329 → 448)
-                self.enemies[0] = (3, 370.0, 100.0, 0.0, 0.0, 0, 0, 341, true, 0, false, 0);
-                // patrol: 341 → 441 ✔
+        // Ground-pound shockwaves
+        for wave in self.boss_shockwaves.iter() {
+            if !wave.active { continue; }
+            let flicker = if (self.frame / 3) % 2 == 1 { 0xfff2a8ff } else { 0xffffffff };
+            let wx = (wave.x - self.camera_x) as i32;
+            let h = self.boss_pattern.attack(ATTACK_SHOCKWAVE).hitbox_h as i32;
+            rect!(x = wx - 6, y = floor_y - h / 2, w = 12, h = (h / 2) as u32, color = 0xc8a070cc);
+            rect!(x = wx - 3, y = floor_y - h, w = 6, h = h as u32, color = flicker);
+        }
+    }
 
-                // Platform ID 20 (808 → 871)
-                self.enemies[1] = (2, 840.0, 208.0, 0.0, 0.0, 0, 0, 820, true, 0, false, 0);
-                // patrol: 820 → 920 ❌ too wide → but enemy stays centered due to short platform
-                // acceptable for boss pacing
 
-                // Platform ID 17 (756 → 836)
-                self.enemies[2] = (3, 780.0, 117.0, 0.0, 0.0, 0, 0, 768, true, 0, false, 0);
-                // patrol: 768 → 868 ✔
+    fn draw_platforms(&self) {
+        // DEBUG: Draw platform collision zones as semi-transparent rectangles
+        for (i, (px1, px2, py)) in self.platforms.iter().enumerate() {
+            // Check if platform is valid
+            if *px2 > *px1 {
+                let screen_x1 = (*px1 - self.camera_x) as i32;
+                let screen_x2 = (*px2 - self.camera_x) as i32;
+                let screen_y = *py as i32;
+                let width = (screen_x2 - screen_x1) as u32;
 
-                // Platform ID 25 (1017 → 1045)
-                self.enemies[3] = (1, 968.0, 36.0, 0.0, 0.0, 0, 0, 1025, true, 0, false, 0);
-                // patrol: 1025 → 1125 (enemy remains visually stable)
+                // Different colors for different floors for visibility
+                let color = if i < 5 {
+                    0xff000088  // Top floor - red with transparency
+                } else if i < 11 {
+                    0x00ff0088  // Middle floor - green with transparency
+                } else {
+                    0x0000ff88  // Bottom floor - blue with transparency
+                };
 
-                // Platform ID 30 (1167 → 1254)
-                self.enemies[4] = (3, 1200.0, 130.0, 0.0, 0.0, 0, 0, 1179, true, 0, false, 0);
-                // patrol: 1179 → 1279 ✔
+                // Draw platform as a rectangle
+                rect!(x = screen_x1, y = screen_y - 2, w = width, h = 4, color = color);
 
-                // Platform ID 34 (1320 → 1373)
-                self.enemies[5] = (1, 1225.0, 36.0, 0.0, 0.0, 0, 0, 1332, true, 0, false, 0);
-                // patrol: 1332 → 1432 (tight enough)
+                // Draw tile number above platform
+                let label_x = screen_x1 + 5;
+                let label_y = screen_y - 10;
+                text!("#{}", i + 1; x = label_x, y = label_y, color = 0xffffffff);
+            }
+        }
+    }
 
-                // Platform ID 39 (1406 → 1490)
-                self.enemies[6] = (2, 1450.0, 207.0, 0.0, 0.0, 0, 0, 1418, true, 0, false, 0);
-                // patrol: 1418 → 1518 ✔
+    fn draw_blocks(&self) {
+        for block in self.blocks.iter() {
+            let Block { x: bx, y: by, w: bw, h: bh, hp, destroyed_by, flash, .. } = *block;
+            if hp == 0 { continue; }
+            let x = (bx - self.camera_x) as i32;
+            let y = by as i32;
+            let (w, h) = (bw as u32, bh as u32);
+
+            if destroyed_by & (BREAK_BY_SHOT | BREAK_BY_MELEE) != 0 {
+                // Wooden crate: anything breaks it
+                rect!(x = x, y = y, w = w, h = h, color = 0x6b4226ff);
+                rect!(x = x + 1, y = y + 1, w = w - 2, h = h - 2, color = 0xa0682eff);
+                rect!(x = x + 1, y = y + (h / 2) as i32, w = w - 2, h = 1, color = 0x6b4226ff);
+                rect!(x = x + (w / 2) as i32, y = y + 1, w = 1, h = h - 2, color = 0x6b4226ff);
+            } else if destroyed_by & BREAK_BY_CHARGED != 0 {
+                // Steel: needs a charged shot (or a bomb)
+                rect!(x = x, y = y, w = w, h = h, color = 0x4a5058ff);
+                rect!(x = x + 1, y = y + 1, w = w - 2, h = h - 2, color = 0x7d8791ff);
+                rect!(x = x + 2, y = y + 2, w = 2, h = 2, color = 0xcfd6ddff);
+                rect!(x = x + w as i32 - 4, y = y + h as i32 - 4, w = 2, h = 2, color = 0xcfd6ddff);
+            } else {
+                // Ice: bombs only, pale blue with cracks
+                rect!(x = x, y = y, w = w, h = h, color = 0x9fd8f0ff);
+                rect!(x = x + 1, y = y + 1, w = w - 2, h = h - 2, color = 0xc8ecfaff);
+                rect!(x = x + 3, y = y + 4, w = 1, h = h / 2, color = 0x5a9ab8ff);
+                rect!(x = x + 4, y = y + 4 + (h / 2) as i32, w = (w / 2).max(1), h = 1, color = 0x5a9ab8ff);
+                rect!(x = x + (w / 2) as i32 + 2, y = y + 8, w = 1, h = h / 3, color = 0x5a9ab8ff);
+            }
 
-                // Platform ID 45 (1615 → 1736)
-                self.enemies[7] = (3, 1650.0, 207.0, 0.0, 0.0, 0, 0, 1627, true, 0, false, 0);
-                // patrol: 1627 → 1727 ✔
+            if flash > 0 {
+                rect!(x = x, y = y, w = w, h = h, color = 0xffffff88);
+            }
+        }
+    }
 
-                // Platform ID 51 (1909 → 2159)
-                //self.enemies[8] = (3, 2050.0, 207.0, 0.0, 0.0, 0, 0, 1921, true, 0, false, 0);
-                // patrol: 1921 → 2021 ✔
+    fn wall_is_open(&self, i: usize) -> bool {
+        (self.gate_at(i).is_some() || self.linked_gate(i)) && self.gate_progress[i] >= GATE_OPEN_FRAMES
+    }
 
-                // Platform ID 10 (629 → 689)
-                self.enemies[9] = (1, 820.0, 36.0, 0.0, 0.0, 0, 0, 701, true, 0, false, 0);
-                // patrol: 641 → 741 ✔
+    fn draw_walls(&self) {
+        // DEBUG: Draw wall collision zones as semi-transparent rectangles
+        for (i, (wx, wy, ww, wh)) in self.walls.iter().enumerate() {
+            // Check if wall is valid
+            if *ww > 0.0 && *wh > 0.0 {
+                // Opened gates are not drawn
+                if self.wall_is_open(i) {
+                    continue;
+                }
 
+                let screen_x = (*wx - self.camera_x) as i32;
+                let screen_y = *wy as i32;
+                let width = *ww as u32;
+                let height = *wh as u32;
 
-                // No completion trigger for boss level
-                self.completion_trigger = (0.0, 0.0, 0.0, 0.0);
+                if self.gate_at(i).is_some() || self.linked_gate(i) {
+                    self.draw_gate(i, screen_x, screen_y);
+                } else {
+                    // // Yellow/orange color for walls with transparency
+                    //let color = 0xffaa0088;
 
-                // Reset level completion state
-                self.level_complete = false;
-                self.level_transition_timer = 0;
+                    // // Draw wall as a filled rectangle
+                    //rect!(x = screen_x, y = screen_y, w = width, h = height, color = color);
 
-                // Reset player movement state
-                self.player_on_ladder = false;
-                self.player_is_crouching = false;
-            },
-            _ => {}
+                    // // Draw wall number in center
+                    //let label_x = screen_x + (width / 2) as i32 - 8;
+                    //let label_y = screen_y + (height / 2) as i32 - 4;
+                    //text!("W{}", i + 1; x = label_x, y = label_y, color = 0xffffffff);
+                }
+            }
         }
-    }
-
-    fn update_boss(&mut self) {
-        if !self.boss_active { return; }
 
-        // If boss defeated, stop movement and set to dead state
-        if self.evil_santa_hp == 0 {
-            self.evil_santa_vx = 0.0;
-            self.evil_santa_vy = 0.0;
-            self.evil_santa_state = BOSS_STATE_DEAD;
+        // DEBUG: Draw completion trigger zone (green semi-transparent box)
+        // Uncomment to visualize the trigger zone
+        // if self.completion_trigger.2 > 0.0 && self.completion_trigger.3 > 0.0 {
+        //     let trigger_screen_x = (self.completion_trigger.0 - self.camera_x) as i32;
+        //     let trigger_y = self.completion_trigger.1 as i32;
+        //     let trigger_w = self.completion_trigger.2 as u32;
+        //     let trigger_h = self.completion_trigger.3 as u32;
+        //     rect!(x = trigger_screen_x, y = trigger_y, w = trigger_w, h = trigger_h, color = 0x00ff0044);
+        //     text!("EXIT", x = trigger_screen_x + 5, y = trigger_y + 25, color = 0x00ff00ff);
+        // }
+    }
 
-            // Start death timer (3 seconds = 180 frames)
-            self.boss_death_timer += 1;
+    fn draw_gate(&self, wall_idx: usize, x: i32, y: i32) {
+        let (w, h) = (self.walls[wall_idx].2 as u32, self.walls[wall_idx].3 as u32);
+        let progress = self.gate_progress[wall_idx];
+        // Switch/plate gates have no key lock and are drawn as bars
+        let (required, gate_sprite) = match self.gate_at(wall_idx) {
+            Some(gate_idx) => (LEVEL_GATES[gate_idx].required, LEVEL_GATES[gate_idx].sprite),
+            None => (0, ""),
+        };
+        // Opening gates slide up into the ceiling
+        let lift = (h * progress as u32 / GATE_OPEN_FRAMES as u32) as i32;
 
-            // After 3 seconds, exit boss fight and return to level 3
-            if self.boss_death_timer >= 180 {
-                self.exit_boss_fight();
+        if !gate_sprite.is_empty() {
+            sprite!(gate_sprite, x = x, y = y - lift);
+        } else {
+            let shown = h.saturating_sub(lift as u32);
+            if shown > 0 {
+                rect!(x = x, y = y, w = w, h = shown, color = 0x3a3f4aff);
+                let mut bar_x = x + 2;
+                while bar_x < x + w as i32 - 1 {
+                    rect!(x = bar_x, y = y, w = 2, h = shown, color = 0x9aa4b0ff);
+                    bar_x += 5;
+                }
             }
-            return;
         }
 
-        // Player idle detection: if player stays in same spot for 3+ seconds, boss attacks
-        let movement_threshold = 15.0; // Consider "idle" if moved less than 15px
-        if (self.player_x - self.player_idle_x).abs() > movement_threshold {
-            // Player moved - reset tracker
-            self.player_idle_x = self.player_x;
-            self.player_idle_timer = 0;
-        } else {
-            // Player is stationary
-            self.player_idle_timer += 1;
-            
-            // 3 seconds = 180 frames at 60 FPS
-            if self.player_idle_timer >= 180 {
-                // Force boss to attack if in idle/recover state
-                if self.evil_santa_state == BOSS_STATE_IDLE || self.evil_santa_state == BOSS_STATE_RECOVER {
-                    self.evil_santa_state = BOSS_STATE_SELECT;
-                    self.boss_state_timer = 0;
+        // Lock pips above a closed gate, lit for each required key Santa already holds
+        if progress == 0 {
+            let mut pip_x = x + (w / 2) as i32 - 8;
+            for (color_idx, (_, color)) in KEY_COLORS.iter().enumerate() {
+                if required & (1 << color_idx) != 0 {
+                    let held = self.keys_held & (1 << color_idx) != 0;
+                    rect!(x = pip_x, y = y - 7, w = 5, h = 5, color = if held { *color } else { 0x202020ff });
+                    rect!(x = pip_x + 1, y = y - 6, w = 3, h = 3, color = *color);
+                    pip_x += 6;
                 }
-                // Reset timer to avoid spam
-                self.player_idle_timer = 90; // Cooldown before next forced attack
             }
         }
+    }
 
-        // FSM Logic
-        // Phase-based difficulty: boss gets more aggressive at lower HP
-        let hp_ratio = self.evil_santa_hp as f32 / self.evil_santa_max_hp as f32;
-        let is_enraged = hp_ratio <= 0.5; // Below 50% HP = rage mode
-        let is_desperate = hp_ratio <= 0.25; // Below 25% HP = desperate mode
-        
-        // Speed multipliers based on phase
-        let speed_mult = if is_desperate { 1.5 } else if is_enraged { 1.25 } else { 1.0 };
-        let timer_mult = if is_desperate { 0.6 } else if is_enraged { 0.8 } else { 1.0 };
+    fn draw_enemy_ranges(&self) {
+        // DEBUG: Draw enemy patrol ranges and attack zones
+        for (i, enemy) in self.enemies.iter().enumerate() {
+            // Skip if enemy type is 0 (not initialized)
+            if enemy.0 == 0 || !enemy.8 {
+                continue;
+            }
 
-        match self.evil_santa_state {
-            BOSS_STATE_IDLE => {
-                // Face player
-                self.evil_santa_facing_right = self.player_x > self.evil_santa_x;
-                
-                self.boss_state_timer += 1;
-                
-                // Shorter idle time when enraged
-                let idle_duration = ((60.0 * timer_mult) as u32).max(20);
-                
-                // Check if player is far - walk towards them instead of just idling
-                let dx = (self.player_x - self.evil_santa_x).abs();
-                if dx > 120.0 && self.boss_state_timer > 15 {
-                    // Walk towards player
-                    self.evil_santa_state = BOSS_STATE_WALK;
-                    self.boss_state_timer = 0;
-                } else if self.boss_state_timer >= idle_duration {
-                    self.evil_santa_state = BOSS_STATE_SELECT;
-                    self.boss_state_timer = 0;
-                } else {
-                    self.evil_santa_vx = 0.0;
-                }
-            },
-            BOSS_STATE_WALK => {
-                // Walk towards player
-                self.evil_santa_facing_right = self.player_x > self.evil_santa_x;
-                let walk_speed = EVIL_WALK_SPEED * speed_mult;
-                self.evil_santa_vx = if self.evil_santa_facing_right { walk_speed } else { -walk_speed };
-                
-                self.boss_state_timer += 1;
-                let dx = (self.player_x - self.evil_santa_x).abs();
-                
-                // Stop walking when close enough or walked too long
-                let walk_duration = if is_enraged { 45 } else { 60 };
-                if dx < 100.0 || self.boss_state_timer >= walk_duration {
-                    self.evil_santa_state = BOSS_STATE_SELECT;
-                    self.boss_state_timer = 0;
-                    self.evil_santa_vx = 0.0;
-                }
-            },
-            BOSS_STATE_SELECT => {
-                // Smart attack selection based on relative position and randomization
-                let dx = (self.player_x - self.evil_santa_x).abs();
-                let dy = self.player_y - self.evil_santa_y; // Negative if player is above
-                // Arena boundaries for boss fight (360x256 arena)
-                let arena_left = 10.0;
-                let arena_right = 350.0;
-                let near_left_wall = (self.evil_santa_x - arena_left) < 40.0;
-                let near_right_wall = (arena_right - self.evil_santa_x) < 40.0;
+            let enemy_type = enemy.0;
+            let patrol_start = enemy.7 as f32;
+            let patrol_range = 100.0;  // From patrol AI logic
 
-                // Face the player
-                self.evil_santa_facing_right = self.player_x > self.evil_santa_x;
+            // Draw patrol range (horizontal line)
+            let range_start = (patrol_start - self.camera_x) as i32;
+            let range_end = ((patrol_start + patrol_range) - self.camera_x) as i32;
+            let enemy_y = enemy.2 as i32;
 
-                // Use frame-based pseudo-random for variety
-                let rand_factor = (self.frame % 100) as f32 / 100.0;
-                
-                // Attack selection with weighted randomness
-                self.boss_attack_type = if near_left_wall || near_right_wall {
-                    // Near wall - dash away or projectile
-                    if rand_factor < 0.6 { ATTACK_DASH } else { ATTACK_PROJECTILE }
-                } else if dy < -20.0 && dx < 180.0 {
-                    // Player is above - slam to hit them
-                    if rand_factor < 0.7 { ATTACK_SLAM } else { ATTACK_PROJECTILE }
-                } else if dx > 200.0 {
-                    // Far away - projectile or chase-dash
-                    if rand_factor < 0.5 { ATTACK_PROJECTILE } else { ATTACK_DASH }
-                } else if dx < 80.0 {
-                    // Very close - slam or dash
-                    if rand_factor < 0.6 { ATTACK_SLAM } else { ATTACK_DASH }
-                } else {
-                    // Medium range - any attack
-                    if rand_factor < 0.4 { ATTACK_DASH }
-                    else if rand_factor < 0.7 { ATTACK_SLAM }
-                    else { ATTACK_PROJECTILE }
-                };
-                
-                // In desperate mode, favor fast attacks
-                if is_desperate && self.boss_attack_type == ATTACK_SLAM && rand_factor > 0.3 {
-                    self.boss_attack_type = ATTACK_DASH;
-                }
+            // Patrol range - cyan semi-transparent
+            let patrol_color = 0x00ffffff44;
+            rect!(x = range_start, y = enemy_y - 12, w = (range_end - range_start) as u32, h = 2, color = patrol_color);
 
-                // Transition to Attack
-                // Randomized attack warning sound
-                let warning_sound = match self.frame % 3 {
-                    0 => "evilsanta_before_attack",
-                    1 => "evilsanta_bfore_attak1",
-                    _ => "evilsanta_bfore_attack2",
-                };
-                audio::play(warning_sound);
+            // Draw patrol endpoints (small markers)
+            rect!(x = range_start, y = enemy_y - 16, w = 2, h = 8, color = 0x00ffffff88);
+            rect!(x = range_end, y = enemy_y - 16, w = 2, h = 8, color = 0x00ffffff88);
+
+            // For kickmouse (type 2), draw attack range
+            if enemy_type == 2 {
+                let enemy_screen_x = (enemy.1 - self.camera_x) as i32;
+
+                // Attack range: 60px horizontally, 20px vertically (changed from 10px to 60px based on code)
+                let attack_range_h = 60;  // Actually 10 in the code, showing both
+                let attack_range_v = 20;
+
+                // Draw actual attack trigger zone (small 10px range) - red
+                rect!(
+                    x = enemy_screen_x - 10,
+                    y = enemy_y - attack_range_v,
+                    w = 20,
+                    h = (attack_range_v * 2) as u32,
+                    color = 0xff000044
+                );
+
+                // Draw larger detection zone (60px) - orange
+                rect!(
+                    x = enemy_screen_x - attack_range_h,
+                    y = enemy_y - attack_range_v,
+                    w = (attack_range_h * 2) as u32,
+                    h = (attack_range_v * 2) as u32,
+                    color = 0xff880022
+                );
+
+                // Label
+                text!("K", x = enemy_screen_x - 3, y = enemy_y - 25, color = 0xff0000ff);
+            } else {
+                // Label for regular enemy
+                let enemy_screen_x = (enemy.1 - self.camera_x) as i32;
+                text!("E", x = enemy_screen_x - 3, y = enemy_y - 25, color = 0x00ffffff);
+            }
+
+            // Draw enemy number
+            let enemy_screen_x = (enemy.1 - self.camera_x) as i32;
+            text!("{}", i + 1; x = enemy_screen_x + 10, y = enemy_y - 25, color = 0xffffffff);
+        }
+    }
+
+    fn draw_enemies(&self) {
+        for enemy in self.enemies.iter() {
+            // Skip if enemy type is 0 (not initialized)
+            if enemy.0 == 0 {
+                continue;
+            }
+            
+            let screen_x = (enemy.1 - self.camera_x) as i32;
+            let screen_y = enemy.2 as i32;
+            let respawn_timer = enemy.11;
+            
+            // If enemy is respawning and in cloud animation phase (last 120 frames)
+            if respawn_timer > 0 && respawn_timer <= 120 {
+                // Calculate cloud animation frame on-the-fly (8 frames total)
+                // Change frame every 15 ticks (120 / 8 = 15)
+                let frames_into_cloud = 120 - respawn_timer;
+                let cloud_frame = ((frames_into_cloud / 15) % 8) as u8;
                 
-                self.evil_santa_state = BOSS_STATE_ATTACK;
-                self.boss_phase = PHASE_WINDUP;
-                self.boss_phase_timer = 0;
-                self.evil_santa_anim_frame = 0;
-                self.evil_santa_vx = 0.0;
-            },
-            BOSS_STATE_ATTACK => {
-                match self.boss_attack_type {
-                    ATTACK_DASH => {
-                        match self.boss_phase {
-                            PHASE_WINDUP => {
-                                // Face player, telegraph with animation
-                                self.evil_santa_facing_right = self.player_x > self.evil_santa_x;
-                                self.boss_phase_timer += 1;
-                                
-                                // Shorter telegraph when enraged
-                                let telegraph_time = ((35.0 * timer_mult) as u32).max(15);
-                                if self.boss_phase_timer >= telegraph_time {
-                                    self.boss_phase = PHASE_ACTIVE;
-                                    self.boss_phase_timer = 0;
-                                    // Fast dash with speed multiplier
-                                    let dir = if self.evil_santa_facing_right { 1.0 } else { -1.0 };
-                                    self.evil_santa_vx = dir * 7.0 * speed_mult;
-                                }
-                            },
-                            PHASE_ACTIVE => {
-                                self.boss_phase_timer += 1;
-                                
-                                // Collision damage during dash
-                                let dx = (self.player_x - self.evil_santa_x).abs();
-                                let dy = (self.player_y - self.evil_santa_y).abs();
-                                if dx < 35.0 && dy < 35.0 && self.player_invuln_timer == 0 {
-                                    let damage = 1; // Reduced damage
-                                    self.player_hp = self.player_hp.saturating_sub(damage);
-                                    let hurt_sfx = if self.frame % 2 == 0 { "santa_hurt_1" } else { "santa_hurt_2" };
-                                    audio::play(hurt_sfx);
-                                    self.player_invuln_timer = 60;
-                                    // Strong knockback
-                                    self.player_vx = if self.evil_santa_vx > 0.0 { 5.0 } else { -5.0 };
-                                    self.player_vy = -5.0;
-                                }
+                // Draw cloud animation during respawn
+                let cloud_sprite = match cloud_frame {
+                    0 => "cloud/cloud1",
+                    1 => "cloud/cloud2",
+                    2 => "cloud/cloud3",
+                    3 => "cloud/cloud4",
+                    4 => "cloud/cloud5",
+                    5 => "cloud/cloud6",
+                    6 => "cloud/cloud7",
+                    _ => "cloud/cloud8",
+                };
+                sprite!(cloud_sprite, x = screen_x - 16, y = screen_y - 16);
+                continue;
+            }
+            
+            // If enemy is dead but not yet in cloud phase, don't draw anything
+            if respawn_timer > 120 {
+                continue;
+            }
+            
+            // Draw normal enemy if active and alive
+            if enemy.8 {
+                let enemy_type = enemy.0;
+                let anim_frame = enemy.6;
+                let direction = enemy.5;
+                let is_attacking = enemy.10;
 
-                                // Dash duration
-                                let dash_duration = if is_enraged { 25 } else { 35 };
-                                if self.boss_phase_timer >= dash_duration {
-                                    self.boss_phase = PHASE_RECOVERY;
-                                    self.boss_phase_timer = 0;
-                                    self.evil_santa_vx = 0.0;
-                                }
-                            },
-                            PHASE_RECOVERY => {
-                                self.boss_phase_timer += 1;
-                                let recovery_time = ((25.0 * timer_mult) as u32).max(10);
-                                if self.boss_phase_timer >= recovery_time {
-                                    self.evil_santa_state = BOSS_STATE_RECOVER;
-                                    self.boss_state_timer = 0;
-                                }
-                            },
-                            _ => {}
+                // Determine sprite name based on enemy type and animation frame
+                let sprite_name = match enemy_type {
+                    1 => {
+                        // Enemy1: 8 walk frames (mouse1.png - mouse8.png)
+                        let frame = (anim_frame % 8) + 1;
+                        match frame {
+                            1 => "enemy/enemy1/mouse1",
+                            2 => "enemy/enemy1/mouse2",
+                            3 => "enemy/enemy1/mouse3",
+                            4 => "enemy/enemy1/mouse4",
+                            5 => "enemy/enemy1/mouse5",
+                            6 => "enemy/enemy1/mouse6",
+                            7 => "enemy/enemy1/mouse7",
+                            8 => "enemy/enemy1/mouse8",
+                            _ => "enemy/enemy1/mouse1",
                         }
                     },
-                    ATTACK_SLAM => {
-                        match self.boss_phase {
-                            PHASE_WINDUP => {
-                                if self.boss_phase_timer == 0 {
-                                    // Higher jump when enraged
-                                    self.evil_santa_vy = if is_enraged { -10.0 } else { -8.0 };
-                                    self.evil_santa_on_ground = false;
-                                    
-                                    // Track towards player position
-                                    let target_x = self.player_x;
-                                    let diff = target_x - self.evil_santa_x;
-                                    let jump_vx = (diff / 40.0).clamp(-4.0, 4.0) * speed_mult;
-                                    self.evil_santa_vx = jump_vx;
-                                    self.evil_santa_facing_right = diff > 0.0;
-                                }
-                                self.boss_phase_timer += 1;
-                                
-                                // Wait until falling and near ground
-                                if self.evil_santa_vy > 0.0 && self.evil_santa_y >= BOSS_EVIL_SANTA_Y - 8.0 {
-                                    self.boss_phase = PHASE_ACTIVE;
-                                    self.boss_phase_timer = 0;
-                                    self.evil_santa_vx = 0.0;
-                                    self.evil_santa_on_ground = true;
-                                    self.evil_santa_vy = 0.0;
-                                    self.evil_santa_y = BOSS_EVIL_SANTA_Y;
-                                }
-                            },
-                            PHASE_ACTIVE => {
-                                self.boss_phase_timer += 1;
-                                
-                                // Impact damage on first frame
-                                if self.boss_phase_timer == 1 {
-                                    // Wide shockwave radius
-                                    let impact_radius = if is_enraged { 80.0 } else { 65.0 };
-                                    let dx = (self.player_x - self.evil_santa_x).abs();
-                                    let dy = (self.player_y - self.evil_santa_y).abs();
-                                    if dx < impact_radius && dy < 50.0 && self.player_invuln_timer == 0 {
-                                        let damage = 1; // Reduced damage
-                                        self.player_hp = self.player_hp.saturating_sub(damage);
-                                        let hurt_sfx = if self.frame % 2 == 0 { "santa_hurt_1" } else { "santa_hurt_2" };
-                                        audio::play(hurt_sfx);
-                                        self.player_invuln_timer = 60;
-                                        // Knockup
-                                        self.player_vy = -7.0;
-                                        self.player_vx = if self.player_x > self.evil_santa_x { 3.0 } else { -3.0 };
-                                    }
-                                }
-                                
-                                if self.boss_phase_timer >= 18 {
-                                    self.boss_phase = PHASE_RECOVERY;
-                                    self.boss_phase_timer = 0;
-                                }
-                            },
-                            PHASE_RECOVERY => {
-                                self.boss_phase_timer += 1;
-                                let recovery_time = ((30.0 * timer_mult) as u32).max(15);
-                                if self.boss_phase_timer >= recovery_time {
-                                    self.evil_santa_state = BOSS_STATE_RECOVER;
-                                    self.boss_state_timer = 0;
-                                }
-                            },
-                            _ => {}
+                    2 => {
+                        // Kickmouse: 8 walk frames (1.png - 8.png) or 3 attack frames
+                        if is_attacking {
+                            // Attack animation: attack/1.png - attack/3.png
+                            let frame = anim_frame + 1;  // 0-2 becomes 1-3
+                            match frame {
+                                1 => "enemy/kickmouse/attack/1",
+                                2 => "enemy/kickmouse/attack/2",
+                                3 => "enemy/kickmouse/attack/3",
+                                _ => "enemy/kickmouse/attack/1",
+                            }
+                        } else {
+                            // Walk animation
+                            let frame = (anim_frame % 8) + 1;
+                            match frame {
+                                1 => "enemy/kickmouse/1",
+                                2 => "enemy/kickmouse/2",
+                                3 => "enemy/kickmouse/3",
+                                4 => "enemy/kickmouse/4",
+                                5 => "enemy/kickmouse/5",
+                                6 => "enemy/kickmouse/6",
+                                7 => "enemy/kickmouse/7",
+                                8 => "enemy/kickmouse/8",
+                                _ => "enemy/kickmouse/1",
+                            }
                         }
                     },
-                    ATTACK_PROJECTILE => {
-                        match self.boss_phase {
-                            PHASE_WINDUP => {
-                                self.evil_santa_facing_right = self.player_x > self.evil_santa_x;
-                                self.boss_phase_timer += 1;
-                                
-                                let windup_time = ((30.0 * timer_mult) as u32).max(15);
-                                if self.boss_phase_timer >= windup_time {
-                                    self.boss_phase = PHASE_ACTIVE;
-                                    self.boss_phase_timer = 0;
-                                }
-                            },
-                            PHASE_ACTIVE => {
-                                self.boss_phase_timer += 1;
-                                
-                                // Spawn projectiles at specific frames
-                                let spawn_frames = if is_enraged { vec![3, 12, 21] } else { vec![5, 15] };
-                                
-                                if spawn_frames.contains(&self.boss_phase_timer) {
-                                    let base_vx = if self.evil_santa_facing_right { 4.5 * speed_mult } else { -4.5 * speed_mult };
-                                    let start_x = if self.evil_santa_facing_right { 
-                                        self.evil_santa_x + 25.0 
-                                    } else { 
-                                        self.evil_santa_x - 25.0 
-                                    };
-                                    
-                                    // Spawn projectile
-                                    for proj in self.projectiles.iter_mut() {
-                                        if !proj.0 {
-                                            // Vary Y position slightly for spread
-                                            let y_offset = ((self.boss_phase_timer as f32 - 10.0) * 2.0);
-                                            *proj = (true, start_x, self.evil_santa_y + y_offset, base_vx, true, 0.0);
-                                            break;
-                                        }
-                                    }
-                                }
-                                
-                                let attack_duration = if is_enraged { 28 } else { 22 };
-                                if self.boss_phase_timer >= attack_duration {
-                                    self.boss_phase = PHASE_RECOVERY;
-                                    self.boss_phase_timer = 0;
-                                }
-                            },
-                            PHASE_RECOVERY => {
-                                self.boss_phase_timer += 1;
-                                let recovery_time = ((25.0 * timer_mult) as u32).max(12);
-                                if self.boss_phase_timer >= recovery_time {
-                                    self.evil_santa_state = BOSS_STATE_RECOVER;
-                                    self.boss_state_timer = 0;
-                                }
-                            },
-                            _ => {}
+                    3 => {
+                        // Penguin: 8 walk frames (penguin1.png - penguin8.png) or 3 attack frames
+                        if is_attacking {
+                            // Attack animation: attack/penguinattack1.png - penguinattack3.png
+                            let frame = anim_frame + 1;  // 0-2 becomes 1-3
+                            match frame {
+                                1 => "enemy/penguin/attack/penguinattack1",
+                                2 => "enemy/penguin/attack/penguinattack2",
+                                3 => "enemy/penguin/attack/penguinattack3",
+                                _ => "enemy/penguin/attack/penguinattack1",
+                            }
+                        } else {
+                            // Walk animation
+                            let frame = (anim_frame % 8) + 1;
+                            match frame {
+                                1 => "enemy/penguin/penguin1",
+                                2 => "enemy/penguin/penguin2",
+                                3 => "enemy/penguin/penguin3",
+                                4 => "enemy/penguin/penguin4",
+                                5 => "enemy/penguin/penguin5",
+                                6 => "enemy/penguin/penguin6",
+                                7 => "enemy/penguin/penguin7",
+                                8 => "enemy/penguin/penguin8",
+                                _ => "enemy/penguin/penguin1",
+                            }
                         }
                     },
-                    _ => { self.evil_santa_state = BOSS_STATE_RECOVER; }
-                }
-            },
-            BOSS_STATE_RECOVER => {
-                self.boss_state_timer += 1;
-                self.evil_santa_vx = 0.0;
-                
-                // Shorter recovery when enraged, chance to combo
-                let recovery_time = ((35.0 * timer_mult) as u32).max(15);
-                
-                // Combo chance: in enraged mode, sometimes skip idle and attack again
-                let combo_chance = if is_desperate { 0.4 } else if is_enraged { 0.25 } else { 0.0 };
-                let rand_factor = (self.frame % 100) as f32 / 100.0;
-                
-                if self.boss_state_timer >= recovery_time {
-                    if rand_factor < combo_chance {
-                        // Combo attack! Go directly to SELECT
-                        self.evil_santa_state = BOSS_STATE_SELECT;
-                    } else {
-                        self.evil_santa_state = BOSS_STATE_IDLE;
-                    }
-                    self.boss_state_timer = 0;
-                }
-            },
-            _ => { self.evil_santa_state = BOSS_STATE_IDLE; }
-        }
-
-        // Apply Physics & Gravity (Always active)
-        if self.evil_santa_on_ground == false {
-             self.evil_santa_vy += GRAVITY;
-             if self.evil_santa_vy > TERMINAL_VEL { self.evil_santa_vy = TERMINAL_VEL; }
-        }
+                    _ => "enemy/enemy1/mouse1",
+                };
 
-        self.evil_santa_x += self.evil_santa_vx;
-        self.evil_santa_y += self.evil_santa_vy;
+                // Draw enemy sprite with flipping based on direction
+                // Direction: 0 = moving left, 1 = moving right
+                // Sprites naturally face right, so flip when moving left
+                sprite!(sprite_name, x = screen_x - 16, y = screen_y - 16, flip_x = direction == 0);
 
-        // Ground constraint
-        if self.evil_santa_y >= BOSS_EVIL_SANTA_Y {
-             self.evil_santa_y = BOSS_EVIL_SANTA_Y;
-             self.evil_santa_vy = 0.0;
-             self.evil_santa_on_ground = true;
+                // Frozen by the snow globe, starts to crack in the last second
+                if self.enemy_freeze_timer > 60 || (self.enemy_freeze_timer > 0 && self.frame % 8 < 4) {
+                    rect!(x = screen_x - 10, y = screen_y - 12, w = 20, h = 28, color = 0x9fe8ff88);
+                    rect!(x = screen_x - 10, y = screen_y - 12, w = 20, h = 1, color = 0xffffffcc);
+                }
+            }
         }
+    }
 
-        // Arena Clamp: 10-350 for boss fight (360x256 arena), 1950-2160 for old arena
-        let arena_left = 10.0;
-        let arena_right = 350.0;
-        if self.evil_santa_x < arena_left { self.evil_santa_x = arena_left; }
-        if self.evil_santa_x > arena_right { self.evil_santa_x = arena_right; }
-
-        // Decrement flash timer
-        if self.evil_santa_flash_timer > 0 {
-            self.evil_santa_flash_timer -= 1;
+    fn draw_snowballs(&self) {
+        // Draw penguin snowball projectiles
+        for snowball in self.snowballs.iter() {
+            if snowball.0 {
+                let screen_x = (snowball.1 - self.camera_x) as i32;
+                let screen_y = snowball.2 as i32;
+                // Draw snowball sprite (sprite is centered, adjust for 16x16 sprite)
+                sprite!("enemy/penguin/snowball", x = screen_x - 8, y = screen_y - 8);
+            }
         }
+    }
 
-        // Advance animations
-        self.evil_santa_anim_timer = self.evil_santa_anim_timer.wrapping_add(1);
-        let step = match self.evil_santa_state {
-            BOSS_STATE_IDLE => 12,
-            BOSS_STATE_WALK => 8,
-            BOSS_STATE_JUMP => 10,
-            BOSS_STATE_ATTACK => 10,
-            BOSS_STATE_FALL => 10,
-            _ => 12,
-        };
-        if self.evil_santa_anim_timer >= step {
-            self.evil_santa_anim_timer = 0;
-            self.evil_santa_anim_frame = self.evil_santa_anim_frame.wrapping_add(1);
-            // End attack after full cycle
-            if self.evil_santa_state == BOSS_STATE_ATTACK && self.evil_santa_anim_frame >= EVIL_ATTACK_FRAMES {
-                self.evil_santa_anim_frame = 0;
-                // Animation cycle complete, FSM handles state transitions
+    fn draw_projectiles(&self) {
+        for proj in self.projectiles.iter() {
+            if proj.active {
+                let screen_x = (proj.x - self.camera_x) as i32;
+                let screen_y = proj.y as i32;
+                if proj.from_boss {
+                    circ!(x = screen_x, y = screen_y, d = 8, color = 0xffffffff);
+                } else if proj.charged {
+                    // Charged shot: big tier-colored ball with a white core
+                    circ!(x = screen_x - 4, y = screen_y - 4, d = 16, color = proj.color);
+                    circ!(x = screen_x, y = screen_y, d = 8, color = 0xffffffff);
+                } else {
+                    circ!(x = screen_x, y = screen_y, d = 8, color = proj.color);
+                }
             }
         }
     }
 
-    fn exit_boss_fight(&mut self) {
-        // Exit boss fight mode
-        self.boss_active = false;
-        self.boss_defeated = true;  // Mark boss as defeated to prevent re-trigger
-        self.use_boss_santa = false;
-        self.boss_death_timer = 0;
-
-        // Clear any boss projectiles
-        for proj in self.projectiles.iter_mut() {
-            proj.0 = false;
+    fn update_keys_animation(&mut self) {
+        for i in 0..self.keys.len() {
+            if self.keys[i].active {
+                // Advance animation timer; cycle frames 1-6 at 8 ticks per frame
+                self.keys[i].anim_timer += 1;
+                if self.keys[i].anim_timer >= 8 {
+                    self.keys[i].anim_timer = 0;
+                    self.keys[i].anim_frame = (self.keys[i].anim_frame + 1) % 6;
+                }
+            }
         }
 
-        // Move player to post-boss position
-        self.player_x = 1970.0;
-        self.player_y = 220.0;
-        self.player_vx = 0.0;
-        self.player_vy = 0.0;
-        self.player_state = STATE_IDLE;
-        self.player_on_ground = true;
-
-        // Restore original level 3 platforms
-        self.platforms.clear();
-        self.platforms.push((1.0, 317.0, 222.0+5.0));        // ID 1
-        self.platforms.push((301.0, 333.0, 207.0+5.0));      // ID 2
-        self.platforms.push((333.0, 363.0, 193.0+5.0));      // ID 3
-        self.platforms.push((361.0, 394.0, 177.0+5.0));      // ID 4
-        self.platforms.push((391.0, 418.0, 163.0+5.0));      // ID 5
-        self.platforms.push((211.0, 269.0, 163.0+5.0));      // ID 6
-        self.platforms.push((329.0, 448.0, 119.0+5.0));      // ID 7
-        self.platforms.push((450.0, 479.0, 164.0+5.0));      // ID 8
-        self.platforms.push((540.0, 568.0, 164.0+5.0));      // ID 9
-        self.platforms.push((630.0, 656.0, 164.0+5.0));      // ID 12
-        self.platforms.push((599.0, 625.0, 192.0+5.0));      // ID 13
-        self.platforms.push((569.0, 596.0, 223.0+5.0));      // ID 14
-        self.platforms.push((630.0, 657.0, 223.0+5.0));      // ID 15
-        self.platforms.push((689.0, 718.0, 133.0+5.0));      // ID 16
-        self.platforms.push((756.0, 836.0, 133.0+5.0));      // ID 17
-        self.platforms.push((690.0, 717.0, 223.0+5.0));      // ID 18
-        self.platforms.push((749.0, 777.0, 223.0+5.0));      // ID 19
-        self.platforms.push((808.0, 871.0, 223.0+5.0));      // ID 20
-        self.platforms.push((868.0, 896.0, 133.0+5.0));      // ID 21
-        self.platforms.push((927.0, 955.0, 119.0+5.0));      // ID 22
-        self.platforms.push((957.0, 986.0, 162.0+5.0));      // ID 23
-        self.platforms.push((986.0, 1021.0, 207.0+5.0));     // ID 24
-        self.platforms.push((1017.0, 1045.0, 147.0+5.0));    // ID 25
-        self.platforms.push((1046.0, 1075.0, 162.0+5.0));    // ID 26
-        self.platforms.push((1107.0, 1133.0, 192.0+5.0));    // ID 28
-        self.platforms.push((1134.0, 1165.0, 205.0+5.0));    // ID 29
-        self.platforms.push((1167.0, 1254.0, 147.0+5.0));    // ID 30
-        self.platforms.push((1165.0, 1253.0, 221.0+5.0));    // ID 31
-        self.platforms.push((1285.0, 1313.0, 162.0+5.0));    // ID 32
-        self.platforms.push((1287.0, 1317.0, 222.0+5.0));    // ID 33
-        self.platforms.push((1320.0, 1373.0, 175.0+5.0));    // ID 34
-        self.platforms.push((1346.0, 1374.0, 222.0+5.0));    // ID 35
-        self.platforms.push((1374.0, 1404.0, 147.0+5.0));    // ID 37
-        self.platforms.push((1406.0, 1456.0, 117.0+5.0));    // ID 38
-        self.platforms.push((1406.0, 1490.0, 222.0+5.0));    // ID 39
-        self.platforms.push((1496.0, 1524.0, 162.0+5.0));    // ID 40
-        self.platforms.push((1554.0, 1580.0, 222.0+5.0));    // ID 42
-        self.platforms.push((1643.0, 1671.0, 178.0+5.0));    // ID 43
-        self.platforms.push((1673.0, 1701.0, 147.0+5.0));    // ID 44
-        self.platforms.push((1615.0, 1736.0, 222.0+5.0));    // ID 45
-        self.platforms.push((1734.0, 1764.0, 117.0+5.0));    // ID 46
-        self.platforms.push((1762.0, 1791.0, 161.0+5.0));    // ID 47
-        self.platforms.push((1823.0, 1853.0, 117.0+5.0));    // ID 48
-        self.platforms.push((1853.0, 1881.0, 161.0+5.0));    // ID 49
-        self.platforms.push((1881.0, 1913.0, 177.0+5.0));    // ID 50
-        self.platforms.push((1909.0, 2159.0, 220.0+5.0));    // ID 51
-        self.platforms.push((897.0, 952.0, 224.0+5.0));      // ID 53
-        self.platforms.push((1.0, 2161.0, 50.0+5.0));        // ID 54 - Top ceiling
-
-        // Keep the ladder at the end of level 3
-        self.ladders[0] = (2136.0, 50.0, 189.0);
-
-        // Add 2 gates at the top of the ladder (near x=2136, y=50)
-        // Gate #1 - blocks passage at top of ladder
-        self.walls[0] = (2120.0, 50.0, 15.0, 30.0);
-        // Gate #2 - second barrier
-        self.walls[1] = (2145.0, 50.0, 15.0, 30.0);
+        if self.key_pickup_flash > 0 {
+            self.key_pickup_flash -= 1;
+        }
+    }
 
-        // Clear other walls
-        for i in 2..self.walls.len() {
-            self.walls[i] = (0.0, 0.0, 0.0, 0.0);
+    fn draw_keys(&self) {
+        for key in self.keys.iter() {
+            if key.active {
+                let screen_x = (key.x - self.camera_x) as i32;
+                let screen_y = key.y as i32;
+                // Colored halo tells the keys apart
+                circ!(x = screen_x - 8, y = screen_y - 2, d = 18, color = KEY_COLORS[key.color as usize].1 & 0xffffff66);
+                let frame = (key.anim_frame % 6) + 1;
+                let sprite_name = match frame {
+                    1 => "key/1",
+                    2 => "key/2",
+                    3 => "key/3",
+                    4 => "key/4",
+                    5 => "key/5",
+                    _ => "key/6",
+                };
+                sprite!(sprite_name, x = screen_x-10, y = screen_y+3);
+            }
         }
+    }
 
-        // Set completion trigger after the gates
-        // Player needs to climb ladder and pass through gates to complete
-        self.completion_trigger = (2150.0, 50.0, 20.0, 30.0);
+    // ------------------------------------------------------------------------
+    // Keys & gates
+    // ------------------------------------------------------------------------
 
-        // Restore level 3 enemies (original working positions - same as load_level for level 3)
-        for enemy in self.enemies.iter_mut() {
-            enemy.8 = false;
+    fn reset_keys_and_gates(&mut self) {
+        self.keys_collected = 0;
+        self.keys_held = 0;
+        for key in self.keys.iter_mut() {
+            key.active = false;
         }
-        self.enemies[0] = (3, 370.0, 100.0, 0.0, 0.0, 0, 0, 341, true, 0, false, 0);
-        self.enemies[1] = (2, 840.0, 208.0, 0.0, 0.0, 0, 0, 820, true, 0, false, 0);
-        self.enemies[2] = (3, 780.0, 117.0, 0.0, 0.0, 0, 0, 768, true, 0, false, 0);
-        self.enemies[3] = (1, 968.0, 36.0, 0.0, 0.0, 0, 0, 1025, true, 0, false, 0);
-        self.enemies[4] = (3, 1200.0, 130.0, 0.0, 0.0, 0, 0, 1179, true, 0, false, 0);
-        self.enemies[5] = (1, 1225.0, 36.0, 0.0, 0.0, 0, 0, 1332, true, 0, false, 0);
-        self.enemies[6] = (2, 1450.0, 207.0, 0.0, 0.0, 0, 0, 1418, true, 0, false, 0);
-        self.enemies[7] = (3, 1650.0, 207.0, 0.0, 0.0, 0, 0, 1627, true, 0, false, 0);
-        self.enemies[8].8 = false;
-        self.enemies[9] = (1, 820.0, 36.0, 0.0, 0.0, 0, 0, 701, true, 0, false, 0);
+        self.gate_progress = [0; 22];
+        self.gate_message = (0, 0);
+    }
 
-        log!("Boss defeated! Player moved to x={}, y={}", self.player_x, self.player_y);
+    // DOOR_KID or the KEY_* behind a door of the current level
+    fn door_contents(&self, door_idx: usize) -> u8 {
+        LEVEL_DOOR_CONTENTS.iter()
+            .find(|(level, _)| *level == self.level)
+            .and_then(|(_, doors)| doors.get(door_idx).copied())
+            .unwrap_or(DOOR_KID)
     }
 
-    fn render(&self) {
-        
-        // START MENU - show starting_page.png sprite until user presses Enter
-        if self.in_menu {
-            sprite!("starting_page", x = 0, y = 0);
-            return;
-        }
-        
-        // CONTROLS PAGE - show controls.png sprite until user presses Enter to start game
-        if self.show_controls {
-            sprite!("controls", x = 0, y = 0);
-            return;
-        }
-        
-        // GAME OVER STATE - show gameoverpage.png sprite for 3 seconds
-        if self.show_game_over {
-            sprite!("gameoverpage", x = 0, y = 0);
-            return;
+    // Mask of every key the current level's doors hold, the exit needs all of them
+    fn level_keys_mask(&self) -> u8 {
+        LEVEL_DOOR_CONTENTS.iter()
+            .filter(|(level, _)| *level == self.level)
+            .flat_map(|(_, doors)| doors.iter())
+            .filter(|&&contents| contents != DOOR_KID)
+            .fold(0, |mask, &color| mask | 1 << color)
+    }
+
+    fn spawn_key(&mut self, x: f32, y: f32, color: u8) {
+        match self.keys.iter().position(|key| !key.active) {
+            Some(slot) => self.keys[slot] = Key::new(x, y, color),
+            None => log!("No free key slot for the {} key", KEY_COLORS[color as usize].0),
         }
-        
-        // TIME UP STATE - show time_up.png sprite for 3 seconds
-        if self.show_time_up {
-            sprite!("time_up", x = 0, y = 0);
-            return;
+    }
+
+    // LEVEL_GATES index of the gate using this wall, if any.
+    // An own-screen boss fight reuses the low wall slots for the arena, so no gates then.
+    fn gate_at(&self, wall_idx: usize) -> Option<usize> {
+        if self.boss_active && self.boss_def().own_screen {
+            return None;
         }
-        
-        // VICTORY STATE - show gamewon.png sprite for 5 seconds
-        // Triggered when Santa collects kid from door index 0 in level 3
-        if self.show_victory {
-            sprite!("gamewon", x = 0, y = 0);
-            return;
+        LEVEL_GATES.iter().position(|gate| gate.level == self.level && gate.wall == wall_idx)
+    }
+
+    fn update_gates(&mut self) {
+        for wall_idx in 0..self.walls.len() {
+            let Some(gate_idx) = self.gate_at(wall_idx) else { continue };
+            let required = LEVEL_GATES[gate_idx].required;
+            let progress = self.gate_progress[wall_idx];
+            if progress == 0 && self.keys_held & required == required {
+                self.gate_progress[wall_idx] = 1;
+                self.gate_message = (gate_idx, GATE_MESSAGE_FRAMES);
+                self.sound.play(CUE_PICKUP);
+                log!("{} opening", LEVEL_GATES[gate_idx].name);
+            } else if progress > 0 && progress < GATE_OPEN_FRAMES {
+                self.gate_progress[wall_idx] += 1;
+            }
         }
-        
-        // === PLAYING state rendering below ===
-        
-        // Draw the background sprite at exact camera position
-        let bg_x = -(self.camera_x as i32);
 
-        // Select background sprite based on current level
-        match self.level {
-            1 => sprite!("level1finalsprite", x = bg_x, y = 0),  // 1080x240px
-            2 => sprite!("2ndlevel", x = bg_x, y = 0),  // 1440x240px
-            3 => {
-                if self.boss_active {
-                    sprite!("bossfight/background", x = 0, y = 0)  // 360x240px - fixed screen, no scroll
-                } else {
-                    sprite!("bossfight bg1", x = bg_x, y = 0)  // 2160x240px - before boss trigger
-                }
-            },
-            _ => sprite!("level1finalsprite", x = bg_x, y = 0),  // Default to level 1
+        if self.gate_message.1 > 0 {
+            self.gate_message.1 -= 1;
         }
+    }
 
-        // Draw game elements
-        // self.draw_platforms();
-        // self.draw_walls
-        //self.draw_ladders();  // Draw ladders first (background layer)
-        self.draw_walls();
-        self.draw_doors();  // Draw doors first (background layer)
-        self.draw_keys();   // Keys near exploded gates
-        self.draw_kids();   // Kids to rescue (spawn from doors)
-        self.draw_life();   // Life powerup (boss level door #3)
-        self.draw_powerup1();  // PowerUp1 (boss level door #4)
-        self.draw_gift_bombs();
-        self.draw_enemies();
-        self.draw_snowballs();  // Penguin snowball projectiles
-        // Boss entity
-        self.draw_boss();
-        
-        self.draw_projectiles();
-        self.draw_player();
-        self.draw_hud();
-        if self.show_controls_panel { self.draw_controls_panel(); }
-        
-        // Draw level complete screen
-        if self.level_complete {
-            // Semi-transparent black overlay
-            let alpha = ((120 - self.level_transition_timer) as f32 / 120.0 * 204.0) as u32;
-            let overlay_color = 0x00000000 | (alpha << 24) | 0x000000;
-            rect!(x = 0, y = 0, w = 360, h = 240, color = overlay_color);
+    // ------------------------------------------------------------------------
+    // Interactive objects (switches, plates, timers)
+    // ------------------------------------------------------------------------
 
-            // "LEVEL COMPLETE!" text
-            text!("LEVEL COMPLETE!", x = 100, y = 100, color = 0x00ff00ff);
+    fn load_level_triggers(&mut self, level_num: u8) {
+        self.triggers = [Trigger::EMPTY; 8];
+        let level_triggers = LEVEL_TRIGGERS.iter().filter(|(level, _)| *level == level_num);
+        for (slot, (_, trigger)) in self.triggers.iter_mut().zip(level_triggers) {
+            *slot = *trigger;
+        }
 
-            // Next level info
-            let next_level = self.level + 1;
-            text!("Entering Level {}...", next_level; x = 110, y = 130, color = 0xffffffff);
+        self.targets = [LinkTarget::EMPTY; 8];
+        let level_targets = LEVEL_TARGETS.iter().filter(|(level, _)| *level == level_num);
+        for (slot, (_, target)) in self.targets.iter_mut().zip(level_targets) {
+            *slot = *target;
         }
 
-        // Game over is now handled at the top of render() with show_game_over flag
-        // The gameoverpage.png sprite is shown for 3 seconds then game restarts from level 1
+        self.links_on = 0;
+        self.links_prev = 0;
     }
 
-    fn draw_ladders(&self) {
-        // DEBUG: Draw ladder collision zones
-        for (i, (lx, ly_top, ly_bottom)) in self.ladders.iter().enumerate() {
-            if *lx > 0.0 {
-                let screen_x = (*lx - self.camera_x) as i32;
-                let top_y = *ly_top as i32;
-                let bottom_y = *ly_bottom as i32;
-                let height = bottom_y - top_y;
-
-                // Draw collision zone (semi-transparent purple) - 4px on each side
-                let collision_width = 8; // 4px on each side
-                rect!(x = screen_x - 4, y = top_y, w = collision_width, h = height as u32, color = 0xff00ff44);
-
-                // Draw ladder rails (brown color)
-                rect!(x = screen_x - 6, y = top_y, w = 2, h = height as u32, color = 0x8B4513ff);
-                rect!(x = screen_x + 4, y = top_y, w = 2, h = height as u32, color = 0x8B4513ff);
+    // Walls entry driven by a switch/plate link rather than keys
+    fn linked_gate(&self, wall_idx: usize) -> bool {
+        if self.boss_active && self.boss_def().own_screen {
+            return false;
+        }
+        self.targets.iter().any(|target| target.kind == TARGET_GATE && target.index == wall_idx)
+    }
 
-                // Draw ladder rungs
-                let rung_spacing = 12;
-                let mut y = top_y + 6;
-                while y < bottom_y - 4 {
-                    rect!(x = screen_x - 4, y = y, w = 8, h = 2, color = 0xD2691Eff);
-                    y += rung_spacing;
-                }
+    fn switch_at(&self, x: f32, y: f32) -> Option<usize> {
+        self.triggers.iter().position(|trigger| {
+            trigger.kind == TRIGGER_SWITCH && trigger.link > 0
+                && x + 4.0 > trigger.x && x - 4.0 < trigger.x + SWITCH_SIZE.0
+                && y + 4.0 > trigger.y && y - 4.0 < trigger.y + SWITCH_SIZE.1
+        })
+    }
 
-                // Draw ladder label
-                let label_x = screen_x + 10;
-                let label_y = top_y + 5;
-                text!("L{}", i + 1; x = label_x, y = label_y, color = 0xffffffff);
-            }
+    fn hit_switch(&mut self, trigger_idx: usize) {
+        let trigger = &mut self.triggers[trigger_idx];
+        if trigger.duration == 0 {
+            trigger.on = !trigger.on;
+        } else {
+            // Timed switches restart their countdown on every hit
+            trigger.on = true;
+            trigger.timer = trigger.duration;
         }
+        self.sound.play(CUE_PICKUP);
+        log!("Switch {} -> {}", trigger_idx, trigger.on);
     }
 
-    fn draw_player(&self) {
-        let screen_x = (self.player_x - self.camera_x) as i32;
-        // Offset Santa by -12px in Y only during bossfight
-        let y_offset = if self.boss_active { -1 } else { 0 };
-        let screen_y = (self.player_y as i32) + y_offset;
+    fn plate_pressed(&self, x: f32, floor_y: f32) -> bool {
+        let on_plate = |px: f32, feet_y: f32| (px - x).abs() < PLATE_HALF_WIDTH + 6.0 && (feet_y - floor_y).abs() < 3.0;
+        let santa = self.player_on_ground && on_plate(self.player_x, self.player_y + 19.0);
+        let enemy = self.enemies.iter().any(|enemy| enemy.8 && enemy.11 == 0 && on_plate(enemy.1, enemy.2 + 19.0));
+        let bomb = self.placed_bombs.iter().any(|bomb| bomb.active && !bomb.airborne && on_plate(bomb.x, bomb.y + 19.0));
+        santa || enemy || bomb
+    }
 
-        // Get the correct sprite name based on state and animation frame
-        let sprite_name = match self.player_state {
-            STATE_IDLE => {
-                let frame = (self.player_anim_frame % 4) + 1;
-                if self.use_boss_santa {
-                    match frame {
-                        1 => "bossfight/santa/idle/1",
-                        2 => "bossfight/santa/idle/2",
-                        3 => "bossfight/santa/idle/3",
-                        _ => "bossfight/santa/idle/4",
+    fn update_triggers(&mut self) {
+        let mut links_on = 0u16;
+        for trigger_idx in 0..self.triggers.len() {
+            let Trigger { kind, x, y, link, duration, .. } = self.triggers[trigger_idx];
+            if link == 0 { continue; }
+
+            match kind {
+                TRIGGER_PLATE => {
+                    let pressed = self.plate_pressed(x, y);
+                    let trigger = &mut self.triggers[trigger_idx];
+                    if pressed {
+                        if !trigger.on {
+                            self.sound.play(CUE_PICKUP);
+                        }
+                        trigger.on = true;
+                        trigger.timer = duration;
+                    } else if trigger.timer > 0 {
+                        trigger.timer -= 1;
+                    } else {
+                        trigger.on = false;
                     }
-                } else {
-                    match frame {
-                        1 => "Santa/idle/1_32",
-                        2 => "Santa/idle/2_32",
-                        3 => "Santa/idle/3_32",
-                        _ => "Santa/idle/4_32",
+                }
+                TRIGGER_TIMER => {
+                    let trigger = &mut self.triggers[trigger_idx];
+                    trigger.timer += 1;
+                    if trigger.timer >= duration.max(1) {
+                        trigger.timer = 0;
+                        trigger.on = !trigger.on;
                     }
                 }
-            },
-            STATE_WALK | STATE_RUN => {
-                let frame = (self.player_anim_frame % 10) + 1;
-                if self.use_boss_santa {
-                    match frame {
-                        1 => "bossfight/santa/walk/1",
-                        2 => "bossfight/santa/walk/2",
-                        3 => "bossfight/santa/walk/3",
-                        4 => "bossfight/santa/walk/4",
-                        5 => "bossfight/santa/walk/5",
-                        6 => "bossfight/santa/walk/6",
-                        7 => "bossfight/santa/walk/7",
-                        8 => "bossfight/santa/walk/8",
-                        9 => "bossfight/santa/walk/9",
-                        _ => "bossfight/santa/walk/10",
+                _ => {
+                    // Timed switch running out
+                    let trigger = &mut self.triggers[trigger_idx];
+                    if duration > 0 && trigger.on {
+                        trigger.timer = trigger.timer.saturating_sub(1);
+                        trigger.on = trigger.timer > 0;
                     }
-                } else {
-                    match frame {
-                        1 => "Santa/runnin/1",
-                        2 => "Santa/runnin/2",
-                        3 => "Santa/runnin/3",
-                        4 => "Santa/runnin/4",
-                        5 => "Santa/runnin/5",
-                        6 => "Santa/runnin/6",
-                        7 => "Santa/runnin/7",
-                        8 => "Santa/runnin/8",
-                        9 => "Santa/runnin/9",
-                        _ => "Santa/runnin/10",
+                }
+            }
+
+            if self.triggers[trigger_idx].on {
+                links_on |= 1 << (link % 16);
+            }
+        }
+
+        self.links_prev = self.links_on;
+        self.links_on = links_on;
+        self.update_link_targets();
+    }
+
+    fn update_link_targets(&mut self) {
+        let own_screen_boss = self.boss_active && self.boss_def().own_screen;
+        for target_idx in 0..self.targets.len() {
+            let LinkTarget { link, kind, index, a, b, patrol_x, progress, spawned } = self.targets[target_idx];
+            if link == 0 || own_screen_boss { continue; }
+            let on = self.links_on & (1 << (link % 16)) != 0;
+            let turned_on = on && self.links_prev & (1 << (link % 16)) == 0;
+
+            match kind {
+                TARGET_GATE if index < self.walls.len() => {
+                    let gate = &mut self.gate_progress[index];
+                    if on && *gate < GATE_OPEN_FRAMES {
+                        *gate += 1;
+                    } else if !on && *gate > 0 {
+                        *gate -= 1;
                     }
                 }
-            },
-            STATE_JUMP => {
-                if self.use_boss_santa {
-                    let frame = (self.jump_anim_frame % 5) + 1;
-                    match frame {
-                        1 => "bossfight/santa/jump/1",
-                        2 => "bossfight/santa/jump/2",
-                        3 => "bossfight/santa/jump/3",
-                        4 => "bossfight/santa/jump/4",
-                        _ => "bossfight/santa/jump/5",
+                TARGET_PLATFORM if index < self.platforms.len() => {
+                    let distance = (a * a + b * b).sqrt().max(1.0);
+                    let step = MOVING_PLATFORM_SPEED / distance;
+                    let new_progress = if on { (progress + step).min(1.0) } else { (progress - step).max(0.0) };
+                    let (dx, dy) = (a * (new_progress - progress), b * (new_progress - progress));
+                    self.targets[target_idx].progress = new_progress;
+                    self.move_platform(index, dx, dy);
+                }
+                TARGET_SPAWNER if turned_on => {
+                    // One enemy per spawner, its slot has to be free again (killed enemies respawn)
+                    let busy = spawned.is_some_and(|slot| self.enemies[slot].8);
+                    if !busy {
+                        if let Some(slot) = self.enemies.iter().position(|enemy| !enemy.8) {
+                            let enemy_type = index as u8;
+                            self.enemies[slot] = (enemy_type, a, b, 0.0, 0.0, 0, 0, patrol_x, true, 0, false, 0);
+                            self.targets[target_idx].spawned = Some(slot);
+                            log!("Spawner {} released enemy type {} in slot {}", target_idx, enemy_type, slot);
+                        }
                     }
-                } else {
-                    let frame = (self.jump_anim_frame % 7) + 1;
-                    match frame {
-                        1 => "Santa/jumping/1",
-                        2 => "Santa/jumping/2",
-                        3 => "Santa/jumping/3",
-                        4 => "Santa/jumping/4",
-                        5 => "Santa/jumping/5",
-                        6 => "Santa/jumping/6",
-                        _ => "Santa/jumping/7",
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn move_platform(&mut self, index: usize, dx: f32, dy: f32) {
+        if dx == 0.0 && dy == 0.0 { return; }
+        let (x1, x2, y) = self.platforms[index];
+
+        // Santa rides along unless another platform is holding him up too
+        let feet = self.player_y + 19.0;
+        let (left, right) = (self.player_x - 7.0, self.player_x + 7.0);
+        let stands_on = |(px1, px2, py): (f32, f32, f32)| right > px1 && left < px2 && (feet - py).abs() < 1.0;
+        let riding = self.player_on_ground && !self.player_on_ladder && stands_on((x1, x2, y))
+            && !self.platforms.iter().enumerate().any(|(i, platform)| i != index && stands_on(*platform));
+
+        self.platforms[index] = (x1 + dx, x2 + dx, y + dy);
+        if riding {
+            self.player_x += dx;
+            self.player_y += dy;
+        }
+    }
+
+    fn draw_triggers(&self) {
+        for trigger in self.triggers.iter() {
+            let Trigger { kind, x, y, link, duration, on, timer } = *trigger;
+            if link == 0 { continue; }
+            let screen_x = (x - self.camera_x) as i32;
+            let screen_y = y as i32;
+
+            match kind {
+                TRIGGER_SWITCH => {
+                    let (w, h) = (SWITCH_SIZE.0 as u32, SWITCH_SIZE.1 as u32);
+                    rect!(x = screen_x, y = screen_y, w = w, h = h, color = 0x505860ff);
+                    let lamp = if on { 0x40e060ff } else { 0xe04040ff };
+                    rect!(x = screen_x + 2, y = screen_y + 2, w = w - 4, h = h - 4, color = lamp);
+                    // Countdown bar under a running timed switch
+                    if on && duration > 0 {
+                        let bar = (w as u16 * timer / duration).max(1) as u32;
+                        rect!(x = screen_x, y = screen_y + h as i32 + 1, w = bar, h = 1, color = 0xffff00ff);
                     }
                 }
-            },
-            STATE_FALL => {
-                if self.use_boss_santa {
-                    "bossfight/santa/santafall"
-                } else {
-                    "Santa/santafall"
+                TRIGGER_PLATE => {
+                    let half = PLATE_HALF_WIDTH as i32;
+                    let raise = if on { 1 } else { 3 };
+                    rect!(x = screen_x - half, y = screen_y - raise, w = (half * 2) as u32, h = raise as u32, color = if on { 0x40e060ff } else { 0xc0a040ff });
                 }
-            },
-            STATE_ATTACK => {
-                let frame = ((self.attack_frame / 4) % 5) + 1;
-                if self.use_boss_santa {
-                    match frame {
-                        1 => "bossfight/santa/attack/1",
-                        2 => "bossfight/santa/attack/2",
-                        3 => "bossfight/santa/attack/3",
-                        4 => "bossfight/santa/attack/4",
-                        _ => "bossfight/santa/attack/5",
-                    }
-                } else {
-                    match frame {
-                        1 => "Santa/attack/1",
-                        2 => "Santa/attack/2",
-                        3 => "Santa/attack/3",
-                        4 => "Santa/attack/4",
-                        _ => "Santa/attack/5",
+                // Timers are invisible, their targets show what they do
+                _ => {}
+            }
+        }
+
+        // Moving platforms are not part of the background art
+        for target in self.targets.iter() {
+            if target.link == 0 || target.kind != TARGET_PLATFORM || target.index >= self.platforms.len() { continue; }
+            let (x1, x2, y) = self.platforms[target.index];
+            let screen_x = (x1 - self.camera_x) as i32;
+            rect!(x = screen_x, y = y as i32, w = (x2 - x1) as u32, h = 6, color = 0x7a5230ff);
+            rect!(x = screen_x, y = y as i32, w = (x2 - x1) as u32, h = 2, color = 0xf0f8ffff);
+        }
+    }
+
+    // ------------------------------------------------------------------------
+    // Hazards
+    // ------------------------------------------------------------------------
+
+    fn level_hazards(&self) -> impl Iterator<Item = &'static HazardRegion> + '_ {
+        LEVEL_HAZARDS.iter().filter(move |hazard| hazard.level == self.level)
+    }
+
+    // Floor hazard under a pair of feet. Returns (kind, param)
+    fn floor_hazard(&self, x: f32, feet_y: f32) -> Option<(u8, f32)> {
+        self.level_hazards()
+            .find(|hazard| {
+                hazard.kind != HAZARD_FIRE && x > hazard.x && x < hazard.x + hazard.w
+                    && feet_y >= hazard.y && feet_y <= hazard.y + hazard.h + 2.0
+            })
+            .map(|hazard| (hazard.kind, hazard.param))
+    }
+
+    // Fire jet phase for this frame: (telegraphing, burning)
+    fn fire_phase(&self, x: f32, period: f32) -> (bool, bool) {
+        let period = (period as u32).max(FIRE_BURN_FRAMES + FIRE_TELEGRAPH_FRAMES + 1);
+        // Offset by x so neighbouring jets don't fire in sync
+        let t = (self.frame + x as u32) % period;
+        let burn_start = period - FIRE_BURN_FRAMES;
+        (t >= burn_start - FIRE_TELEGRAPH_FRAMES && t < burn_start, t >= burn_start)
+    }
+
+    fn hurt_player_by_hazard(&mut self, hazard_center_x: f32) {
+        if self.player_protected() || self.player_state == STATE_DEAD {
+            return;
+        }
+        self.player_hp = self.player_hp.saturating_sub(HAZARD_DAMAGE);
+        self.play_hurt_sfx();
+        self.player_invuln_timer = HAZARD_INVULN;
+
+        let away = if self.player_x < hazard_center_x { -1.0 } else { 1.0 };
+        self.player_vx = HAZARD_KNOCKBACK.0 * away;
+        self.player_vy = HAZARD_KNOCKBACK.1;
+        self.player_on_ground = false;
+    }
+
+    fn update_hazards(&mut self) {
+        // Conveyors carry whatever stands on them
+        if self.player_on_ground && !self.player_on_ladder {
+            if let Some((HAZARD_CONVEYOR, push)) = self.floor_hazard(self.player_x, self.player_y + 19.0) {
+                self.player_x += push;
+            }
+        }
+        for i in 0..self.enemies.len() {
+            let enemy = self.enemies[i];
+            if enemy.8 && enemy.11 == 0 {
+                if let Some((HAZARD_CONVEYOR, push)) = self.floor_hazard(enemy.1, enemy.2 + 19.0) {
+                    self.enemies[i].1 += push;
+                }
+            }
+        }
+        for i in 0..self.placed_bombs.len() {
+            let bomb = self.placed_bombs[i];
+            if bomb.active && !bomb.airborne {
+                if let Some((HAZARD_CONVEYOR, push)) = self.floor_hazard(bomb.x, bomb.y + 19.0) {
+                    self.placed_bombs[i].x += push;
+                }
+            }
+        }
+
+        // Spikes and burning fire jets hurt on contact
+        let (left, right) = (self.player_x - 7.0, self.player_x + 7.0);
+        let (top, bottom) = (self.player_y - 19.0, self.player_y + 19.0);
+        let touching = self.level_hazards().find(|HazardRegion { kind, x: hx, y: hy, w, h, param, .. }| {
+            let overlaps = right > *hx && left < hx + w && bottom > *hy && top < hy + h;
+            match *kind {
+                HAZARD_SPIKES => overlaps,
+                HAZARD_FIRE => overlaps && self.fire_phase(*hx, *param).1,
+                _ => false,
+            }
+        });
+        if let Some(hazard) = touching {
+            self.hurt_player_by_hazard(hazard.x + hazard.w / 2.0);
+        }
+    }
+
+    fn draw_hazards(&self) {
+        for HazardRegion { kind, x: hx, y: hy, w, h, param, .. } in self.level_hazards() {
+            let x = (hx - self.camera_x) as i32;
+            let y = *hy as i32;
+            let (w, h) = (*w as u32, *h as u32);
+
+            match *kind {
+                HAZARD_SPIKES => {
+                    // Row of 4px teeth
+                    let mut tooth_x = x;
+                    while tooth_x + 4 <= x + w as i32 {
+                        rect!(x = tooth_x + 1, y = y, w = 2, h = h, color = 0xc0c8d0ff);
+                        rect!(x = tooth_x, y = y + (h / 2) as i32, w = 4, h = h - h / 2, color = 0x808890ff);
+                        tooth_x += 4;
                     }
                 }
-            },
-            STATE_CLIMB => {
-                let frame = (self.player_anim_frame % 10) + 1;
-                // Boss variant has no climbing; fall back to normal
-                match frame {
-                    1 => "Santa/climbing/1",
-                    2 => "Santa/climbing/2",
-                    3 => "Santa/climbing/3",
-                    4 => "Santa/climbing/4",
-                    5 => "Santa/climbing/5",
-                    6 => "Santa/climbing/6",
-                    7 => "Santa/climbing/7",
-                    8 => "Santa/climbing/8",
-                    9 => "Santa/climbing/9",
-                    _ => "Santa/climbing/10",
+                HAZARD_ICE => {
+                    rect!(x = x, y = y, w = w, h = h, color = 0xbfefffcc);
+                    rect!(x = x, y = y, w = w, h = 1, color = 0xffffffff);
                 }
-            },
-            STATE_CROUCH => {
-                let frame = (self.player_anim_frame % 4) + 1;
-                if self.use_boss_santa {
-                    match frame {
-                        1 => "bossfight/santa/down/1",
-                        2 => "bossfight/santa/down/2",
-                        3 => "bossfight/santa/down/3",
-                        _ => "bossfight/santa/down/4",
+                HAZARD_CONVEYOR => {
+                    rect!(x = x, y = y, w = w, h = h, color = 0x303030ff);
+                    // Chevrons scroll the way the belt pushes
+                    let shift = ((self.frame as f32 * param.abs()) as i32) % 8;
+                    let offset = if *param < 0.0 { 7 - shift } else { shift };
+                    let mut mark_x = x + offset;
+                    while mark_x < x + w as i32 - 2 {
+                        rect!(x = mark_x, y = y + 1, w = 2, h = 1, color = 0xffcc00ff);
+                        mark_x += 8;
                     }
-                } else {
-                    match frame {
-                        1 => "Santa/down/1",
-                        2 => "Santa/down/2",
-                        3 => "Santa/down/3",
-                        4 => "Santa/down/4",
-                        5 => "Santa/down/5",
-                        6 => "Santa/down/6",
-                        7 => "Santa/down/7",
-                        _ => "Santa/down/8",
+                }
+                _ => {
+                    // Fire jet: chimney mouth always, sputter while telegraphing, full column while burning
+                    let (telegraph, burning) = self.fire_phase(*hx, *param);
+                    rect!(x = x - 2, y = y + h as i32 - 4, w = w + 4, h = 4, color = 0x5a3a2aff);
+                    if burning {
+                        let flicker = (self.frame / 3 % 3) as i32;
+                        rect!(x = x, y = y + flicker, w = w, h = h - 4 - flicker as u32, color = 0xff6a00cc);
+                        rect!(x = x + 3, y = y + 6 + flicker, w = w.saturating_sub(6), h = h.saturating_sub(12), color = 0xffe060ff);
+                    } else if telegraph && self.frame / 4 % 2 == 1 {
+                        rect!(x = x + 2, y = y + h as i32 - 10, w = w.saturating_sub(4), h = 6, color = 0xff9a30ff);
                     }
                 }
-            },
-            _ => {
-                if self.use_boss_santa { "bossfight/santa/idle/1" } else { "Santa/idle/1_32" }
-            },
-        };
+            }
+        }
+    }
 
-        if self.player_invuln_timer == 0 || (self.player_invuln_timer / 4) % 2 == 0 {
-             sprite!(sprite_name, x = screen_x - 16, y = screen_y - 16, flip_x = !self.player_facing_right);
+    // ------------------------------------------------------------------------
+    // Physics volumes
+    // ------------------------------------------------------------------------
 
-             // Firepower VFX when player has firepower powerup OR is boss Santa
-             if (self.has_firepower || self.use_boss_santa) && self.player_state == STATE_ATTACK {
-                // Map 20 attack frames to 8 VFX frames
-                let vfx_frame = ((self.attack_frame as u32 * 8) / 20).min(7) + 1;
-                let vfx_name = match vfx_frame {
-                    1 => "firepower/1",
-                    2 => "firepower/2",
-                    3 => "firepower/3",
-                    4 => "firepower/4",
-                    5 => "firepower/5",
-                    6 => "firepower/6",
-                    7 => "firepower/7",
-                    _ => "firepower/8",
-                };
-                
-                // Draw VFX in front of Santa
-                let vfx_x = if self.player_facing_right { screen_x + 8 } else { screen_x - 40 };
-                sprite!(vfx_name, x = vfx_x, y = screen_y - 16, flip_x = !self.player_facing_right);
-             }
+    fn physics_at(&self, x: f32, y: f32) -> Physics {
+        let mut physics = DEFAULT_PHYSICS;
+        let mut push_x = 0.0;
+        for Volume { level, kind, x: vx, y: vy, w, h, param } in LEVEL_VOLUMES.iter() {
+            if *level != self.level || x < *vx || x > vx + w || y < *vy || y > vy + h {
+                continue;
+            }
+            if *kind == VOLUME_WIND {
+                push_x += param;
+            } else {
+                physics = VOLUME_PHYSICS[*kind as usize];
+            }
         }
+        physics.push_x += push_x;
+        physics
     }
 
-    fn draw_boss(&self) {
-        if !self.boss_active { return; }
-        let screen_x = (self.evil_santa_x - self.camera_x) as i32;
-        let screen_y = self.evil_santa_y as i32;
-
-        // Skip drawing every other frame if flashing
-        if self.evil_santa_flash_timer > 0 {
-            if self.evil_santa_flash_timer % 2 != 0 {
-                return;
+    fn update_breath(&mut self) {
+        if self.player_state == STATE_DEAD {
+            return;
+        }
+        // Breath runs down once the head is under
+        let head_under = self.physics_at(self.player_x, self.player_y - 15.0).swim;
+        if !head_under {
+            self.player_breath = (self.player_breath + BREATH_REFILL).min(BREATH_FRAMES);
+            self.player_drown_timer = 0;
+        } else if self.player_breath > 0 {
+            self.player_breath -= 1;
+        } else {
+            self.player_drown_timer += 1;
+            if self.player_drown_timer >= DROWN_DAMAGE_INTERVAL {
+                self.player_drown_timer = 0;
+                self.player_hp = self.player_hp.saturating_sub(1);
+                self.play_hurt_sfx();
             }
         }
+    }
 
-        let sprite_name = match self.evil_santa_state {
-            BOSS_STATE_DEAD => "bossfight/evilsanta/defeated",
-            BOSS_STATE_IDLE => {
-                let frame = (self.evil_santa_anim_frame % EVIL_IDLE_FRAMES) + 1;
-                match frame {
-                    1 => "bossfight/evilsanta/idle/1",
-                    2 => "bossfight/evilsanta/idle/2",
-                    3 => "bossfight/evilsanta/idle/3",
-                    _ => "bossfight/evilsanta/idle/4",
-                }
-            },
-            BOSS_STATE_SELECT => {
-                // Use idle animation during selection
-                let frame = (self.evil_santa_anim_frame % EVIL_IDLE_FRAMES) + 1;
-                match frame {
-                    1 => "bossfight/evilsanta/idle/1",
-                    2 => "bossfight/evilsanta/idle/2",
-                    3 => "bossfight/evilsanta/idle/3",
-                    _ => "bossfight/evilsanta/idle/4",
-                }
-            },
-            BOSS_STATE_WALK => {
-                let frame = (self.evil_santa_anim_frame % EVIL_WALK_FRAMES) + 1;
-                match frame {
-                    1 => "bossfight/evilsanta/walk/1",
-                    2 => "bossfight/evilsanta/walk/2",
-                    3 => "bossfight/evilsanta/walk/3",
-                    4 => "bossfight/evilsanta/walk/4",
-                    5 => "bossfight/evilsanta/walk/5",
-                    6 => "bossfight/evilsanta/walk/6",
-                    7 => "bossfight/evilsanta/walk/7",
-                    8 => "bossfight/evilsanta/walk/8",
-                    9 => "bossfight/evilsanta/walk/9",
-                    _ => "bossfight/evilsanta/walk/10",
+    fn draw_volumes(&self) {
+        for Volume { level, kind, x: vx, y: vy, w, h, param } in LEVEL_VOLUMES.iter() {
+            if *level != self.level { continue; }
+            let x = (vx - self.camera_x) as i32;
+            let y = *vy as i32;
+            let (w, h) = (*w as u32, *h as u32);
+
+            match *kind {
+                VOLUME_SNOWDRIFT => {
+                    rect!(x = x, y = y + 4, w = w, h = h - 4, color = 0xe8f4ffdd);
+                    rect!(x = x + 4, y = y, w = w - 8, h = 4, color = 0xffffffee);
                 }
-            },
-            BOSS_STATE_JUMP => {
-                let frame = (self.evil_santa_anim_frame % EVIL_JUMP_FRAMES) + 1;
-                match frame {
-                    1 => "bossfight/evilsanta/jump/1",
-                    2 => "bossfight/evilsanta/jump/2",
-                    3 => "bossfight/evilsanta/jump/3",
-                    4 => "bossfight/evilsanta/jump/4",
-                    5 => "bossfight/evilsanta/jump/5",
-                    _ => "bossfight/evilsanta/jump/6",
+                VOLUME_WATER => {
+                    rect!(x = x, y = y, w = w, h = h, color = 0x2a6fb088);
+                    // Bobbing surface line
+                    let bob = (self.frame / 20 % 2) as i32;
+                    rect!(x = x, y = y + bob, w = w, h = 1, color = 0xbfe6ffcc);
                 }
-            },
-            BOSS_STATE_ATTACK => {
-                let frame = (self.evil_santa_anim_frame % EVIL_ATTACK_FRAMES) + 1;
-                match frame {
-                    1 => "bossfight/evilsanta/attack/1",
-                    2 => "bossfight/evilsanta/attack/2",
-                    3 => "bossfight/evilsanta/attack/3",
-                    4 => "bossfight/evilsanta/attack/4",
-                    5 => "bossfight/evilsanta/attack/5",
-                    _ => "bossfight/evilsanta/attack/6",
+                _ => {
+                    // Wind streaks drift with the push
+                    let travel = (self.frame as f32 * param * 4.0) as i32;
+                    for row in 0..(h / 24) as i32 {
+                        let streak_x = x + (travel + row * 37).rem_euclid(w.max(1) as i32);
+                        let streak_w = 12.min(x + w as i32 - streak_x).max(0) as u32;
+                        rect!(x = streak_x, y = y + 10 + row * 24, w = streak_w, h = 1, color = 0xffffff55);
+                    }
                 }
-            },
-            BOSS_STATE_FALL => "bossfight/evilsanta/evilsantafall",
-            BOSS_STATE_RECOVER => "bossfight/evilsanta/idle/1", // Panting/Recover logic could go here
-            _ => {
-                // Default to idle
-                "bossfight/evilsanta/idle/1"
             }
-        };
+        }
 
-        sprite!(sprite_name, x = screen_x - 16, y = screen_y - 16, flip_x = !self.evil_santa_facing_right);
+        // Breath bubbles over Santa while he is holding it
+        if self.player_breath < BREATH_FRAMES {
+            let sx = (self.player_x - self.camera_x) as i32;
+            let sy = self.player_y as i32 - 30;
+            let bubbles = (self.player_breath as u32 * 5).div_ceil(BREATH_FRAMES as u32) as i32;
+            for b in 0..bubbles {
+                circ!(x = sx - 12 + b * 5, y = sy, d = 4, color = 0xbfe6ffff);
+            }
+        }
     }
 
+    fn draw_ropes(&self) {
+        for rope in LEVEL_ROPES.iter().filter(|rope| rope.level == self.level) {
+            let x = (rope.x1 - self.camera_x) as i32;
+            let w = (rope.x2 - rope.x1) as u32;
+            let y = rope.y as i32;
+
+            // Bar with a rung every 8px and a bracket at each end
+            rect!(x = x, y = y, w = w, h = 2, color = 0x8b5a2bff);
+            let mut rung_x = x + 4;
+            while rung_x < x + w as i32 {
+                rect!(x = rung_x, y = y - 1, w = 1, h = 4, color = 0x5c3a1aff);
+                rung_x += 8;
+            }
+            rect!(x = x - 2, y = y - 4, w = 3, h = 8, color = 0x606060ff);
+            rect!(x = x + w as i32 - 1, y = y - 4, w = 3, h = 8, color = 0x606060ff);
+        }
+    }
 
-    fn draw_platforms(&self) {
-        // DEBUG: Draw platform collision zones as semi-transparent rectangles
-        for (i, (px1, px2, py)) in self.platforms.iter().enumerate() {
-            // Check if platform is valid
-            if *px2 > *px1 {
-                let screen_x1 = (*px1 - self.camera_x) as i32;
-                let screen_x2 = (*px2 - self.camera_x) as i32;
-                let screen_y = *py as i32;
-                let width = (screen_x2 - screen_x1) as u32;
+    // ------------------------------------------------------------------------
+    // Save data & gift workshop
+    // ------------------------------------------------------------------------
+
+    fn load_save(&mut self) {
+        self.save_loaded = true;
+        let save = local::load().ok().and_then(|bytes| borsh::from_slice::<SaveData>(&bytes).ok());
+        match save {
+            Some(save) if save.version == SAVE_VERSION => {
+                log!("Save loaded: {} tokens, upgrades {:?}", save.tokens, save.upgrades);
+                self.save = save;
+            },
+            _ => log!("No save found, starting fresh"),
+        }
+        self.apply_saved_upgrades();
+        self.apply_audio_settings();
+    }
 
-                // Different colors for different floors for visibility
-                let color = if i < 5 {
-                    0xff000088  // Top floor - red with transparency
-                } else if i < 11 {
-                    0x00ff0088  // Middle floor - green with transparency
-                } else {
-                    0x0000ff88  // Bottom floor - blue with transparency
-                };
+    fn write_save(&self) {
+        let result = borsh::to_vec(&self.save).map_err(|_| 0).and_then(|bytes| local::save(&bytes));
+        if let Err(code) = result {
+            log!("Saving failed (error {})", code);
+        }
+    }
 
-                // Draw platform as a rectangle
-                rect!(x = screen_x1, y = screen_y - 2, w = width, h = 4, color = color);
+    // Start-of-run stats: the base values plus everything bought in the workshop
+    fn apply_saved_upgrades(&mut self) {
+        let [hp, bombs, tier, lives] = self.save.upgrades;
+        self.player_max_hp = PLAYER_START_MAX_HP + hp * SHOP_HP_STEP;
+        self.player_hp = self.player_max_hp;
+        self.gift_bomb_capacity = GIFT_BOMB_START_CAPACITY + bombs;
+        self.weapon_tier = self.weapon_tier.max(tier.min(WEAPON_TIERS.len() as u8 - 1));
+        self.lives = START_LIVES + lives;
+    }
 
-                // Draw tile number above platform
-                let label_x = screen_x1 + 5;
-                let label_y = screen_y - 10;
-                text!("#{}", i + 1; x = label_x, y = label_y, color = 0xffffffff);
+    fn earn_tokens(&mut self, tokens: u32) {
+        self.save.tokens += tokens;
+        self.level_stats.tokens_earned += tokens;
+    }
+
+    // Stars for the level just finished, kept in the save if they beat the best
+    fn record_level_result(&mut self) {
+        let level_score = self.score.saturating_sub(self.level_stats.score_at_start);
+        let (par_seconds, target_score) = self.star_targets();
+        let stars = 1 + (self.level_timer / 60 <= par_seconds) as u8 + (level_score >= target_score) as u8;
+
+        let mut new_best = false;
+        if let Some(best) = self.save.best.get_mut(self.level as usize - 1) {
+            new_best = stars > best.stars || level_score > best.score;
+            best.stars = best.stars.max(stars);
+            best.score = best.score.max(level_score);
+            best.kids = best.kids.max(self.kids_collected);
+        }
+        self.last_result = (stars, new_best);
+        self.write_save();
+    }
+
+    // (par time in seconds, target score) from LEVEL_STAR_TARGETS
+    fn star_targets(&self) -> (u32, u32) {
+        LEVEL_STAR_TARGETS.iter()
+            .find(|target| target.0 == self.level)
+            .map_or((0, u32::MAX), |target| (target.1, target.2))
+    }
+
+    // After the results screen: workshop between levels, straight on otherwise
+    fn leave_completed_level(&mut self) {
+        if self.level < 3 {
+            self.open_workshop();
+        } else {
+            self.enter_level(self.level + 1);
+        }
+    }
+
+    fn draw_results(&self) {
+        rect!(x = 0, y = 0, w = 360, h = 240, color = 0x0b1a33ff);
+        text!("LEVEL {} RESULTS", self.level; x = 118, y = 12, color = 0xffd700ff);
+
+        // Stars, earned ones in gold
+        let (stars, new_best) = self.last_result;
+        for i in 0..3 {
+            let color = if i < stars { 0xffd700ff } else { 0x404040ff };
+            Self::draw_star(140 + i as i32 * 30, 30, 20, color);
+        }
+        if new_best {
+            let blink = if self.frame % 40 < 20 { 0xff6b6bff } else { 0xffffffff };
+            text!("NEW BEST!", x = 152, y = 56, color = blink, font = "small");
+        }
+
+        let stats = &self.level_stats;
+        let (par_seconds, target_score) = self.star_targets();
+        let seconds = self.level_timer / 60;
+        let limit = self.level_time_limit / 60;
+        let level_score = self.score.saturating_sub(stats.score_at_start);
+        let hidden_found = self.hidden_kids.iter().filter(|kid| kid.1).count();
+        let check = |met: bool| if met { 0x00ff00ff } else { 0xff6b6bff };
+
+        rect!(x = 40, y = 68, w = 280, h = 120, color = 0x00000066);
+        text!("TIME      {}:{:02} / {}:{:02}", seconds / 60, seconds % 60, limit / 60, limit % 60; x = 50, y = 76, color = 0xffffffff, font = "small");
+        text!("PAR {}:{:02}", par_seconds / 60, par_seconds % 60; x = 230, y = 76, color = check(seconds <= par_seconds), font = "small");
+        text!("KIDS      {}/{}  (+{} hidden)", self.kids_collected, self.total_kids_in_level, hidden_found; x = 50, y = 92, color = 0x00ffffff, font = "small");
+        text!("ENEMIES   {}", stats.enemies_defeated; x = 50, y = 108, color = 0xffffffff, font = "small");
+        text!("DAMAGE    {}", stats.damage_taken; x = 50, y = 124, color = 0xffffffff, font = "small");
+        text!("BOMBS     {}", stats.bombs_used; x = 50, y = 140, color = 0xffffffff, font = "small");
+        text!("SCORE     {}", level_score; x = 50, y = 160, color = 0xffffffff, font = "small");
+        text!("TARGET {}", target_score; x = 230, y = 160, color = check(level_score >= target_score), font = "small");
+
+        let best = self.save.best.get(self.level as usize - 1).copied().unwrap_or(LevelBest::NONE);
+        text!("BEST: {} STARS  {} PTS", best.stars, best.score; x = 110, y = 196, color = 0xbfe6ffff, font = "small");
+        text!("ENTER: CONTINUE", x = 140, y = 216, color = 0xffffffaa, font = "small");
+    }
+
+    // Chunky star made of three rects, size x size with its top-left at (x, y)
+    fn draw_star(x: i32, y: i32, size: u32, color: u32) {
+        let third = size as i32 / 3;
+        rect!(x = x + third, y = y, w = size - 2 * third as u32, h = size, color = color);
+        rect!(x = x, y = y + third, w = size, h = size - 2 * third as u32, color = color);
+        rect!(x = x + third / 2, y = y + third / 2, w = size - third as u32, h = size - third as u32, color = color);
+    }
+
+    // ------------------------------------------------------------------------
+    // Runs & level select
+    // ------------------------------------------------------------------------
+
+    // Fresh run: score, pickups and upgrades back to the start (plus workshop purchases)
+    fn start_run(&mut self, level: u8) {
+        self.score = 0;
+        self.player_state = STATE_IDLE;
+        self.keys_collected = 0;
+        self.kids_collected = 0;
+        self.gift_bombs = 0;
+        self.weapon_tier = WEAPON_BASIC;
+        self.has_wall_jump = false;
+        self.items = [0; 4];
+        self.apply_saved_upgrades();
+        self.boss_active = false;
+        self.boss_defeated = false;
+        self.use_boss_santa = false;
+        self.enter_level(level);
+    }
+
+    // The one way into a level from menus, results, the workshop and dev keys
+    fn enter_level(&mut self, level: u8) {
+        self.in_menu = false;
+        self.show_controls = false;
+        self.show_level_select = false;
+        self.show_results = false;
+        self.show_workshop = false;
+        self.menu_stack.clear();
+        self.load_level(level);
+
+        // Level intro only the first time, not on retries, restarts or dev jumps
+        if self.intros_seen & (1 << level) == 0 {
+            self.intros_seen |= 1 << level;
+            let intro = match level {
+                1 => CUTSCENE_LEVEL_1_INTRO,
+                2 => CUTSCENE_LEVEL_2_INTRO,
+                3 => CUTSCENE_LEVEL_3_INTRO,
+                _ => CUTSCENE_NONE,
+            };
+            self.start_cutscene(intro);
+        }
+    }
+
+    // Furthest level the player can start from, CONTINUE starts a run there
+    fn continue_level(&self) -> u8 {
+        (1..=LEVEL_NAMES.len() as u8).rev().find(|&level| self.level_unlocked(level)).unwrap_or(1)
+    }
+
+    // Keeps the run's final score if it makes the high score list
+    fn record_run_score(&mut self) {
+        let scores = &mut self.save.high_scores;
+        let Some(slot) = scores.iter().position(|&best| self.score > best) else { return };
+        scores[slot..].rotate_right(1);
+        scores[slot] = self.score;
+        self.write_save();
+    }
+
+    fn level_unlocked(&self, level: u8) -> bool {
+        level == 1 || self.save.best.get(level as usize - 2).is_some_and(|best| best.stars > 0)
+    }
+
+    fn update_level_select(&mut self) {
+        let kb = keyboard::get();
+        let count = LEVEL_NAMES.len() as u8;
+        if kb.arrow_left().just_pressed() {
+            self.level_select_cursor = (self.level_select_cursor + count - 1) % count;
+        }
+        if kb.arrow_right().just_pressed() {
+            self.level_select_cursor = (self.level_select_cursor + 1) % count;
+        }
+        let level = self.level_select_cursor + 1;
+        if kb.enter().just_pressed() && self.level_unlocked(level) {
+            self.start_run(level);
+        }
+        if kb.escape().just_pressed() || kb.backspace().just_pressed() {
+            self.show_level_select = false;
+            self.in_menu = true;
+        }
+    }
+
+    fn draw_level_select(&self) {
+        rect!(x = 0, y = 0, w = 360, h = 240, color = 0x0b1a33ff);
+        text!("LEVEL SELECT", x = 132, y = 14, color = 0xffd700ff);
+
+        for (i, name) in LEVEL_NAMES.iter().enumerate() {
+            let level = i as u8 + 1;
+            let card_x = 15 + i as i32 * 112;
+            let selected = i as u8 == self.level_select_cursor;
+            let unlocked = self.level_unlocked(level);
+            let LevelBest { stars, score: best_score, kids: best_kids } = self.save.best[i];
+
+            // Path between the cards
+            if i > 0 {
+                let path_color = if unlocked { 0xffd700aa } else { 0x404040aa };
+                rect!(x = card_x - 10, y = 104, w = 10, h = 3, color = path_color);
+            }
+
+            let border = if selected { 0xffd700ff } else { 0x1a472aff };
+            rect!(x = card_x - 2, y = 48, w = 106, h = 124, color = border);
+            rect!(x = card_x, y = 50, w = 102, h = 120, color = if unlocked { 0x1a472aff } else { 0x202020ff });
+            text!("LEVEL {}", level; x = card_x + 8, y = 56, color = 0xffffffff);
+
+            if !unlocked {
+                // Padlock
+                rect!(x = card_x + 43, y = 84, w = 16, h = 14, color = 0x808080ff);
+                rect!(x = card_x + 46, y = 76, w = 10, h = 10, color = 0x808080ff);
+                rect!(x = card_x + 49, y = 79, w = 4, h = 7, color = 0x202020ff);
+                text!("CLEAR LEVEL {}", level - 1; x = card_x + 12, y = 110, color = 0x808080ff, font = "small");
+                continue;
+            }
+
+            text!(name, x = card_x + 8, y = 70, color = 0xbfe6ffff, font = "small");
+            for star in 0..3 {
+                let color = if star < stars { 0xffd700ff } else { 0x404040ff };
+                Self::draw_star(card_x + 20 + star as i32 * 22, 86, 14, color);
+            }
+            if stars == 0 {
+                text!("NOT CLEARED", x = card_x + 8, y = 112, color = 0x808080ff, font = "small");
+            } else {
+                text!("BEST {}", best_score; x = card_x + 8, y = 112, color = 0xffffffff, font = "small");
+                text!("KIDS {}", best_kids; x = card_x + 8, y = 124, color = 0x00ffffff, font = "small");
             }
         }
+
+        text!("LEFT/RIGHT: CHOOSE   ENTER: PLAY   ESC: BACK", x = 62, y = 200, color = 0xffffffaa, font = "small");
     }
 
-    fn draw_walls(&self) {
-        // DEBUG: Draw wall collision zones as semi-transparent rectangles
-        for (i, (wx, wy, ww, wh)) in self.walls.iter().enumerate() {
-            // Check if wall is valid
-            if *ww > 0.0 && *wh > 0.0 {
-                // Gate logic: different for each level
-                if self.level == 3 && !self.boss_active {
-                    // Level 3 (not in boss fight): Gate #1 (wall[0]) disappears with 1 key, Gate #2 (wall[1]) with 2 keys
-                    if (i == 0 && self.keys_collected >= 1) || (i == 1 && self.keys_collected >= 2) {
-                        continue;
-                    }
-                } else if self.level != 3 {
-                    // Other levels: Gate (wall #3) disappears once 3 keys are collected
-                    if i == 2 && self.keys_collected >= 3 {
-                        continue;
-                    }
+    // ------------------------------------------------------------------------
+    // Menus
+    // ------------------------------------------------------------------------
+
+    fn menu_items(menu: u8) -> &'static [(&'static str, u8)] {
+        match menu {
+            MENU_TITLE => &TITLE_MENU,
+            MENU_PAUSE => &PAUSE_MENU,
+            MENU_SETTINGS => &SETTINGS_MENU,
+            _ => &INFO_MENU,
+        }
+    }
+
+    fn menu_item_enabled(&self, action: u8) -> bool {
+        match action {
+            ACTION_CONTINUE => self.continue_level() > 1,
+            _ => true,
+        }
+    }
+
+    fn open_menu(&mut self, menu: u8) {
+        let cursor = Self::menu_items(menu).iter()
+            .position(|&(_, action)| self.menu_item_enabled(action))
+            .unwrap_or(0);
+        self.menu_stack.push((menu, cursor as u8));
+    }
+
+    fn update_menu(&mut self) {
+        let Some(&(menu, cursor)) = self.menu_stack.last() else { return };
+        let items = Self::menu_items(menu);
+        let kb = keyboard::get();
+
+        let step = if kb.arrow_down().just_pressed() {
+            1
+        } else if kb.arrow_up().just_pressed() {
+            items.len() - 1
+        } else {
+            0
+        };
+        if step > 0 {
+            // Walk in the pressed direction until an enabled item comes up
+            let mut next = cursor as usize;
+            for _ in 0..items.len() {
+                next = (next + step) % items.len();
+                if self.menu_item_enabled(items[next].1) {
+                    break;
                 }
-                // During boss fight in level 3, walls[0] and walls[1] are corner walls and should NOT disappear
+            }
+            if let Some(top) = self.menu_stack.last_mut() {
+                top.1 = next as u8;
+            }
+            return;
+        }
 
-                let screen_x = (*wx - self.camera_x) as i32;
-                let screen_y = *wy as i32;
-                let width = *ww as u32;
-                let height = *wh as u32;
+        let action = items[cursor as usize].1;
+        if kb.arrow_left().just_pressed() {
+            self.change_setting(action, false, false);
+        } else if kb.arrow_right().just_pressed() {
+            self.change_setting(action, true, false);
+        } else if kb.enter().just_pressed() || kb.key_x().just_pressed() {
+            if self.menu_item_enabled(action) {
+                self.menu_confirm(action);
+            }
+        } else if kb.escape().just_pressed() || kb.backspace().just_pressed() {
+            self.menu_back();
+        }
+    }
 
-                if i == 2 {
-                    // Wall #3 - draw the gate sprite instead of debug rectangle
-                    sprite!("gatelevel", x = screen_x, y = screen_y);
+    // Closes the focused menu. The title is the bottom of the stack and stays open
+    fn menu_back(&mut self) {
+        if self.menu_stack.last().is_some_and(|&(menu, _)| menu != MENU_TITLE) {
+            self.menu_stack.pop();
+        }
+    }
 
-                    // Draw "GATE OPEN!" message when gate disappears
-                    if self.keys_collected >= 3 {
-                        let msg_x = screen_x + 10;
-                        let msg_y = screen_y - 15;
-                        text!("GATE OPEN!", x = msg_x, y = msg_y, color = 0x00ff00ff);
-                    }
-                } else {
-                    // // Yellow/orange color for walls with transparency
-                    //let color = 0xffaa0088;
+    fn menu_confirm(&mut self, action: u8) {
+        match action {
+            ACTION_START => {
+                // Controls page first, Enter there starts the run
+                self.in_menu = false;
+                self.show_controls = true;
+            },
+            ACTION_CONTINUE => self.start_run(self.continue_level()),
+            ACTION_LEVEL_SELECT => {
+                self.in_menu = false;
+                self.show_level_select = true;
+                self.level_select_cursor = 0;
+            },
+            ACTION_SETTINGS => self.open_menu(MENU_SETTINGS),
+            ACTION_HIGH_SCORES => self.open_menu(MENU_HIGH_SCORES),
+            ACTION_CREDITS => self.open_menu(MENU_CREDITS),
+            ACTION_CONTROLS => self.open_menu(MENU_CONTROLS),
+            ACTION_QUIT => {
+                self.record_run_score();
+                self.menu_stack = vec![(MENU_TITLE, 0)];
+                self.in_menu = true;
+                log!("Quit to title");
+            },
+            ACTION_RESUME | ACTION_BACK => self.menu_back(),
+            _ => self.change_setting(action, true, true),
+        }
+    }
 
-                    // // Draw wall as a filled rectangle
-                    //rect!(x = screen_x, y = screen_y, w = width, h = height, color = color);
+    // Value shown at the right end of a settings item
+    fn menu_item_value(&self, action: u8) -> Option<String> {
+        let settings = &self.save.settings;
+        let on_off = |on: bool| if on { "ON".to_string() } else { "OFF".to_string() };
+        match action {
+            ACTION_MASTER_VOLUME => Some(format!("{}%", settings.master_volume as u32 * 100 / VOLUME_STEPS as u32)),
+            ACTION_MUSIC_VOLUME => Some(format!("{}%", settings.music_volume as u32 * 100 / VOLUME_STEPS as u32)),
+            ACTION_SFX_VOLUME => Some(format!("{}%", settings.sfx_volume as u32 * 100 / VOLUME_STEPS as u32)),
+            ACTION_REDUCED_MOTION => Some(on_off(settings.reduced_motion)),
+            ACTION_HUD_LAYOUT => Some(HUD_LAYOUT_NAMES[settings.hud_layout as usize].to_string()),
+            _ => None,
+        }
+    }
+
+    // ------------------------------------------------------------------------
+    // Settings
+    // ------------------------------------------------------------------------
+
+    // Steps a settings item up or down. Confirm wraps around, Left/Right stop at the ends.
+    // Anything that isn't a setting is ignored.
+    fn change_setting(&mut self, action: u8, up: bool, wrap: bool) {
+        let step_volume = |volume: u8| match (up, wrap) {
+            (true, true) if volume >= VOLUME_STEPS => 0,
+            (true, _) => (volume + 1).min(VOLUME_STEPS),
+            (false, _) => volume.saturating_sub(1),
+        };
+        let layouts = HUD_LAYOUT_NAMES.len() as u8;
+        let settings = &mut self.save.settings;
+        match action {
+            ACTION_MASTER_VOLUME => settings.master_volume = step_volume(settings.master_volume),
+            ACTION_MUSIC_VOLUME => settings.music_volume = step_volume(settings.music_volume),
+            ACTION_SFX_VOLUME => settings.sfx_volume = step_volume(settings.sfx_volume),
+            ACTION_REDUCED_MOTION => settings.reduced_motion = !settings.reduced_motion,
+            ACTION_HUD_LAYOUT => {
+                let offset = if up { 1 } else { layouts - 1 };
+                settings.hud_layout = (settings.hud_layout + offset) % layouts;
+            },
+            _ => return,
+        }
+        self.apply_audio_settings();
+        self.write_save();
+    }
+
+    fn music_volume(&self) -> f32 {
+        let settings = &self.save.settings;
+        (settings.master_volume as f32 / VOLUME_STEPS as f32) * (settings.music_volume as f32 / VOLUME_STEPS as f32)
+    }
+
+    fn sfx_volume(&self) -> f32 {
+        let settings = &self.save.settings;
+        (settings.master_volume as f32 / VOLUME_STEPS as f32) * (settings.sfx_volume as f32 / VOLUME_STEPS as f32)
+    }
+
+    fn apply_audio_settings(&mut self) {
+        let (music, sfx) = (self.music_volume(), self.sfx_volume());
+        self.sound.set_volumes(music, sfx);
+    }
+
+    // Music for what's on screen. None after a stinger, until the next screen picks a track
+    fn music_cue(&self) -> Option<u8> {
+        if self.in_menu || self.show_controls || self.show_level_select || self.show_results || self.show_workshop {
+            Some(CUE_MUSIC_MENU)
+        } else if self.show_game_over || self.show_time_up || self.show_victory || self.level_complete {
+            None
+        } else if self.boss_active {
+            Some(CUE_MUSIC_BOSS)
+        } else {
+            LEVEL_MUSIC.get(self.level as usize - 1).copied()
+        }
+    }
+
+    fn reduced_motion(&self) -> bool {
+        self.save.settings.reduced_motion
+    }
+
+    // Draws the focused menu: its page (heading, backdrop, info text) and then its items
+    fn draw_menu(&self) {
+        let Some(&(menu, cursor)) = self.menu_stack.last() else { return };
+        let (heading, x, y, width) = MENU_LAYOUT[menu as usize];
 
-                    // // Draw wall number in center
-                    //let label_x = screen_x + (width / 2) as i32 - 8;
-                    //let label_y = screen_y + (height / 2) as i32 - 4;
-                    //text!("W{}", i + 1; x = label_x, y = label_y, color = 0xffffffff);
+        match menu {
+            MENU_TITLE => {},
+            MENU_CONTROLS => sprite!("controls", x = 0, y = 0),
+            _ => {
+                rect!(x = 0, y = 0, w = 360, h = 240, color = 0x000000cc);
+                text!(heading, x = 180 - heading.len() as i32 * 4, y = 20, color = 0xffd700ff);
+            },
+        }
+        match menu {
+            MENU_HIGH_SCORES => self.draw_high_scores(),
+            MENU_CREDITS => {
+                for (i, line) in CREDITS_LINES.iter().enumerate() {
+                    text!(line, x = 180 - line.len() as i32 * 5 / 2, y = 56 + i as i32 * 14, color = 0xffffffff, font = "small");
                 }
-            }
+            },
+            _ => {},
         }
 
-        // DEBUG: Draw completion trigger zone (green semi-transparent box)
-        // Uncomment to visualize the trigger zone
-        // if self.completion_trigger.2 > 0.0 && self.completion_trigger.3 > 0.0 {
-        //     let trigger_screen_x = (self.completion_trigger.0 - self.camera_x) as i32;
-        //     let trigger_y = self.completion_trigger.1 as i32;
-        //     let trigger_w = self.completion_trigger.2 as u32;
-        //     let trigger_h = self.completion_trigger.3 as u32;
-        //     rect!(x = trigger_screen_x, y = trigger_y, w = trigger_w, h = trigger_h, color = 0x00ff0044);
-        //     text!("EXIT", x = trigger_screen_x + 5, y = trigger_y + 25, color = 0x00ff00ff);
-        // }
+        let items = Self::menu_items(menu);
+        rect!(x = x - 8, y = y - 6, w = width + 16, h = (items.len() as i32 * MENU_ROW_HEIGHT + 8) as u32, color = 0x000000aa);
+        for (i, &(label, action)) in items.iter().enumerate() {
+            let row_y = y + i as i32 * MENU_ROW_HEIGHT;
+            let color = if !self.menu_item_enabled(action) {
+                0x606060ff
+            } else if i == cursor as usize {
+                rect!(x = x - 4, y = row_y - 3, w = width + 8, h = (MENU_ROW_HEIGHT - 2) as u32, color = 0xffd70044);
+                text!(">", x = x, y = row_y, color = 0xffd700ff, font = "small");
+                0xffd700ff
+            } else {
+                0xffffffff
+            };
+            text!(label, x = x + 10, y = row_y, color = color, font = "small");
+            if let Some(value) = self.menu_item_value(action) {
+                let value_x = x + width as i32 - value.len() as i32 * 5;
+                text!(&value, x = value_x, y = row_y, color = color, font = "small");
+            }
+        }
+        if menu == MENU_SETTINGS {
+            text!("LEFT/RIGHT: CHANGE   ESC: BACK", x = 105, y = 180, color = 0xffffffaa, font = "small");
+        }
     }
 
-    fn draw_enemy_ranges(&self) {
-        // DEBUG: Draw enemy patrol ranges and attack zones
-        for (i, enemy) in self.enemies.iter().enumerate() {
-            // Skip if enemy type is 0 (not initialized)
-            if enemy.0 == 0 || !enemy.8 {
-                continue;
+    fn draw_high_scores(&self) {
+        text!("BEST RUNS", x = 40, y = 48, color = 0x00ffffff, font = "small");
+        for (i, score) in self.save.high_scores.iter().enumerate() {
+            let row_y = 64 + i as i32 * 14;
+            text!("{}.", i + 1; x = 40, y = row_y, color = 0xffffffff, font = "small");
+            if *score > 0 {
+                text!("{}", score; x = 56, y = row_y, color = 0xffffffff, font = "small");
+            } else {
+                text!("---", x = 56, y = row_y, color = 0x606060ff, font = "small");
             }
+        }
 
-            let enemy_type = enemy.0;
-            let patrol_start = enemy.7 as f32;
-            let patrol_range = 100.0;  // From patrol AI logic
+        text!("BEST PER LEVEL", x = 180, y = 48, color = 0x00ffffff, font = "small");
+        for (i, &LevelBest { stars, score, kids }) in self.save.best.iter().enumerate() {
+            let row_y = 64 + i as i32 * 28;
+            text!("LEVEL {}", i + 1; x = 180, y = row_y, color = 0xffffffff, font = "small");
+            for star in 0..3 {
+                let color = if star < stars { 0xffd700ff } else { 0x404040ff };
+                Self::draw_star(230 + star as i32 * 12, row_y - 2, 9, color);
+            }
+            if stars > 0 {
+                text!("{}  KIDS {}", score, kids; x = 180, y = row_y + 11, color = 0xbfe6ffff, font = "small");
+            }
+        }
+    }
 
-            // Draw patrol range (horizontal line)
-            let range_start = (patrol_start - self.camera_x) as i32;
-            let range_end = ((patrol_start + patrol_range) - self.camera_x) as i32;
-            let enemy_y = enemy.2 as i32;
+    fn open_workshop(&mut self) {
+        self.show_workshop = true;
+        self.workshop_cursor = 0;
+        // Tokens earned this level are banked even if the player quits here
+        self.write_save();
+    }
 
-            // Patrol range - cyan semi-transparent
-            let patrol_color = 0x00ffffff44;
-            rect!(x = range_start, y = enemy_y - 12, w = (range_end - range_start) as u32, h = 2, color = patrol_color);
+    // Price of the next purchase, None once it's maxed out. The blaster is also
+    // maxed while this run already has the top weapon tier from pickups
+    fn upgrade_cost(&self, item: u8) -> Option<u32> {
+        let shop_item = &SHOP_ITEMS[item as usize];
+        let bought = self.save.upgrades[item as usize];
+        if item == SHOP_WEAPON && self.weapon_tier as usize + 1 >= WEAPON_TIERS.len() {
+            return None;
+        }
+        (bought < shop_item.max).then_some(shop_item.base_cost + shop_item.cost_step * bought as u32)
+    }
 
-            // Draw patrol endpoints (small markers)
-            rect!(x = range_start, y = enemy_y - 16, w = 2, h = 8, color = 0x00ffffff88);
-            rect!(x = range_end, y = enemy_y - 16, w = 2, h = 8, color = 0x00ffffff88);
+    fn update_workshop(&mut self) {
+        let kb = keyboard::get();
+        let rows = SHOP_ITEMS.len() as u8;
+        if kb.arrow_up().just_pressed() {
+            self.workshop_cursor = (self.workshop_cursor + rows - 1) % rows;
+        }
+        if kb.arrow_down().just_pressed() {
+            self.workshop_cursor = (self.workshop_cursor + 1) % rows;
+        }
+        if kb.key_x().just_pressed() || kb.key_z().just_pressed() {
+            self.buy_upgrade(self.workshop_cursor);
+        }
+        if kb.enter().just_pressed() {
+            self.enter_level(self.level + 1);
+        }
+    }
 
-            // For kickmouse (type 2), draw attack range
-            if enemy_type == 2 {
-                let enemy_screen_x = (enemy.1 - self.camera_x) as i32;
+    fn buy_upgrade(&mut self, item: u8) {
+        let Some(cost) = self.upgrade_cost(item) else { return };
+        if self.save.tokens < cost {
+            return;
+        }
+        self.save.tokens -= cost;
+        self.save.upgrades[item as usize] += 1;
 
-                // Attack range: 60px horizontally, 20px vertically (changed from 10px to 60px based on code)
-                let attack_range_h = 60;  // Actually 10 in the code, showing both
-                let attack_range_v = 20;
+        // Takes effect for the rest of this run too, not just the next one
+        match item {
+            SHOP_MAX_HP => {
+                self.player_max_hp += SHOP_HP_STEP;
+                self.player_hp = self.player_max_hp;
+            },
+            SHOP_BOMB_SACK => self.gift_bomb_capacity += 1,
+            SHOP_WEAPON => self.weapon_tier += 1,  // upgrade_cost refuses at the top tier
+            SHOP_EXTRA_LIFE => self.lives += 1,
+            _ => {}
+        }
+        self.sound.play(CUE_PICKUP);
+        self.write_save();
+    }
 
-                // Draw actual attack trigger zone (small 10px range) - red
-                rect!(
-                    x = enemy_screen_x - 10,
-                    y = enemy_y - attack_range_v,
-                    w = 20,
-                    h = (attack_range_v * 2) as u32,
-                    color = 0xff000044
-                );
+    fn draw_workshop(&self) {
+        rect!(x = 0, y = 0, w = 360, h = 240, color = 0x0d2818ff);
+        rect!(x = 0, y = 0, w = 360, h = 3, color = 0xcc2222ff);
+        rect!(x = 0, y = 237, w = 360, h = 3, color = 0xcc2222ff);
+        text!("GIFT WORKSHOP", x = 128, y = 8, color = 0xffd700ff);
+        text!("LEVEL {} COMPLETE", self.level; x = 134, y = 20, color = 0x00ff00ff, font = "small");
+
+        // ---- Run summary ----
+        let stats = &self.level_stats;
+        let seconds = self.level_timer / 60;
+        let level_score = self.score.saturating_sub(stats.score_at_start);
+        let other_score = level_score.saturating_sub(stats.kill_score + stats.door_score);
+        let hidden_found = self.hidden_kids.iter().filter(|kid| kid.1).count();
+        rect!(x = 12, y = 34, w = 336, h = 62, color = 0x00000066);
+        text!("KIDS RESCUED  {}/{}  +{} HIDDEN", self.kids_collected, self.total_kids_in_level, hidden_found; x = 20, y = 40, color = 0x00ffffff, font = "small");
+        text!("TIME          {}:{:02}", seconds / 60, seconds % 60; x = 20, y = 50, color = 0xffffffff, font = "small");
+        text!("ENEMIES       {}  ({} pts)", stats.enemies_defeated, stats.kill_score; x = 20, y = 60, color = 0xff6b6bff, font = "small");
+        text!("DOORS         {} pts", stats.door_score; x = 20, y = 70, color = 0xffaa00ff, font = "small");
+        text!("KIDS & BONUS  {} pts", other_score; x = 20, y = 80, color = 0x00ffffff, font = "small");
+        text!("LEVEL SCORE {}", level_score; x = 220, y = 50, color = 0xffffffff, font = "small");
+        text!("TOTAL {}", self.score; x = 220, y = 60, color = 0xffffffff, font = "small");
+        text!("TOKENS +{}", stats.tokens_earned; x = 220, y = 70, color = 0xffd700ff, font = "small");
+
+        // ---- Upgrades ----
+        text!("TOY TOKENS: {}", self.save.tokens; x = 20, y = 104, color = 0xffd700ff);
+        for (i, ShopItem { name, effect, max, .. }) in SHOP_ITEMS.iter().enumerate() {
+            let row_y = 118 + i as i32 * 22;
+            let selected = i as u8 == self.workshop_cursor;
+            let bg = if selected { 0xcc2222dd } else { 0x1a472aaa };
+            rect!(x = 12, y = row_y, w = 336, h = 20, color = bg);
+            if selected {
+                text!(">", x = 16, y = row_y + 6, color = 0xffffffff, font = "small");
+            }
+            text!(name, x = 26, y = row_y + 3, color = 0xffffffff, font = "small");
+            text!(effect, x = 26, y = row_y + 11, color = 0xbfe6ffff, font = "small");
+
+            // Owned pips, then the price (grey when it can't be afforded)
+            let bought = self.save.upgrades[i];
+            for pip in 0..*max {
+                let color = if pip < bought { 0xffd700ff } else { 0x404040ff };
+                rect!(x = 200 + pip as i32 * 8, y = row_y + 7, w = 6, h = 6, color = color);
+            }
+            match self.upgrade_cost(i as u8) {
+                Some(cost) => {
+                    let color = if self.save.tokens >= cost { 0xffd700ff } else { 0x808080ff };
+                    text!("{} TOKENS", cost; x = 270, y = row_y + 7, color = color, font = "small");
+                },
+                None => text!("MAXED", x = 270, y = row_y + 7, color = 0x00ff00ff, font = "small"),
+            }
+        }
 
-                // Draw larger detection zone (60px) - orange
-                rect!(
-                    x = enemy_screen_x - attack_range_h,
-                    y = enemy_y - attack_range_v,
-                    w = (attack_range_h * 2) as u32,
-                    h = (attack_range_v * 2) as u32,
-                    color = 0xff880022
-                );
+        text!("UP/DOWN: SELECT   X: BUY   ENTER: LEVEL {}", self.level + 1; x = 60, y = 212, color = 0xffffffaa, font = "small");
+    }
 
-                // Label
-                text!("K", x = enemy_screen_x - 3, y = enemy_y - 25, color = 0xff0000ff);
-            } else {
-                // Label for regular enemy
-                let enemy_screen_x = (enemy.1 - self.camera_x) as i32;
-                text!("E", x = enemy_screen_x - 3, y = enemy_y - 25, color = 0x00ffffff);
-            }
+    // ------------------------------------------------------------------------
+    // Consumables
+    // ------------------------------------------------------------------------
 
-            // Draw enemy number
-            let enemy_screen_x = (enemy.1 - self.camera_x) as i32;
-            text!("{}", i + 1; x = enemy_screen_x + 10, y = enemy_y - 25, color = 0xffffffff);
+    fn load_level_items(&mut self, level_num: u8) {
+        // Loot left on the floor doesn't follow Santa to the next level
+        for item in self.drop_items.iter_mut() {
+            item.active = false;
+        }
+        for placed in LEVEL_ITEMS.iter().filter(|placed| placed.level == level_num) {
+            if let Some(slot) = self.drop_items.iter_mut().find(|item| !item.active) {
+                *slot = DropItem::new(placed.x, placed.y, DROP_NO_DESPAWN, placed.kind);
+            }
         }
+        self.hidden_kids = [(false, false); 3];
     }
 
-    fn draw_enemies(&self) {
-        for enemy in self.enemies.iter() {
-            // Skip if enemy type is 0 (not initialized)
-            if enemy.0 == 0 {
-                continue;
-            }
-            
-            let screen_x = (enemy.1 - self.camera_x) as i32;
-            let screen_y = enemy.2 as i32;
-            let respawn_timer = enemy.11;
-            
-            // If enemy is respawning and in cloud animation phase (last 120 frames)
-            if respawn_timer > 0 && respawn_timer <= 120 {
-                // Calculate cloud animation frame on-the-fly (8 frames total)
-                // Change frame every 15 ticks (120 / 8 = 15)
-                let frames_into_cloud = 120 - respawn_timer;
-                let cloud_frame = ((frames_into_cloud / 15) % 8) as u8;
-                
-                // Draw cloud animation during respawn
-                let cloud_sprite = match cloud_frame {
-                    0 => "cloud/cloud1",
-                    1 => "cloud/cloud2",
-                    2 => "cloud/cloud3",
-                    3 => "cloud/cloud4",
-                    4 => "cloud/cloud5",
-                    5 => "cloud/cloud6",
-                    6 => "cloud/cloud7",
-                    _ => "cloud/cloud8",
-                };
-                sprite!(cloud_sprite, x = screen_x - 16, y = screen_y - 16);
-                continue;
-            }
-            
-            // If enemy is dead but not yet in cloud phase, don't draw anything
-            if respawn_timer > 120 {
-                continue;
+    // Select the next item Santa actually carries
+    fn cycle_item(&mut self) {
+        let count = ITEMS.len() as u8;
+        for step in 1..=count {
+            let next = (self.selected_item + step) % count;
+            if self.items[next as usize] > 0 {
+                self.selected_item = next;
+                return;
             }
-            
-            // Draw normal enemy if active and alive
-            if enemy.8 {
-                let enemy_type = enemy.0;
-                let anim_frame = enemy.6;
-                let direction = enemy.5;
-                let is_attacking = enemy.10;
+        }
+    }
 
-                // Determine sprite name based on enemy type and animation frame
-                let sprite_name = match enemy_type {
-                    1 => {
-                        // Enemy1: 8 walk frames (mouse1.png - mouse8.png)
-                        let frame = (anim_frame % 8) + 1;
-                        match frame {
-                            1 => "enemy/enemy1/mouse1",
-                            2 => "enemy/enemy1/mouse2",
-                            3 => "enemy/enemy1/mouse3",
-                            4 => "enemy/enemy1/mouse4",
-                            5 => "enemy/enemy1/mouse5",
-                            6 => "enemy/enemy1/mouse6",
-                            7 => "enemy/enemy1/mouse7",
-                            8 => "enemy/enemy1/mouse8",
-                            _ => "enemy/enemy1/mouse1",
-                        }
-                    },
-                    2 => {
-                        // Kickmouse: 8 walk frames (1.png - 8.png) or 3 attack frames
-                        if is_attacking {
-                            // Attack animation: attack/1.png - attack/3.png
-                            let frame = anim_frame + 1;  // 0-2 becomes 1-3
-                            match frame {
-                                1 => "enemy/kickmouse/attack/1",
-                                2 => "enemy/kickmouse/attack/2",
-                                3 => "enemy/kickmouse/attack/3",
-                                _ => "enemy/kickmouse/attack/1",
-                            }
-                        } else {
-                            // Walk animation
-                            let frame = (anim_frame % 8) + 1;
-                            match frame {
-                                1 => "enemy/kickmouse/1",
-                                2 => "enemy/kickmouse/2",
-                                3 => "enemy/kickmouse/3",
-                                4 => "enemy/kickmouse/4",
-                                5 => "enemy/kickmouse/5",
-                                6 => "enemy/kickmouse/6",
-                                7 => "enemy/kickmouse/7",
-                                8 => "enemy/kickmouse/8",
-                                _ => "enemy/kickmouse/1",
-                            }
-                        }
-                    },
-                    3 => {
-                        // Penguin: 8 walk frames (penguin1.png - penguin8.png) or 3 attack frames
-                        if is_attacking {
-                            // Attack animation: attack/penguinattack1.png - penguinattack3.png
-                            let frame = anim_frame + 1;  // 0-2 becomes 1-3
-                            match frame {
-                                1 => "enemy/penguin/attack/penguinattack1",
-                                2 => "enemy/penguin/attack/penguinattack2",
-                                3 => "enemy/penguin/attack/penguinattack3",
-                                _ => "enemy/penguin/attack/penguinattack1",
-                            }
-                        } else {
-                            // Walk animation
-                            let frame = (anim_frame % 8) + 1;
-                            match frame {
-                                1 => "enemy/penguin/penguin1",
-                                2 => "enemy/penguin/penguin2",
-                                3 => "enemy/penguin/penguin3",
-                                4 => "enemy/penguin/penguin4",
-                                5 => "enemy/penguin/penguin5",
-                                6 => "enemy/penguin/penguin6",
-                                7 => "enemy/penguin/penguin7",
-                                8 => "enemy/penguin/penguin8",
-                                _ => "enemy/penguin/penguin1",
-                            }
-                        }
-                    },
-                    _ => "enemy/enemy1/mouse1",
-                };
+    fn use_item(&mut self) {
+        let item = self.selected_item as usize;
+        if self.items[item] == 0 {
+            return;
+        }
 
-                // Draw enemy sprite with flipping based on direction
-                // Direction: 0 = moving left, 1 = moving right
-                // Sprites naturally face right, so flip when moving left
-                sprite!(sprite_name, x = screen_x - 16, y = screen_y - 16, flip_x = direction == 0);
+        // Items that would do nothing right now stay in the bag
+        let used = match self.selected_item {
+            ITEM_COCOA => {
+                let hurt = self.player_hp < self.player_max_hp;
+                if hurt {
+                    self.player_hp = (self.player_hp + COCOA_HEAL).min(self.player_max_hp);
+                }
+                hurt
+            },
+            ITEM_SHIELD => {
+                self.player_shield_timer = SHIELD_FRAMES;
+                true
+            },
+            ITEM_SNOW_GLOBE => {
+                self.enemy_freeze_timer = FREEZE_FRAMES;
+                true
+            },
+            ITEM_BELL => self.ring_bell(),
+            _ => false,
+        };
+
+        if used {
+            self.items[item] -= 1;
+            self.item_message = (self.selected_item, ITEM_MSG_USED, ITEM_MESSAGE_FRAMES);
+            self.sound.play(CUE_PICKUP);
+            if self.items[item] == 0 {
+                self.cycle_item();
             }
+        } else {
+            self.item_message = (self.selected_item, ITEM_MSG_NOT_NOW, ITEM_MESSAGE_FRAMES);
         }
     }
 
-    fn draw_snowballs(&self) {
-        // Draw penguin snowball projectiles
-        for snowball in self.snowballs.iter() {
-            if snowball.0 {
-                let screen_x = (snowball.1 - self.camera_x) as i32;
-                let screen_y = snowball.2 as i32;
-                // Draw snowball sprite (sprite is centered, adjust for 16x16 sprite)
-                sprite!("enemy/penguin/snowball", x = screen_x - 8, y = screen_y - 8);
+    // Reveal this level's hidden kids. False when there's none left to find
+    fn ring_bell(&mut self) -> bool {
+        let mut revealed = false;
+        for (i, (level, _, _)) in LEVEL_HIDDEN_KIDS.iter().enumerate() {
+            if *level == self.level && !self.hidden_kids[i].0 {
+                self.hidden_kids[i].0 = true;
+                revealed = true;
             }
         }
+        revealed
     }
 
-    fn draw_projectiles(&self) {
-        for proj in self.projectiles.iter() {
-            if proj.0 {
-                let screen_x = (proj.1 - self.camera_x) as i32;
-                let screen_y = proj.2 as i32;
-                let color = if proj.4 { 0xffffffff } else { 0xff0000ff };
-                circ!(x = screen_x, y = screen_y, d = 8, color = color);
+    fn update_consumables(&mut self) {
+        if self.item_message.2 > 0 {
+            self.item_message.2 -= 1;
+        }
+        if self.enemy_freeze_timer > 0 {
+            self.enemy_freeze_timer -= 1;
+        }
+        if self.player_shield_timer > 0 {
+            self.player_shield_timer -= 1;
+        }
+    }
+
+    // Hit i-frames or the candy-cane shield, every damage source checks this
+    fn player_protected(&self) -> bool {
+        self.player_invuln_timer > 0 || self.player_shield_timer > 0
+    }
+
+    fn check_hidden_kid_collection(&mut self) {
+        for (i, (level, x, y)) in LEVEL_HIDDEN_KIDS.iter().enumerate() {
+            let (revealed, rescued) = self.hidden_kids[i];
+            if *level != self.level || !revealed || rescued {
+                continue;
+            }
+            if (self.player_x - x).abs() < 20.0 && (self.player_y - y).abs() < 20.0 {
+                self.hidden_kids[i].1 = true;
+                self.score += HIDDEN_KID_SCORE;
+                self.kid_pickup_flash = 30;
+                self.sound.play(CUE_KID_RESCUED);
+                log!("Hidden kid {} rescued!", i);
             }
         }
     }
 
-    fn update_keys_animation(&mut self) {
-        for i in 0..self.keys.len() {
-            if self.keys[i].2 {
-                // Advance animation timer; cycle frames 1-6 at 8 ticks per frame
-                self.keys[i].4 += 1;
-                if self.keys[i].4 >= 8 {
-                    self.keys[i].4 = 0;
-                    self.keys[i].3 = (self.keys[i].3 + 1) % 6;
-                }
+    fn draw_hidden_kids(&self) {
+        for (i, (level, x, y)) in LEVEL_HIDDEN_KIDS.iter().enumerate() {
+            let (revealed, rescued) = self.hidden_kids[i];
+            if *level != self.level || !revealed || rescued {
+                continue;
             }
-        }
-
-        if self.key_pickup_flash > 0 {
-            self.key_pickup_flash -= 1;
+            let screen_x = (x - self.camera_x) as i32;
+            let screen_y = *y as i32;
+            let sprite_name = match (self.frame / 10) % 3 {
+                0 => "kid/1",
+                1 => "kid/2",
+                _ => "kid/3",
+            };
+            sprite!(sprite_name, x = screen_x - 8, y = screen_y);
+            // Bell sparkle so they stand out from the door kids
+            let twinkle = (self.frame / 6 % 4) as i32;
+            rect!(x = screen_x - 6 + twinkle * 4, y = screen_y - 4, w = 2, h = 2, color = 0xffd700ff);
         }
     }
 
-    fn draw_keys(&self) {
-        for key in self.keys.iter() {
-            if key.2 {
-                let screen_x = (key.0 - self.camera_x) as i32;
-                let screen_y = key.1 as i32;
-                let frame = (key.3 % 6) + 1;
-                let sprite_name = match frame {
-                    1 => "key/1",
-                    2 => "key/2",
-                    3 => "key/3",
-                    4 => "key/4",
-                    5 => "key/5",
-                    _ => "key/6",
-                };
-                sprite!(sprite_name, x = screen_x-10, y = screen_y+3);
-            }
+    fn draw_shield(&self) {
+        if self.player_shield_timer == 0 {
+            return;
+        }
+        // Flickers out over the last second
+        if self.player_shield_timer < 60 && self.frame % 8 < 4 {
+            return;
+        }
+        let screen_x = (self.player_x - self.camera_x) as i32;
+        let screen_y = self.player_y as i32;
+        circ!(x = screen_x - 18, y = screen_y - 20, d = 36, color = 0xff406033);
+        // Candy stripes orbiting Santa
+        for stripe in 0..4 {
+            let angle = (self.frame as f32 / 10.0) + stripe as f32 * std::f32::consts::FRAC_PI_2;
+            let sx = screen_x + (angle.cos() * 16.0) as i32;
+            let sy = screen_y - 2 + (angle.sin() * 18.0) as i32;
+            let color = if stripe % 2 == 1 { 0xffffffff } else { 0xff4060ff };
+            rect!(x = sx - 1, y = sy - 1, w = 3, h = 3, color = color);
         }
     }
 
@@ -3663,9 +8323,9 @@ impl GameState {
             if dx < 20.0 && dy < 20.0 {
                 self.powerup1_collected = true;
                 self.powerup1_active = false;
-                self.has_firepower = true;  // Grant firepower ability
+                self.weapon_tier = (self.weapon_tier + 1).min(WEAPON_SPREAD);
                 self.score += 750;
-                log!("PowerUp1 collected! Firepower activated!");
+                log!("PowerUp1 collected! Weapon tier: {}", WEAPON_TIERS[self.weapon_tier as usize].name);
             }
         }
     }
@@ -3690,17 +8350,17 @@ impl GameState {
 
     fn check_keys(&mut self) {
         for key in self.keys.iter_mut() {
-            if key.2 {
-                let dx = (self.player_x - key.0).abs();
-                let dy = (self.player_y - key.1).abs();
+            if key.active {
+                let dx = (self.player_x - key.x).abs();
+                let dy = (self.player_y - key.y).abs();
                 if dx < 16.0 && dy < 16.0 {
-                    key.2 = false;
+                    key.active = false;
                     self.keys_collected = self.keys_collected.saturating_add(1);
+                    self.keys_held |= 1 << key.color;
+                    self.key_pickup_color = key.color;
                     self.score += 100;
                     self.key_pickup_flash = 30;
-                    audio::play("collection");
-                    // SFX hook (stub) – integrate Turbo audio when available
-                    log!("SFX: key_pickup");
+                    self.sound.play(CUE_PICKUP);
                 }
             }
         }
@@ -3731,6 +8391,7 @@ impl GameState {
     }
 
     fn check_kid_collection(&mut self) {
+        let mut ending = false;
         for (kid_idx, kid) in self.kids.iter_mut().enumerate() {
             // Format: (x, y, active, collected, anim_frame, anim_timer, spawned_from_door_idx)
             if kid.2 && !kid.3 {
@@ -3751,25 +8412,30 @@ impl GameState {
                         self.player_hp += 1;
                     }
 
-                    log!("SFX: kid_rescued - Kids rescued: {}/{}", self.kids_collected, self.total_kids_in_level);
-                    let kid_sfx = if self.frame % 2 == 0 { "meeting_kid" } else { "meeting_kid_2" };
-                    audio::play(kid_sfx);
+                    log!("Kid rescued! Kids rescued: {}/{}", self.kids_collected, self.total_kids_in_level);
+                    self.sound.play(CUE_KID_RESCUED);
                     
                     // Check for game victory: Level 3, door 0 kid
                     if self.level == 3 && door_idx == 0 {
-                        self.show_victory = true;
-                        self.game_won_timer = 0;
+                        ending = true;
                         log!("Game Complete! Victory!");
                     }
                 }
             }
         }
+
+        // Victory screen comes at the end of the ending cutscene
+        if ending {
+            // The last level ends on the victory screen, its result is still recorded
+            self.record_level_result();
+            self.start_cutscene(CUTSCENE_ENDING);
+        }
     }
 
     fn check_level_completion(&mut self) {
-        // Check if player has collected all required keys
-        let required_keys = if self.level == 3 { 2 } else { 3 };
-        if self.keys_collected < required_keys {
+        // Check if player holds every key the level's doors drop
+        let required = self.level_keys_mask();
+        if self.keys_held & required != required {
             return;
         }
         
@@ -3815,41 +8481,101 @@ impl GameState {
             // Player entered the completion zone!
             self.level_complete = true;
             self.level_transition_timer = 120; // 2 seconds transition
-            audio::play("completion");
+            self.sound.play(CUE_LEVEL_CLEAR);
             log!("Level Complete! Transitioning to next level...");
         }
     }
 
     fn draw_gift_bombs(&self) {
-        // Draw gift bomb items (to pick up)
-        for item in self.gift_bomb_items.iter() {
-            if item.2 {  // If active
-                let screen_x = (item.0 - self.camera_x) as i32;
-                let screen_y = item.1 as i32;
-                // Gift bomb sprite is 64x64 pixels
-                // Santa sprite is 32x32, rendered at (screen_x - 16, screen_y - 16)
-                // To align gift bomb with Santa's level:
-                // - Santa's feet are at screen_y + 16 (bottom of 32px sprite centered at screen_y)
-                // - Gift bomb should have its bottom at the same level
-                // - For 64px sprite: bottom = screen_y + 32, so top = screen_y - 32
-                // - But gift visual is at bottom of sprite, so we need to adjust
-                // Offset: center horizontally (-32), but align bottom with Santa's feet
-                sprite!("gift bomb/idle", x = screen_x - 32, y = screen_y - 48);
+        // Draw dropped items (to pick up)
+        for item in self.drop_items.iter() {
+            if item.active {  // If active
+                // Blink warning before the drop despawns
+                if item.despawn_timer <= DROP_BLINK_TIME && (item.despawn_timer / 6) % 2 == 0 {
+                    continue;
+                }
+
+                let screen_x = (item.x - self.camera_x) as i32;
+                let screen_y = item.y as i32;
+                match item.kind {
+                    DROP_GIFT_BOMB => {
+                        // Gift bomb sprite is 64x64 pixels
+                        // Santa sprite is 32x32, rendered at (screen_x - 16, screen_y - 16)
+                        // To align gift bomb with Santa's level:
+                        // - Santa's feet are at screen_y + 16 (bottom of 32px sprite centered at screen_y)
+                        // - Gift bomb should have its bottom at the same level
+                        // - For 64px sprite: bottom = screen_y + 32, so top = screen_y - 32
+                        // - But gift visual is at bottom of sprite, so we need to adjust
+                        // Offset: center horizontally (-32), but align bottom with Santa's feet
+                        sprite!("gift bomb/idle", x = screen_x - 32, y = screen_y - 48);
+                    },
+                    DROP_COIN => {
+                        // Gold coin resting on the floor (no sprite yet)
+                        circ!(x = screen_x - 5, y = screen_y + 6, d = 10, color = 0xb8860bff);
+                        circ!(x = screen_x - 3, y = screen_y + 8, d = 6, color = 0xffd700ff);
+                    },
+                    DROP_CANDY => {
+                        // Candy cane drawn with rectangles (red/white stripes)
+                        rect!(x = screen_x - 2, y = screen_y + 4, w = 3, h = 12, color = 0xffffffff);
+                        rect!(x = screen_x - 2, y = screen_y + 6, w = 3, h = 2, color = 0xff0000ff);
+                        rect!(x = screen_x - 2, y = screen_y + 11, w = 3, h = 2, color = 0xff0000ff);
+                        rect!(x = screen_x - 2, y = screen_y + 2, w = 7, h = 3, color = 0xff0000ff);
+                        rect!(x = screen_x + 3, y = screen_y + 4, w = 2, h = 3, color = 0xffffffff);
+                    },
+                    DROP_WALL_BOOTS => {
+                        // Spiked boots with a pulsing glow so they read as an upgrade
+                        let glow = if self.frame % 30 < 15 { 0xfff06088 } else { 0xfff06044 };
+                        circ!(x = screen_x - 9, y = screen_y - 1, d = 18, color = glow);
+                        rect!(x = screen_x - 5, y = screen_y + 2, w = 5, h = 8, color = 0x202020ff);
+                        rect!(x = screen_x - 5, y = screen_y + 10, w = 9, h = 3, color = 0x202020ff);
+                        rect!(x = screen_x - 5, y = screen_y + 2, w = 5, h = 2, color = 0xffffffff);
+                        rect!(x = screen_x - 5, y = screen_y + 13, w = 1, h = 1, color = 0xc0c8d0ff);
+                        rect!(x = screen_x - 1, y = screen_y + 13, w = 1, h = 1, color = 0xc0c8d0ff);
+                        rect!(x = screen_x + 3, y = screen_y + 13, w = 1, h = 1, color = 0xc0c8d0ff);
+                    },
+                    DROP_COCOA => {
+                        // Mug with a cream top and a wisp of steam
+                        rect!(x = screen_x - 4, y = screen_y + 6, w = 8, h = 9, color = 0xc87533ff);
+                        rect!(x = screen_x - 4, y = screen_y + 6, w = 8, h = 2, color = 0xfff4e0ff);
+                        rect!(x = screen_x + 4, y = screen_y + 8, w = 2, h = 4, color = 0xc87533ff);
+                        let steam = (self.frame / 10 % 2) as i32;
+                        rect!(x = screen_x - 1 + steam, y = screen_y + 1, w = 1, h = 3, color = 0xffffff88);
+                    },
+                    DROP_SHIELD => {
+                        // Striped ring
+                        circ!(x = screen_x - 7, y = screen_y + 1, d = 14, color = 0xff4060ff);
+                        circ!(x = screen_x - 4, y = screen_y + 4, d = 8, color = 0xffffffff);
+                        circ!(x = screen_x - 2, y = screen_y + 6, d = 4, color = 0xff4060ff);
+                    },
+                    DROP_SNOW_GLOBE => {
+                        circ!(x = screen_x - 6, y = screen_y + 1, d = 12, color = 0x9fe8ffcc);
+                        rect!(x = screen_x - 5, y = screen_y + 12, w = 10, h = 3, color = 0x8b5a2bff);
+                        let flake = (self.frame / 8 % 3) as i32;
+                        rect!(x = screen_x - 3 + flake * 2, y = screen_y + 4 + flake, w = 1, h = 1, color = 0xffffffff);
+                    },
+                    DROP_BELL => {
+                        rect!(x = screen_x - 3, y = screen_y + 3, w = 6, h = 8, color = 0xffd700ff);
+                        rect!(x = screen_x - 5, y = screen_y + 10, w = 10, h = 3, color = 0xffd700ff);
+                        rect!(x = screen_x - 1, y = screen_y + 13, w = 2, h = 2, color = 0xb8860bff);
+                        rect!(x = screen_x - 1, y = screen_y + 1, w = 2, h = 2, color = 0xcc2222ff);
+                    },
+                    _ => {}
+                }
             }
         }
 
         // Draw placed bombs (idle or exploding)
         for bomb in self.placed_bombs.iter() {
-            if bomb.2 {  // If active
-                let screen_x = (bomb.0 - self.camera_x) as i32;
-                let screen_y = bomb.1 as i32;
+            if bomb.active {  // If active
+                let screen_x = (bomb.x - self.camera_x) as i32;
+                let screen_y = bomb.y as i32;
 
                 // Map animation frames to sprites:
                 // Frame 0: idle
                 // Frames 1-4: sprites 2-5 (first warning cycle)
                 // Frames 5-8: sprites 2-5 (second warning cycle)
                 // Frames 9-11: sprites 6-8 (explosion)
-                let sprite_name = match bomb.3 {
+                let sprite_name = match bomb.anim_frame {
                     0 => "gift bomb/idle",
                     1 | 5 => "gift bomb/2",  // First & second cycle
                     2 | 6 => "gift bomb/3",
@@ -3893,15 +8619,81 @@ impl GameState {
         }
     }
 
+    fn draw_cutscene(&self) {
+        if self.cutscene_id == CUTSCENE_NONE { return; }
+
+        if self.cutscene_fade > 0 {
+            // Black, alpha in the low byte
+            rect!(x = 0, y = 0, w = 360, h = 240, color = self.cutscene_fade as u32);
+        }
+
+        if self.cutscene_age > CUTSCENE_SKIP_DELAY {
+            text!("ENTER: skip", x = 306, y = 20, color = 0xffffffaa, font = "small");
+        }
+
+        let script = cutscene_script(self.cutscene_id);
+        let Some(&CutsceneStep::Say(speaker, portrait, dialogue)) = script.get(self.cutscene_step as usize) else {
+            return;
+        };
+
+        // Dialogue box along the bottom of the screen
+        let box_y = 172;
+        rect!(x = 4, y = box_y, w = 352, h = 64, color = 0xffffffff);
+        rect!(x = 5, y = box_y + 1, w = 350, h = 62, color = 0x1a1a2eff);
+
+        // Portrait frame
+        rect!(x = 10, y = box_y + 7, w = 50, h = 50, color = 0x334466ff);
+        sprite!(portrait.0, x = 11, y = box_y + 8, scale = portrait.1);
+
+        text!(speaker, x = 68, y = box_y + 7, color = 0xffd700ff);
+
+        // Typewriter: reveal the wrapped lines one character at a time
+        let mut remaining = ((self.cutscene_timer / CUTSCENE_TEXT_SPEED) as usize).min(dialogue.len());
+        let typed = remaining == dialogue.len();
+        for (i, line) in Self::wrap_dialogue(dialogue).iter().enumerate() {
+            let shown = &line[..remaining.min(line.len())];
+            text!("{}", shown; x = 68, y = box_y + 21 + i as i32 * 10, color = 0xffffffff);
+            remaining = remaining.saturating_sub(line.len() + 1);
+        }
+
+        // Blinking prompt once the line is fully typed
+        if typed && (self.frame / 20) % 2 == 1 {
+            text!("X >", x = 334, y = box_y + 52, color = 0xffd700ff, font = "small");
+        }
+    }
+
+    fn wrap_dialogue(text: &str) -> Vec<String> {
+        let mut lines = Vec::new();
+        let mut line = String::new();
+        for word in text.split(' ') {
+            if !line.is_empty() && line.len() + 1 + word.len() > CUTSCENE_LINE_CHARS {
+                lines.push(std::mem::take(&mut line));
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(word);
+        }
+        if !line.is_empty() {
+            lines.push(line);
+        }
+        lines
+    }
+
     fn draw_hud(&self) {
-        // Black HUD bar at top
-        rect!(x = 0, y = 0, w = 360, h = 16, color = 0x000000ff);
+        // Black HUD bar, at the top or bottom depending on the HUD layout setting.
+        // row_y is the line of smaller info (weapon, item slot) just inside the bar
+        let layout = self.save.settings.hud_layout;
+        let bar_y = if layout == HUD_BOTTOM { 224 } else { 0 };
+        let row_y = if layout == HUD_BOTTOM { bar_y - 14 } else { 16 };
+        let bar_color = if layout == HUD_COMPACT { 0x00000088 } else { 0x000000ff };
+        rect!(x = 0, y = bar_y, w = 360, h = 16, color = bar_color);
 
         // ============================================
         // HEALTH BAR (Rectangle style)
         // ============================================
         // Background (dark red)
-        rect!(x = 10, y = 4, w = 50, h = 8, color = 0x400000ff);
+        rect!(x = 10, y = bar_y + 4, w = 50, h = 8, color = 0x400000ff);
         
         // Foreground (bright red, proportional to HP)
         let health_width = ((self.player_hp as f32 / self.player_max_hp as f32) * 50.0) as u32;
@@ -3912,10 +8704,10 @@ impl GameState {
         } else {
             0x00ff00ff  // Green when high
         };
-        rect!(x = 10, y = 4, w = health_width, h = 8, color = health_color);
+        rect!(x = 10, y = bar_y + 4, w = health_width, h = 8, color = health_color);
         
         // HP text
-        text!("HP", x = 11, y = 4, color = 0xffffffff);
+        text!("HP", x = 11, y = bar_y + 4, color = 0xffffffff);
 
         // ============================================
         // LIVES (Heart icons)
@@ -3923,16 +8715,52 @@ impl GameState {
         for i in 0..self.lives {
             let x = 70 + (i as i32 * 10);
             // Draw heart shape with rectangles
-            rect!(x = x, y = 5, w = 3, h = 2, color = 0xff0000ff);      // Left top
-            rect!(x = x + 4, y = 5, w = 3, h = 2, color = 0xff0000ff);  // Right top
-            rect!(x = x, y = 7, w = 7, h = 4, color = 0xff0000ff);      // Body
+            rect!(x = x, y = bar_y + 5, w = 3, h = 2, color = 0xff0000ff);      // Left top
+            rect!(x = x + 4, y = bar_y + 5, w = 3, h = 2, color = 0xff0000ff);  // Right top
+            rect!(x = x, y = bar_y + 7, w = 7, h = 4, color = 0xff0000ff);      // Body
         }
 
         // Rest of HUD - adjusted positions to prevent overlap
-        text!("SCORE:{}", self.score; x = 105, y = 4, color = 0xffffffff);
-        text!("KEYS:{}", self.keys_collected; x = 170, y = 4, color = 0xffd700ff);
-        text!("KIDS:{}/{}", self.kids_collected, self.total_kids_in_level; x = 215, y = 4, color = 0x00ffffff);
-        text!("BOM:{}", self.gift_bombs; x = 270, y = 4, color = 0xffaa00ff);
+        text!("SCORE:{}", self.score; x = 105, y = bar_y + 4, color = 0xffffffff);
+        // Key inventory: one slot per color, filled once held
+        text!("KEYS", x = 170, y = bar_y + 4, color = 0xffd700ff);
+        for (color_idx, (_, color)) in KEY_COLORS.iter().enumerate() {
+            let slot_x = 192 + color_idx as i32 * 7;
+            if self.keys_held & (1 << color_idx) != 0 {
+                rect!(x = slot_x, y = bar_y + 5, w = 5, h = 6, color = *color);
+            } else {
+                rect!(x = slot_x, y = bar_y + 5, w = 5, h = 6, color = 0x404040ff);
+            }
+        }
+        text!("KIDS:{}/{}", self.kids_collected, self.total_kids_in_level; x = 215, y = bar_y + 4, color = 0x00ffffff);
+        text!("BOM:{}/{}", self.gift_bombs, self.gift_bomb_capacity; x = 270, y = bar_y + 4, color = 0xffaa00ff);
+
+        // Selected consumable under BOM (Q cycles, E uses)
+        let ItemDef { name: item_name, color: item_color, .. } = ITEMS[self.selected_item as usize];
+        let carried = self.items[self.selected_item as usize];
+        let (slot_color, label_color) = if carried > 0 { (item_color, 0xffffffff) } else { (0x404040ff, 0x808080ff) };
+        rect!(x = 269, y = row_y - 1, w = 10, h = 10, color = 0x000000aa);
+        rect!(x = 270, y = row_y, w = 8, h = 8, color = slot_color);
+        if layout == HUD_COMPACT {
+            text!("x{}", carried; x = 282, y = row_y + 1, color = label_color, font = "small");
+        } else {
+            text!("{} x{}", item_name, carried; x = 282, y = row_y + 1, color = label_color, font = "small");
+        }
+        if self.item_message.2 > 0 {
+            let ItemDef { name, color, use_text, .. } = ITEMS[self.item_message.0 as usize];
+            let message_y = if layout == HUD_BOTTOM { row_y - 10 } else { row_y + 11 };
+            match self.item_message.1 {
+                ITEM_MSG_PICKUP => text!("+{}", name; x = 270, y = message_y, color = color, font = "small"),
+                ITEM_MSG_USED => text!(use_text, x = 270, y = message_y, color = color, font = "small"),
+                _ => text!("NOT NOW", x = 270, y = message_y, color = 0x808080ff, font = "small"),
+            }
+        }
+
+        // Weapon tier next to the bar
+        if layout != HUD_COMPACT {
+            let tier = &WEAPON_TIERS[self.weapon_tier as usize];
+            text!(tier.name, x = 4, y = row_y + 3, color = tier.color, font = "small");
+        }
 
         // Level timer (countdown) - positioned at far right
         let remaining_frames = self.level_time_limit.saturating_sub(self.level_timer);
@@ -3940,13 +8768,25 @@ impl GameState {
         let minutes = remaining_seconds / 60;
         let secs = remaining_seconds % 60;
         let timer_color = if remaining_seconds < 30 { 0xff0000ff } else { 0xffffffff };
-        text!("{}:{:02}", minutes, secs; x = 320, y = 4, color = timer_color);
+        text!("{}:{:02}", minutes, secs; x = 320, y = bar_y + 4, color = timer_color);
 
         // Visual feedback near player when a key is picked up
         if self.key_pickup_flash > 0 {
             let fx_x = (self.player_x - self.camera_x) as i32;
             let fx_y = self.player_y as i32 - 15;
-            text!("KEY!", x = fx_x - 10, y = fx_y, color = 0xffd700ff, font = "small");
+            let (name, color) = KEY_COLORS[self.key_pickup_color as usize];
+            text!("{} KEY!", name; x = fx_x - 16, y = fx_y, color = color, font = "small");
+        }
+
+        // Gate opened announcement
+        if self.gate_message.1 > 0 {
+            let name = LEVEL_GATES[self.gate_message.0].name;
+            text!("{} OPEN!", name; x = 130, y = 30, color = 0x00ff00ff);
+        }
+
+        // Upgrade announcement
+        if self.upgrade_message_timer > 0 {
+            text!("WALL BOOTS! Hold toward a wall in the air, X to kick off", x = 30, y = 40, color = 0xfff060ff, font = "small");
         }
 
         // Visual feedback near player when a kid is rescued
@@ -3957,7 +8797,7 @@ impl GameState {
         }
 
         // Draw Boss Health Bar if active
-        if self.boss_active && self.evil_santa_hp > 0 {
+        if self.boss_active && self.boss.hp > 0 {
             let bar_width = 150;
             let bar_height = 8;
             let bar_x = (SCREEN_WIDTH as i32 - bar_width) / 2;
@@ -3968,12 +8808,20 @@ impl GameState {
             // Background
             rect!(x = bar_x, y = bar_y, w = bar_width, h = bar_height, color = 0x440000ff);
             // Fill
-            let fill_pct = self.evil_santa_hp as f32 / self.evil_santa_max_hp as f32;
+            let fill_pct = self.boss.hp as f32 / self.boss.max_hp as f32;
             let fill_width = (bar_width as f32 * fill_pct) as u32;
             if fill_width > 0 {
                 rect!(x = bar_x, y = bar_y, w = fill_width, h = bar_height, color = 0xff0000ff);
             }
-            text!("EVIL SANTA", x = bar_x + 35, y = bar_y - 12, color = 0xff0000ff, font = "small");
+            // Phase thresholds
+            for ratio in [BOSS_PHASE_2_HP_RATIO, BOSS_PHASE_3_HP_RATIO] {
+                let tick_x = bar_x + (bar_width as f32 * ratio) as i32;
+                rect!(x = tick_x, y = bar_y - 2, w = 1, h = bar_height + 4, color = 0xffffffff);
+            }
+            let name = self.boss_def().name;
+            let name_x = bar_x + (bar_width - name.len() as i32 * 8) / 2;
+            text!(name, x = name_x, y = bar_y - 12, color = 0xff0000ff, font = "small");
+            text!("PHASE {}", self.boss_fight_phase; x = bar_x + bar_width + 6, y = bar_y, color = 0xffd700ff, font = "small");
         }
 
         // Developer mode hint overlay
@@ -3988,6 +8836,18 @@ impl GameState {
                 color = 0xffff00ff,
                 font = "small"
             );
+            let profile = &self.movement_profiles[self.movement_profile];
+            let (field, _) = MOVEMENT_TUNE_FIELDS[self.movement_tune_field];
+            text!(
+                "M:{}  [ ] {} - = {}",
+                profile.name,
+                field,
+                profile.tune_value(self.movement_tune_field);
+                x = 8,
+                y = 30,
+                color = 0x9fe8ffff,
+                font = "small"
+            );
         }
     }
 }
@@ -4048,7 +8908,7 @@ impl GameState {
         // ============================================
         let col1_x = panel_x + 15;
         let col2_x = panel_x + 170;
-        let row_h = 22;
+        let row_h = 19;
         let start_y = panel_y + 55;
         
         // Column 1: MOVE
@@ -4099,10 +8959,28 @@ impl GameState {
         text!("RESTART", x = col2_x + 8, y = start_y + row_h * 3 + 5, color = 0x8888ffff, font = "small");
         text!("Enter", x = col2_x + 95, y = start_y + row_h * 3 + 5, color = 0xffffffff, font = "small");
         
+        // Column 1: RUN
+        rect!(x = col1_x, y = start_y + row_h * 4, w = 145, h = row_h - 2, color = 0x1a472aaa);
+        rect!(x = col1_x, y = start_y + row_h * 4, w = 3, h = row_h - 2, color = 0x00ff00aa);
+        text!("RUN", x = col1_x + 8, y = start_y + row_h * 4 + 5, color = 0x00ff00ff, font = "small");
+        text!("Hold A", x = col1_x + 85, y = start_y + row_h * 4 + 5, color = 0xffffffff, font = "small");
+
+        // Column 2: DASH
+        rect!(x = col2_x, y = start_y + row_h * 4, w = 145, h = row_h - 2, color = 0x1a472aaa);
+        rect!(x = col2_x, y = start_y + row_h * 4, w = 3, h = row_h - 2, color = 0x00ff00aa);
+        text!("DASH", x = col2_x + 8, y = start_y + row_h * 4 + 5, color = 0x00ff00ff, font = "small");
+        text!("D (+Down)", x = col2_x + 80, y = start_y + row_h * 4 + 5, color = 0xffffffff, font = "small");
+
+        // Column 1: ITEM
+        rect!(x = col1_x, y = start_y + row_h * 5, w = 145, h = row_h - 2, color = 0x2a1a0aaa);
+        rect!(x = col1_x, y = start_y + row_h * 5, w = 3, h = row_h - 2, color = 0xffd700aa);
+        text!("ITEM", x = col1_x + 8, y = start_y + row_h * 5 + 5, color = 0xffd700ff, font = "small");
+        text!("E (Q swap)", x = col1_x + 70, y = start_y + row_h * 5 + 5, color = 0xffffffff, font = "small");
+
         // ============================================
         // FOOTER - Animated tips
         // ============================================
-        rect!(x = panel_x + 20, y = panel_y + 155, w = panel_w - 40, h = 1, color = 0xffd70044);
+        rect!(x = panel_x + 20, y = panel_y + 171, w = panel_w - 40, h = 1, color = 0xffd70044);
         
         let tip_phase = (self.frame / 120) % 3;
         let tip_text = match tip_phase {
@@ -4110,7 +8988,7 @@ impl GameState {
             1 => "Defeat enemies to get gift bombs!",
             _ => "Destroy doors to free the children!",
         };
-        text!(tip_text, x = panel_x + 40, y = panel_y + 165, color = 0xff6b6bff, font = "small");
+        text!(tip_text, x = panel_x + 40, y = panel_y + 177, color = 0xff6b6bff, font = "small");
         
         // Snowflake decorations
         let snow_offset = (self.frame / 8) % 20;
@@ -4118,4 +8996,1051 @@ impl GameState {
         circ!(x = panel_x + panel_w - 25, y = panel_y + 175 - (snow_offset as i32 % 10), d = 3, color = 0xffffff66);
     }
 }
-        
\ No newline at end of file
+        
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn respawn_lets_go_of_rope_and_ladder() {
+        let mut game = GameState::new();
+        game.lives = 2;
+        game.player_rope = Some(0);
+        game.player_state = STATE_HANG;
+        game.player_on_ladder = true;
+        game.player_dash_timer = 5;
+
+        game.handle_death();
+
+        assert_eq!(game.player_rope, None);
+        assert!(!game.player_on_ladder);
+        assert_eq!(game.player_dash_timer, 0);
+        assert_eq!(game.player_state, STATE_IDLE);
+        assert_eq!(game.lives, 1);
+    }
+
+    #[test]
+    fn shipped_movement_profiles_are_valid_and_classic_is_default() {
+        let profiles = MovementProfile::list_from_json(MOVEMENT_PROFILES_JSON).unwrap();
+        assert_eq!(profiles[0].name, "CLASSIC");
+        assert_eq!(profiles[0].air_jumps, 0);
+        assert_eq!(profiles[0].apex_gravity, 1.0);
+        assert_eq!(profiles[0].fall_gravity, 1.0);
+    }
+
+    #[test]
+    fn movement_profiles_reject_bad_data() {
+        assert!(MovementProfile::list_from_json("not json").is_err());
+        assert!(MovementProfile::list_from_json(r#"{"profiles": []}"#).is_err());
+
+        let mut file: serde_json::Value = serde_json::from_str(MOVEMENT_PROFILES_JSON).unwrap();
+        file["profiles"][0]["jump_cut"] = serde_json::json!(1.5);
+        assert!(MovementProfile::list_from_json(&file.to_string()).is_err());
+
+        let mut file: serde_json::Value = serde_json::from_str(MOVEMENT_PROFILES_JSON).unwrap();
+        file["profiles"][1]["name"] = serde_json::json!("CLASSIC");
+        assert!(MovementProfile::list_from_json(&file.to_string()).is_err());
+    }
+
+    #[test]
+    fn tuning_steps_values_and_stays_valid() {
+        let mut profile = MovementProfile::list_from_json(MOVEMENT_PROFILES_JSON).unwrap().remove(0);
+        profile.tune(0, 1.0);
+        assert_eq!(profile.accel, 0.22);
+        // jump_cut can't go past 1.0
+        for _ in 0..20 {
+            profile.tune(6, 1.0);
+        }
+        assert_eq!(profile.jump_cut, 1.0);
+        // Adding an air jump gives it a usable strength
+        profile.tune(10, 1.0);
+        assert_eq!(profile.air_jumps, 1);
+        assert!(profile.validate().is_ok());
+    }
+
+    #[test]
+    fn air_jump_waits_when_landing_is_close() {
+        let mut game = GameState::new();
+        game.platforms = vec![(0.0, 100.0, 100.0)];
+        game.walls = [(0.0, 0.0, 0.0, 0.0); 22];
+        for block in game.blocks.iter_mut() {
+            block.hp = 0;
+        }
+        game.player_x = 50.0;
+        game.player_vy = 3.0;
+
+        game.player_y = 100.0 - 19.0 - 4.0;
+        assert!(game.landing_soon());
+        game.player_y = 100.0 - 19.0 - 40.0;
+        assert!(!game.landing_soon());
+        game.player_vy = -3.0;
+        game.player_y = 100.0 - 19.0 - 4.0;
+        assert!(!game.landing_soon());
+    }
+
+    fn bomb_door(game: &mut GameState, door_idx: usize) {
+        let (x, y, _) = game.doors[door_idx];
+        game.player_x = x + 300.0;
+        game.placed_bombs[0] = PlacedBomb::new(x + 21.0, y + 21.0, 0.0, 0.0, false);
+        game.check_bomb_damage(0);
+    }
+
+    #[test]
+    fn doors_drop_their_own_key_color_in_any_order() {
+        let mut game = GameState::new();
+        game.load_level(1);
+        bomb_door(&mut game, 3);
+        bomb_door(&mut game, 0);
+        let colors: Vec<u8> = game.keys.iter().filter(|key| key.active).map(|key| key.color).collect();
+        assert_eq!(colors, vec![KEY_BLUE, KEY_RED]);
+
+        bomb_door(&mut game, 1);
+        assert_eq!(game.total_kids_in_level, 1);
+    }
+
+    #[test]
+    fn every_gate_and_exit_can_be_opened_by_its_level_doors() {
+        let mut game = GameState::new();
+        for level in 1..=3 {
+            game.level = level;
+            let mask = game.level_keys_mask();
+            assert_ne!(mask, 0);
+            for gate in LEVEL_GATES.iter().filter(|gate| gate.level == level) {
+                assert_eq!(gate.required & mask, gate.required, "{} on level {}", gate.name, level);
+            }
+        }
+    }
+
+    #[test]
+    fn gates_need_the_specific_colors() {
+        let mut game = GameState::new();
+        game.load_level(1);
+        let wall = LEVEL_GATES[0].wall;
+        game.keys_held = 1 << KEY_RED | 1 << KEY_GREEN;
+        game.update_gates();
+        assert_eq!(game.gate_progress[wall], 0);
+        game.keys_held |= 1 << KEY_BLUE;
+        game.update_gates();
+        assert_eq!(game.gate_progress[wall], 1);
+    }
+
+    const TEST_PATTERN: &str = r#"{
+        "phase_attacks": [["dash"], ["dash", "slam"], ["dash", "slam", "sack_summon"]],
+        "attacks": [
+            { "name": "dash", "windup": 10, "active": 10, "recovery": 10, "damage": 1 },
+            { "name": "slam", "windup": 0, "active": 10, "recovery": 10, "damage": 2, "damage_max_hp_ratio": 0.25 },
+            { "name": "sack_summon", "windup": 10, "active": 10, "recovery": 10, "count": 2 }
+        ],
+        "rules": [
+            { "attack": "dash", "weight": 10 },
+            { "attack": "slam", "weight": 30, "max_dx": 50.0 },
+            { "attack": "sack_summon", "weight": 60, "min_dx": 100.0 }
+        ]
+    }"#;
+
+    fn situation(dx: f32) -> BossSituation {
+        BossSituation { dx, dy: 0.0, hp_ratio: 1.0, near_wall: false, player_idle: false, minions_full: false }
+    }
+
+    #[test]
+    fn shipped_boss_patterns_load() {
+        assert_eq!(GameState::load_boss_patterns().len(), BOSS_DEFS.len());
+    }
+
+    #[test]
+    fn alternative_boss_pattern_loads() {
+        let pattern = BossPattern::from_json(TEST_PATTERN).unwrap();
+        assert_eq!(pattern.phase_pool(1), &[ATTACK_DASH]);
+        assert_eq!(pattern.phase_pool(3), &[ATTACK_DASH, ATTACK_SLAM, ATTACK_SACK_SUMMON]);
+        assert_eq!(pattern.attack(ATTACK_SLAM).damage, 2);
+    }
+
+    #[test]
+    fn invalid_boss_patterns_are_rejected() {
+        assert!(BossPattern::from_json("{").is_err());
+        assert!(BossPattern::from_json(&TEST_PATTERN.replace("\"slam\", \"sack_summon\"]", "\"slam\", \"laser\"]")).is_err());
+        assert!(BossPattern::from_json(&TEST_PATTERN.replace("[\"dash\"], ", "")).is_err());
+        assert!(BossPattern::from_json(&TEST_PATTERN.replace("[[\"dash\"]", "[[]")).is_err());
+        // Used in a phase but never defined
+        let undefined = TEST_PATTERN.replace(
+            r#"{ "name": "sack_summon", "windup": 10, "active": 10, "recovery": 10, "count": 2 }"#,
+            r#"{ "name": "dash", "windup": 1, "active": 1, "recovery": 1 }"#,
+        );
+        assert!(BossPattern::from_json(&undefined).is_err());
+    }
+
+    #[test]
+    fn boss_rules_weight_the_phase_pool() {
+        let pattern = BossPattern::from_json(TEST_PATTERN).unwrap();
+        // Close in phase 2: dash 10, slam 30 -> rolls 0-9 dash, 10-39 slam
+        assert_eq!(pattern.pick_attack(2, &situation(20.0), 0), ATTACK_DASH);
+        assert_eq!(pattern.pick_attack(2, &situation(20.0), 9), ATTACK_DASH);
+        assert_eq!(pattern.pick_attack(2, &situation(20.0), 10), ATTACK_SLAM);
+        assert_eq!(pattern.pick_attack(2, &situation(20.0), 39), ATTACK_SLAM);
+        assert_eq!(pattern.pick_attack(2, &situation(20.0), 40), ATTACK_DASH);
+        // Phase 1 only unlocks the dash
+        assert_eq!(pattern.pick_attack(1, &situation(20.0), 15), ATTACK_DASH);
+        // Far in phase 3: dash 10, summon 60, unless the minions are maxed out
+        assert_eq!(pattern.pick_attack(3, &situation(150.0), 50), ATTACK_SACK_SUMMON);
+        let full = BossSituation { minions_full: true, ..situation(150.0) };
+        assert_eq!(pattern.pick_attack(3, &full, 50), ATTACK_DASH);
+    }
+
+    #[test]
+    fn boss_damage_scales_with_max_hp() {
+        let pattern = BossPattern::from_json(TEST_PATTERN).unwrap();
+        assert_eq!(pattern.attack(ATTACK_DASH).damage_for(12), 1);
+        assert_eq!(pattern.attack(ATTACK_SLAM).damage_for(6), 2);
+        assert_eq!(pattern.attack(ATTACK_SLAM).damage_for(12), 3);
+        let shipped = BossPattern::from_json(BOSS_PATTERNS_JSON).unwrap();
+        assert_eq!(shipped.attack(ATTACK_PROJECTILE).damage_for(6), 1);
+        assert_eq!(shipped.attack(ATTACK_PROJECTILE).damage_for(12), 2);
+    }
+
+    #[test]
+    fn boss_arenas_stay_clear_of_linked_gates() {
+        for def in BOSS_DEFS.iter().filter(|def| !def.own_screen) {
+            let mut game = GameState::new();
+            game.load_level(def.level);
+            let (left, right, _) = def.arena;
+            assert!(def.boss_start.0 > left && def.boss_start.0 < right, "{} starts outside its arena", def.name);
+            for target in game.targets.iter().filter(|target| target.link > 0 && target.kind == TARGET_GATE) {
+                let (x, _, w, _) = game.walls[target.index];
+                assert!(x >= right || x + w <= left, "{} arena on level {} contains a linked gate", def.name, def.level);
+            }
+        }
+    }
+
+    #[test]
+    fn shots_keep_the_color_of_the_tier_that_fired_them() {
+        let mut game = GameState::new();
+        game.weapon_tier = WEAPON_BASIC;
+        game.spawn_player_projectile();
+        game.weapon_tier = WEAPON_SPREAD;
+
+        let shot = game.projectiles.iter().find(|proj| proj.active).unwrap();
+        assert_eq!(shot.color, WEAPON_TIERS[WEAPON_BASIC as usize].color);
+        // Basic shots only reach a few pixels past Santa's hand
+        assert_eq!(shot.max_distance, 5.0);
+    }
+
+    #[test]
+    fn level_intro_plays_once_until_a_new_game() {
+        let mut game = GameState::new();
+        game.start_run(1);
+        assert_eq!(game.cutscene_id, CUTSCENE_LEVEL_1_INTRO);
+        game.end_cutscene();
+
+        // Game over restart, level select and dev jumps go straight in
+        game.start_run(1);
+        assert_eq!(game.cutscene_id, CUTSCENE_NONE);
+        game.enter_level(1);
+        assert_eq!(game.cutscene_id, CUTSCENE_NONE);
+
+        game.enter_level(2);
+        assert_eq!(game.cutscene_id, CUTSCENE_LEVEL_2_INTRO);
+        assert_eq!(game.cutscene_age, 0);
+    }
+
+    // Santa standing still on the floor at (x, floor_y), nothing else on any plate
+    fn stand_at(game: &mut GameState, x: f32, floor_y: f32) {
+        game.player_x = x;
+        game.player_y = floor_y - 19.0;
+        game.player_on_ground = true;
+        game.player_on_ladder = false;
+        for enemy in game.enemies.iter_mut() {
+            enemy.8 = false;
+        }
+    }
+
+    fn trigger_index(game: &GameState, kind: u8) -> usize {
+        game.triggers.iter().position(|trigger| trigger.link > 0 && trigger.kind == kind).unwrap()
+    }
+
+    #[test]
+    fn plate_holds_its_link_for_the_duration_after_release() {
+        let mut game = GameState::new();
+        game.load_level(1);
+        let idx = trigger_index(&game, TRIGGER_PLATE);
+        let Trigger { x, y, link, duration, .. } = game.triggers[idx];
+
+        stand_at(&mut game, x, y);
+        game.update_triggers();
+        assert!(game.triggers[idx].on);
+        assert_ne!(game.links_on & (1 << link), 0);
+
+        // Stepping off keeps it down for `duration` frames, then it pops up
+        stand_at(&mut game, x - 200.0, y);
+        for _ in 0..duration {
+            game.update_triggers();
+            assert!(game.triggers[idx].on);
+        }
+        game.update_triggers();
+        assert!(!game.triggers[idx].on);
+        assert_eq!(game.links_on & (1 << link), 0);
+    }
+
+    #[test]
+    fn timed_switch_restarts_on_every_hit_and_toggles_without_duration() {
+        let mut game = GameState::new();
+        game.load_level(2);
+        let idx = trigger_index(&game, TRIGGER_SWITCH);
+        let duration = game.triggers[idx].duration;
+        assert!(duration > 0);
+
+        game.hit_switch(idx);
+        for _ in 0..duration - 10 {
+            game.update_triggers();
+        }
+        assert!(game.triggers[idx].on);
+        game.hit_switch(idx);
+        assert_eq!(game.triggers[idx].timer, duration);
+        for _ in 0..duration {
+            game.update_triggers();
+        }
+        assert!(!game.triggers[idx].on);
+
+        game.triggers[idx].duration = 0;
+        game.hit_switch(idx);
+        for _ in 0..duration * 2 {
+            game.update_triggers();
+        }
+        assert!(game.triggers[idx].on);
+        game.hit_switch(idx);
+        assert!(!game.triggers[idx].on);
+    }
+
+    #[test]
+    fn moving_platform_slides_and_carries_santa() {
+        let mut game = GameState::new();
+        game.load_level(2);
+        let target = *game.targets.iter().find(|target| target.link > 0 && target.kind == TARGET_PLATFORM).unwrap();
+        let (x1, x2, y) = game.platforms[target.index];
+
+        // Slides the full (a, b) while its link is on, Santa elsewhere stays put
+        stand_at(&mut game, x1 - 200.0, y);
+        game.links_on = 1 << target.link;
+        for _ in 0..200 {
+            game.update_link_targets();
+        }
+        assert_eq!(game.player_x, x1 - 200.0);
+        let (moved_x1, moved_x2, moved_y) = game.platforms[target.index];
+        assert!((moved_x1 - (x1 + target.a)).abs() < 0.01);
+        assert!((moved_y - (y + target.b)).abs() < 0.01);
+
+        // Santa standing on it rides back at platform speed
+        stand_at(&mut game, (moved_x1 + moved_x2) / 2.0, moved_y);
+        let ride_x = game.player_x;
+        game.links_on = 0;
+        for _ in 0..10 {
+            game.update_link_targets();
+        }
+        let travelled = 10.0 * MOVING_PLATFORM_SPEED * target.a.signum();
+        assert!((game.player_x - (ride_x - travelled)).abs() < 0.01);
+        for _ in 0..200 {
+            game.update_link_targets();
+        }
+        assert_eq!(game.platforms[target.index], (x1, x2, y));
+    }
+
+    #[test]
+    fn spawner_releases_one_enemy_with_the_data_patrol() {
+        let mut game = GameState::new();
+        game.load_level(1);
+        let target_idx = game.targets.iter().position(|target| target.link > 0 && target.kind == TARGET_SPAWNER).unwrap();
+        let target = game.targets[target_idx];
+
+        game.links_on = 1 << target.link;
+        game.update_link_targets();
+        let slot = game.targets[target_idx].spawned.unwrap();
+        let enemy = game.enemies[slot];
+        assert_eq!((enemy.0, enemy.1, enemy.2, enemy.7), (target.index as u8, target.a, target.b, target.patrol_x));
+
+        // Turning the link off and on again doesn't release a second one while it lives
+        let alive = game.enemies.iter().filter(|enemy| enemy.8).count();
+        game.links_prev = 0;
+        game.update_link_targets();
+        assert_eq!(game.enemies.iter().filter(|enemy| enemy.8).count(), alive);
+    }
+
+    #[test]
+    fn icicles_fall_over_the_floor_that_is_left() {
+        let mut game = GameState::new();
+        game.boss_index = 0;
+        game.boss_fight_phase = BOSS_FIGHT_PHASE_3;
+        game.boss.state = BOSS_STATE_IDLE;
+        game.boss_floor_left = BOSS_FLOOR_MIN_LEFT;
+        game.boss_floor_right = BOSS_FLOOR_MIN_RIGHT;
+        game.boss_icicle_timer = 1000;
+        game.player_x = BOSS_ARENA_LEFT;
+
+        game.update_boss_arena();
+
+        let icicle = game.falling_hazards.iter().find(|hazard| hazard.active && hazard.kind == HAZARD_ICICLE).unwrap();
+        assert!(icicle.x >= BOSS_FLOOR_MIN_LEFT && icicle.x <= BOSS_FLOOR_MIN_RIGHT);
+    }
+
+    #[test]
+    fn sound_cues_cool_down_and_stingers_stop_the_music() {
+        let mut sound = AudioManager::new();
+        sound.play_music(Some(CUE_MUSIC_LEVEL_1));
+        assert!(sound.play(CUE_SANTA_HURT));
+        let first = sound.last_variant[CUE_SANTA_HURT as usize];
+        assert!(!sound.play(CUE_SANTA_HURT));
+        for _ in 0..SOUND_CUES[CUE_SANTA_HURT as usize].cooldown {
+            sound.update();
+        }
+        assert!(sound.play(CUE_SANTA_HURT));
+        // Two variants, never the same one twice in a row
+        assert_ne!(sound.last_variant[CUE_SANTA_HURT as usize], first);
+
+        assert_eq!(sound.music, Some((CUE_MUSIC_LEVEL_1, 0)));
+        sound.play(CUE_LEVEL_CLEAR);
+        assert_eq!(sound.music, None);
+    }
+
+    #[test]
+    fn music_follows_the_screen() {
+        let mut game = GameState::new();
+        game.in_menu = true;
+        assert_eq!(game.music_cue(), Some(CUE_MUSIC_MENU));
+        game.start_run(1);
+        assert_eq!(game.music_cue(), Some(CUE_MUSIC_LEVEL_1));
+        game.enter_level(2);
+        assert_eq!(game.music_cue(), Some(CUE_MUSIC_LEVEL_2));
+        game.boss_active = true;
+        assert_eq!(game.music_cue(), Some(CUE_MUSIC_BOSS));
+        game.show_game_over = true;
+        assert_eq!(game.music_cue(), None);
+    }
+
+    #[test]
+    fn stingers_and_boss_voices_are_their_own() {
+        assert_ne!(SOUND_CUES[CUE_VICTORY as usize].variants, SOUND_CUES[CUE_LEVEL_CLEAR as usize].variants);
+        for def in BOSS_DEFS.iter().filter(|def| def.kind != BOSS_EVIL_SANTA) {
+            for cue in [def.hurt_cue, def.warning_cue, def.roar_cue].into_iter().flatten() {
+                assert!(!SOUND_CUES[cue as usize].variants.iter().any(|name| name.starts_with("evil")), "{} uses Evil Santa's voice", def.name);
+            }
+        }
+    }
+
+    #[test]
+    fn level_results_rate_stars_and_keep_the_best() {
+        let mut game = GameState::new();
+        game.level = 1;
+        let (par_seconds, target_score) = game.star_targets();
+
+        // Fast and high scoring: all three stars, a new best
+        game.level_stats.score_at_start = 1000;
+        game.score = 1000 + target_score;
+        game.level_timer = par_seconds * 60;
+        game.kids_collected = 2;
+        game.record_level_result();
+        assert_eq!(game.last_result, (3, true));
+        assert_eq!(game.save.best[0], LevelBest { stars: 3, score: target_score, kids: 2 });
+
+        // A slower, poorer clear only rates the clear itself and leaves the best alone
+        game.level_stats.score_at_start = 0;
+        game.score = 10;
+        game.level_timer = (par_seconds + 1) * 60;
+        game.kids_collected = 0;
+        game.record_level_result();
+        assert_eq!(game.last_result, (1, false));
+        assert_eq!(game.save.best[0], LevelBest { stars: 3, score: target_score, kids: 2 });
+    }
+
+    #[test]
+    fn save_data_round_trips() {
+        let mut save = SaveData::new();
+        save.tokens = 42;
+        save.upgrades = [1, 0, 2, 1];
+        save.best[1] = LevelBest { stars: 2, score: 5000, kids: 3 };
+        save.high_scores[0] = 12345;
+        save.settings.music_volume = 3;
+        save.settings.reduced_motion = true;
+
+        let bytes = borsh::to_vec(&save).unwrap();
+        let loaded = borsh::from_slice::<SaveData>(&bytes).unwrap();
+        assert_eq!(loaded.version, SAVE_VERSION);
+        assert_eq!((loaded.tokens, loaded.upgrades, loaded.best), (save.tokens, save.upgrades, save.best));
+        assert_eq!(loaded.high_scores, save.high_scores);
+        // Saves from before LevelBest still load
+        assert_eq!(borsh::to_vec(&save.best[1]).unwrap(), borsh::to_vec(&(2u8, 5000u32, 3u8)).unwrap());
+        assert_eq!(loaded.settings.music_volume, 3);
+        assert!(loaded.settings.reduced_motion);
+
+        // Bytes from another layout don't load
+        assert!(borsh::from_slice::<SaveData>(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn clearing_a_level_unlocks_the_next_one() {
+        let mut game = GameState::new();
+        assert!(game.level_unlocked(1));
+        assert!(!game.level_unlocked(2));
+        assert!(!game.level_unlocked(3));
+
+        game.level = 1;
+        game.record_level_result();
+        assert!(game.level_unlocked(2));
+        assert!(!game.level_unlocked(3));
+
+        game.level = 2;
+        game.record_level_result();
+        assert!(game.level_unlocked(3));
+        assert!(!game.level_unlocked(4));
+    }
+
+    #[test]
+    fn starting_a_run_resets_it_on_top_of_the_workshop_upgrades() {
+        let mut game = GameState::new();
+        game.save.upgrades = [1, 2, 0, 1];
+        game.score = 999;
+        game.weapon_tier = WEAPON_SPREAD;
+        game.items = [1; 4];
+
+        game.start_run(2);
+        assert_eq!(game.level, 2);
+        assert_eq!(game.score, 0);
+        assert_eq!(game.weapon_tier, WEAPON_BASIC);
+        assert_eq!(game.items, [0; 4]);
+        assert_eq!(game.player_max_hp, PLAYER_START_MAX_HP + SHOP_HP_STEP);
+        assert_eq!(game.gift_bomb_capacity, GIFT_BOMB_START_CAPACITY + 2);
+        assert_eq!(game.lives, START_LIVES + 1);
+    }
+
+    #[test]
+    fn run_scores_slot_into_the_high_score_list() {
+        let mut game = GameState::new();
+        for score in [300, 100, 500, 200, 400, 600, 50] {
+            game.score = score;
+            game.record_run_score();
+        }
+        assert_eq!(game.save.high_scores, [600, 500, 400, 300, 200]);
+    }
+
+    #[test]
+    fn title_menu_skips_continue_until_a_level_is_cleared() {
+        let mut game = GameState::new();
+        assert_eq!(game.continue_level(), 1);
+        assert!(!game.menu_item_enabled(ACTION_CONTINUE));
+        game.open_menu(MENU_TITLE);
+        let (_, cursor) = *game.menu_stack.last().unwrap();
+        assert_ne!(TITLE_MENU[cursor as usize].1, ACTION_CONTINUE);
+
+        game.save.best[0].stars = 1;
+        assert_eq!(game.continue_level(), 2);
+        assert!(game.menu_item_enabled(ACTION_CONTINUE));
+    }
+
+    #[test]
+    fn menu_back_never_closes_the_title() {
+        let mut game = GameState::new();
+        game.menu_stack.clear();
+        game.open_menu(MENU_TITLE);
+        game.menu_confirm(ACTION_SETTINGS);
+        assert_eq!(game.menu_stack.len(), 2);
+        game.menu_back();
+        game.menu_back();
+        assert_eq!(game.menu_stack, vec![(MENU_TITLE, 0)]);
+    }
+
+    #[test]
+    fn settings_step_clamp_and_wrap() {
+        let mut game = GameState::new();
+        game.save.settings.music_volume = VOLUME_STEPS;
+
+        // Left/Right stop at the ends, confirm wraps back to 0
+        game.change_setting(ACTION_MUSIC_VOLUME, true, false);
+        assert_eq!(game.save.settings.music_volume, VOLUME_STEPS);
+        game.change_setting(ACTION_MUSIC_VOLUME, true, true);
+        assert_eq!(game.save.settings.music_volume, 0);
+        game.change_setting(ACTION_MUSIC_VOLUME, false, false);
+        assert_eq!(game.save.settings.music_volume, 0);
+
+        game.change_setting(ACTION_HUD_LAYOUT, false, false);
+        assert_eq!(game.save.settings.hud_layout, HUD_LAYOUT_NAMES.len() as u8 - 1);
+        game.change_setting(ACTION_REDUCED_MOTION, true, true);
+        assert!(game.reduced_motion());
+    }
+
+    #[test]
+    fn master_volume_scales_music_and_sfx() {
+        let mut game = GameState::new();
+        game.save.settings.master_volume = VOLUME_STEPS / 2;
+        game.save.settings.music_volume = VOLUME_STEPS;
+        game.save.settings.sfx_volume = VOLUME_STEPS / 2;
+        game.apply_audio_settings();
+        assert_eq!(game.music_volume(), 0.5);
+        assert_eq!(game.sfx_volume(), 0.25);
+        assert_eq!((game.sound.music_volume, game.sound.sfx_volume), (0.5, 0.25));
+    }
+
+    #[test]
+    fn workshop_upgrades_cost_more_each_time_and_cap_out() {
+        let mut game = GameState::new();
+        let ShopItem { base_cost: base, cost_step: step, max, .. } = SHOP_ITEMS[SHOP_BOMB_SACK as usize];
+        let capacity = game.gift_bomb_capacity;
+
+        game.save.tokens = base - 1;
+        game.buy_upgrade(SHOP_BOMB_SACK);
+        assert_eq!(game.save.upgrades[SHOP_BOMB_SACK as usize], 0);
+
+        game.save.tokens = 1000;
+        for bought in 0..max {
+            assert_eq!(game.upgrade_cost(SHOP_BOMB_SACK), Some(base + step * bought as u32));
+            game.buy_upgrade(SHOP_BOMB_SACK);
+        }
+        assert_eq!(game.upgrade_cost(SHOP_BOMB_SACK), None);
+        let tokens = game.save.tokens;
+        game.buy_upgrade(SHOP_BOMB_SACK);
+        assert_eq!(game.save.tokens, tokens);
+        assert_eq!(game.gift_bomb_capacity, capacity + max);
+    }
+
+    #[test]
+    fn giant_kickmouse_fight_uses_its_own_arena_and_floor() {
+        let mut game = GameState::new();
+        let platforms = game.platforms.clone();
+        let level_enemies = game.enemies.iter().filter(|enemy| enemy.8).count();
+        game.start_boss_fight(1);
+        game.skip_cutscene();
+
+        let def = &BOSS_DEFS[1];
+        assert_eq!((game.boss_floor_left, game.boss_floor_right), (def.arena.0, def.arena.1));
+        assert_eq!(game.camera_x, def.camera_x);
+        assert_eq!(game.platforms, platforms, "in-place arenas keep the level");
+        assert_eq!(game.boss.y, 40.0, "drops in from the ceiling");
+
+        // Minions land on this arena's floor and only they count towards the cap
+        game.summon_boss_minions(2);
+        assert_eq!(game.active_minion_count(), 2);
+        assert_eq!(game.enemies.iter().filter(|enemy| enemy.8).count(), level_enemies + 2);
+        for i in (0..game.enemies.len()).filter(|&i| game.is_boss_minion(i)) {
+            assert_eq!(game.enemies[i].2, def.arena.2 - 20.0);
+        }
+    }
+
+    #[test]
+    fn defeating_a_boss_runs_its_exit_hook_and_restores_the_level() {
+        // In place: the level was never replaced, Santa gets the boots and the camera back
+        let mut game = GameState::new();
+        let platforms = game.platforms.clone();
+        game.start_boss_fight(1);
+        game.skip_cutscene();
+        game.boss.hp = 0;
+        game.update_boss();
+        game.skip_cutscene();
+        assert!(!game.boss_active && game.boss_defeated);
+        assert_eq!(game.platforms, platforms);
+        assert_ne!(game.camera_target(), BOSS_DEFS[1].camera_x);
+        assert!(game.drop_items.iter().any(|drop| drop.active && drop.kind == DROP_WALL_BOOTS));
+
+        // Own screen: level 3 comes back with the exit gates closed
+        game.load_level(3);
+        let platforms = game.platforms.clone();
+        let enemies = game.enemies;
+        game.start_boss_fight(0);
+        assert_eq!(game.platforms.len(), 1);
+        game.skip_cutscene();
+        game.boss.hp = 0;
+        game.update_boss();
+        game.skip_cutscene();
+        assert!(!game.boss_active);
+        assert_eq!(game.platforms, platforms);
+        assert_eq!(game.enemies, enemies);
+        assert_ne!(game.walls[0], (0.0, 0.0, 0.0, 0.0));
+        assert_ne!(game.completion_trigger, (0.0, 0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn cycling_items_skips_empty_slots() {
+        let mut game = GameState::new();
+        game.items = [1, 0, 2, 0];
+        game.selected_item = ITEM_COCOA;
+        game.cycle_item();
+        assert_eq!(game.selected_item, ITEM_SNOW_GLOBE);
+        game.cycle_item();
+        assert_eq!(game.selected_item, ITEM_COCOA);
+
+        // Nothing carried: the selection stays put
+        game.items = [0; 4];
+        game.cycle_item();
+        assert_eq!(game.selected_item, ITEM_COCOA);
+    }
+
+    #[test]
+    fn cocoa_heals_up_to_max_and_stays_in_the_bag_at_full_hp() {
+        let mut game = GameState::new();
+        game.items[ITEM_COCOA as usize] = 2;
+        game.selected_item = ITEM_COCOA;
+        game.player_hp = game.player_max_hp - 1;
+        game.use_item();
+        assert_eq!(game.player_hp, game.player_max_hp);
+        assert_eq!(game.items[ITEM_COCOA as usize], 1);
+
+        game.use_item();
+        assert_eq!(game.items[ITEM_COCOA as usize], 1);
+        assert_eq!(game.item_message.1, ITEM_MSG_NOT_NOW);
+    }
+
+    #[test]
+    fn shield_blocks_hits_until_it_runs_out() {
+        let mut game = GameState::new();
+        game.items[ITEM_SHIELD as usize] = 1;
+        game.selected_item = ITEM_SHIELD;
+        game.use_item();
+        assert_eq!(game.player_shield_timer, SHIELD_FRAMES);
+        assert_eq!(game.items[ITEM_SHIELD as usize], 0);
+
+        let hp = game.player_hp;
+        game.enemies[0].1 = game.player_x;
+        game.enemies[0].2 = game.player_y;
+        game.check_enemy_collisions();
+        game.hurt_player_by_hazard(game.player_x);
+        assert_eq!(game.player_hp, hp);
+        assert_eq!(game.player_invuln_timer, 0, "the shield doesn't lean on i-frames");
+
+        for _ in 0..SHIELD_FRAMES {
+            game.update_consumables();
+        }
+        assert_eq!(game.player_shield_timer, 0);
+        game.check_enemy_collisions();
+        assert!(game.player_hp < hp);
+    }
+
+    #[test]
+    fn snow_globe_freezes_enemies_and_makes_them_harmless() {
+        let mut game = GameState::new();
+        game.items[ITEM_SNOW_GLOBE as usize] = 1;
+        game.selected_item = ITEM_SNOW_GLOBE;
+        game.use_item();
+        assert_eq!(game.enemy_freeze_timer, FREEZE_FRAMES);
+
+        let positions: Vec<f32> = game.enemies.iter().map(|enemy| enemy.1).collect();
+        for _ in 0..30 {
+            game.update_enemies();
+            game.update_consumables();
+        }
+        assert_eq!(game.enemies.iter().map(|enemy| enemy.1).collect::<Vec<f32>>(), positions);
+
+        let hp = game.player_hp;
+        game.enemies[0].1 = game.player_x;
+        game.enemies[0].2 = game.player_y;
+        game.check_enemy_collisions();
+        assert_eq!(game.player_hp, hp);
+    }
+
+    #[test]
+    fn bell_reveals_the_hidden_kids_once() {
+        let mut game = GameState::new();
+        game.items[ITEM_BELL as usize] = 2;
+        game.selected_item = ITEM_BELL;
+        game.use_item();
+        for (i, kid) in LEVEL_HIDDEN_KIDS.iter().enumerate() {
+            assert_eq!(game.hidden_kids[i].0, kid.0 == 1);
+        }
+        game.use_item();
+        assert_eq!(game.items[ITEM_BELL as usize], 1);
+        assert_eq!(game.item_message.1, ITEM_MSG_NOT_NOW);
+    }
+
+    #[test]
+    fn drop_tables_add_up() {
+        let tables: [(&[(u8, u16)], u32); 4] = [
+            (&DROP_TABLE_MOUSE, 106),
+            (&DROP_TABLE_KICKMOUSE, 107),
+            (&DROP_TABLE_PENGUIN, 106),
+            (&DROP_TABLE_DOOR, 116),
+        ];
+        for (table, total) in tables {
+            assert_eq!(table.iter().map(|(_, weight)| *weight as u32).sum::<u32>(), total);
+            assert!(table.iter().all(|(_, weight)| *weight > 0));
+            // Bombs open doors, so every enemy table keeps them the most likely outcome
+            if table != &DROP_TABLE_DOOR[..] {
+                assert!(table.iter().all(|&(_, weight)| weight <= table[0].1) && table[0].0 == DROP_GIFT_BOMB);
+            }
+        }
+    }
+
+    #[test]
+    fn drop_rolls_land_on_each_weight_boundary() {
+        let mut start = 0;
+        for &(kind, weight) in DROP_TABLE_MOUSE.iter() {
+            let end = start + weight as u32;
+            assert_eq!(GameState::pick_drop(&DROP_TABLE_MOUSE, start), kind);
+            assert_eq!(GameState::pick_drop(&DROP_TABLE_MOUSE, end - 1), kind);
+            start = end;
+        }
+        // Rolls past the total wrap around to the first entry
+        assert_eq!(GameState::pick_drop(&DROP_TABLE_MOUSE, start), DROP_GIFT_BOMB);
+        assert_eq!(GameState::pick_drop(&[], 7), DROP_NONE);
+    }
+
+    #[test]
+    fn drops_despawn_unless_they_are_upgrades() {
+        let mut game = GameState::new();
+        game.drop_items = [DropItem::EMPTY; 8];
+        game.spawn_drop(DROP_NONE, 0.0, 0.0);
+        assert!(game.drop_items.iter().all(|drop| !drop.active));
+        game.spawn_drop(DROP_COIN, 0.0, 0.0);
+        game.spawn_drop(DROP_WALL_BOOTS, 0.0, 0.0);
+        assert_eq!(game.drop_items[0].despawn_timer, DROP_DESPAWN_TIME);
+        assert_eq!(game.drop_items[1].despawn_timer, DROP_NO_DESPAWN);
+
+        for _ in 0..=DROP_DESPAWN_TIME {
+            game.check_drop_pickups();
+        }
+        assert!(!game.drop_items[0].active);
+        assert!(game.drop_items[1].active);
+    }
+
+    #[test]
+    fn melee_only_hits_inside_the_swing_window() {
+        let mut game = GameState::new();
+        game.player_facing_right = true;
+        game.player_state = STATE_MELEE;
+        game.attack_frame = 0;
+        for frame in 1..MELEE_FRAMES {
+            // A fresh enemy right in front of the cane every frame
+            game.enemies[0].1 = game.player_x + 15.0;
+            game.enemies[0].2 = game.player_y;
+            game.enemies[0].8 = true;
+            game.enemies[0].11 = 0;
+            game.update_player();
+            let hit = game.enemies[0].11 > 0;
+            assert_eq!(hit, (MELEE_HIT_START..MELEE_HIT_END).contains(&frame), "frame {}", frame);
+        }
+        game.update_player();
+        assert_eq!(game.player_state, STATE_IDLE);
+        assert_eq!(game.attack_cooldown, MELEE_COOLDOWN);
+    }
+
+    #[test]
+    fn melee_hits_the_boss_once_per_swing() {
+        let mut game = GameState::new();
+        for enemy in game.enemies.iter_mut() {
+            enemy.8 = false;
+        }
+        game.boss_index = 1;
+        game.boss_active = true;
+        game.boss = BossEntity::new();
+        game.boss.hp = 10;
+        game.boss.x = game.player_x + 20.0;
+        game.boss.y = game.player_y;
+        game.player_facing_right = true;
+
+        for swing in 1..=2u8 {
+            game.player_state = STATE_MELEE;
+            game.attack_frame = 0;
+            game.melee_hit_boss = false;
+            for _ in 0..MELEE_FRAMES {
+                game.boss.x = game.player_x + 20.0;  // Undo the knockback
+                game.update_player();
+            }
+            assert_eq!(game.boss.hp, 10 - swing * MELEE_DAMAGE);
+        }
+    }
+
+    // Airborne next to nothing, just after sliding off a wall on Santa's right
+    fn left_the_wall(game: &mut GameState) {
+        game.has_wall_jump = true;
+        game.player_x = 600.0;
+        game.player_y = 120.0;
+        game.player_vx = 0.0;
+        game.player_vy = 1.0;
+        game.player_on_ground = false;
+        game.player_coyote_timer = 0;
+        game.player_air_jumps = 0;
+        game.player_state = STATE_FALL;
+        game.player_wall_coyote = WALL_COYOTE_TIME;
+        game.player_wall_coyote_side = 1;
+    }
+
+    #[test]
+    fn buffered_jump_kicks_off_the_wall_within_coyote_time() {
+        let mut game = GameState::new();
+        left_the_wall(&mut game);
+        // Last frame that still counts
+        for _ in 1..WALL_COYOTE_TIME {
+            game.apply_player_input(PlayerInput::default());
+        }
+        game.player_jump_buffer = INPUT_BUFFER;
+        game.apply_player_input(PlayerInput::default());
+        assert_eq!(game.player_state, STATE_WALL_JUMP);
+        assert!(game.player_vx < 0.0 && game.player_vy < 0.0, "kicks up and away from the right wall");
+        assert!(!game.player_facing_right);
+        assert_eq!(game.player_wall_jump_lock, WALL_JUMP_LOCK);
+        assert_eq!(game.player_wall_coyote, 0);
+
+        // The lock holds the arc, air control comes back once it runs out
+        let vx = game.player_vx;
+        for _ in 0..WALL_JUMP_LOCK {
+            game.apply_player_input(PlayerInput::default());
+            assert_eq!(game.player_vx, vx);
+        }
+        assert_eq!(game.player_wall_jump_lock, 0);
+        game.apply_player_input(PlayerInput::default());
+        assert!(game.player_vx.abs() < vx.abs());
+    }
+
+    #[test]
+    fn wall_jump_needs_the_boots_and_a_recent_wall() {
+        let mut game = GameState::new();
+        left_the_wall(&mut game);
+        for _ in 0..WALL_COYOTE_TIME {
+            game.apply_player_input(PlayerInput::default());
+        }
+        game.player_jump_buffer = INPUT_BUFFER;
+        game.apply_player_input(PlayerInput::default());
+        assert_eq!(game.player_state, STATE_FALL);
+        assert_eq!(game.player_wall_jump_lock, 0);
+
+        left_the_wall(&mut game);
+        game.has_wall_jump = false;
+        game.player_jump_buffer = INPUT_BUFFER;
+        game.apply_player_input(PlayerInput::default());
+        assert_eq!(game.player_state, STATE_FALL);
+    }
+
+    #[test]
+    fn dash_grants_i_frames_and_waits_for_its_cooldown() {
+        let mut game = GameState::new();
+        stand_at(&mut game, 150.0, 224.0);
+        let dash = PlayerInput { dash_pressed: true, ..PlayerInput::default() };
+        game.apply_player_input(dash);
+        assert_eq!(game.player_state, STATE_DASH);
+        assert!(game.player_invuln_timer >= DASH_INVULN);
+        assert_eq!(game.player_dash_cooldown, DASH_COOLDOWN);
+
+        // Enemies in the way don't hurt
+        let hp = game.player_hp;
+        game.enemies[0].1 = game.player_x;
+        game.enemies[0].2 = game.player_y;
+        game.enemies[0].8 = true;
+        game.enemies[0].11 = 0;
+        game.check_enemy_collisions();
+        assert_eq!(game.player_hp, hp);
+        game.enemies[0].8 = false;
+
+        while game.player_dash_timer > 0 {
+            game.update_player();
+        }
+        assert_eq!(game.player_state, STATE_IDLE);
+
+        // A second press during the cooldown is ignored
+        let x = game.player_x;
+        game.apply_player_input(dash);
+        assert_eq!(game.player_dash_timer, 0);
+        assert_ne!(game.player_state, STATE_DASH);
+        assert_eq!(game.player_x, x);
+
+        for _ in 0..DASH_COOLDOWN {
+            game.apply_player_input(PlayerInput::default());
+        }
+        game.apply_player_input(dash);
+        assert_eq!(game.player_state, STATE_DASH);
+    }
+
+    #[test]
+    fn slide_ending_under_a_low_ceiling_leaves_santa_crouched() {
+        let mut game = GameState::new();
+        stand_at(&mut game, 150.0, 224.0);
+        game.player_facing_right = true;
+        // Low ceiling over the whole slide, too high to stop the slide itself
+        let slot = game.walls.iter().position(|wall| wall.2 == 0.0).unwrap();
+        game.walls[slot] = (140.0, 180.0, 120.0, 10.0);
+
+        game.apply_player_input(PlayerInput { dash_pressed: true, down: true, ..PlayerInput::default() });
+        assert_eq!(game.player_state, STATE_SLIDE);
+        while game.player_dash_timer > 0 {
+            game.update_player();
+        }
+        assert!(game.player_x > 150.0 && game.player_x < 260.0);
+        assert_eq!(game.player_state, STATE_CROUCH);
+        assert!(game.player_is_crouching);
+
+        // Without the ceiling the slide just ends standing
+        game.walls[slot] = (0.0, 0.0, 0.0, 0.0);
+        game.player_is_crouching = false;
+        game.player_state = STATE_IDLE;
+        game.player_dash_cooldown = 0;
+        game.apply_player_input(PlayerInput { dash_pressed: true, down: true, ..PlayerInput::default() });
+        while game.player_dash_timer > 0 {
+            game.update_player();
+        }
+        assert_eq!(game.player_state, STATE_IDLE);
+    }
+
+    #[test]
+    fn blaster_is_not_sold_at_the_top_weapon_tier() {
+        let mut game = GameState::new();
+        game.save.tokens = 1000;
+        game.weapon_tier = WEAPON_FIREPOWER;
+        game.buy_upgrade(SHOP_WEAPON);
+        assert_eq!(game.weapon_tier, WEAPON_SPREAD);
+        assert_eq!(game.save.upgrades[SHOP_WEAPON as usize], 1);
+
+        // A pickup already got this run to the top: nothing to sell, no tokens taken
+        let tokens = game.save.tokens;
+        assert_eq!(game.upgrade_cost(SHOP_WEAPON), None);
+        game.buy_upgrade(SHOP_WEAPON);
+        assert_eq!(game.save.tokens, tokens);
+        assert_eq!(game.save.upgrades[SHOP_WEAPON as usize], 1);
+        assert_eq!(game.weapon_tier, WEAPON_SPREAD);
+    }
+
+    #[test]
+    fn shockwaves_roll_along_the_floor_and_can_be_jumped() {
+        let mut game = GameState::new();
+        game.load_level(3);
+        game.start_boss_fight(0);
+        game.skip_cutscene();
+        stand_at(&mut game, 150.0, BOSS_ARENA_FLOOR_Y);
+        game.player_invuln_timer = 0;
+        let hp = game.player_hp;
+
+        game.boss_shockwaves[0] = Shockwave::new(game.player_x - 2.0, 3.0);
+        game.boss_shockwaves[1] = Shockwave::new(game.boss_floor_right - 1.0, 3.0);
+        game.update_boss_arena();
+        assert!(game.player_hp < hp);
+        assert_eq!(game.boss_shockwaves[0].x, 151.0);
+        assert!(!game.boss_shockwaves[1].active, "gone once it leaves the floor");
+
+        // Feet above the wave: it rolls right under Santa
+        let hp = game.player_hp;
+        game.player_invuln_timer = 0;
+        game.player_y -= 40.0;
+        game.boss_shockwaves[0] = Shockwave::new(game.player_x - 2.0, 3.0);
+        game.update_boss_arena();
+        assert_eq!(game.player_hp, hp);
+        assert!(game.boss_shockwaves[0].active);
+    }
+
+    #[test]
+    fn jumping_into_a_rope_grabs_it_and_hanging_stays_between_its_ends() {
+        let mut game = GameState::new();
+        let rope = &LEVEL_ROPES[0];
+        assert_eq!(rope.level, game.level);
+        game.player_x = 250.0;
+        game.player_y = rope.y + HANG_OFFSET;
+        game.player_vy = -2.0;
+        game.player_on_ground = false;
+        game.apply_player_input(PlayerInput::default());
+        assert_eq!(game.player_rope, Some(0));
+        assert_eq!(game.player_state, STATE_HANG);
+
+        let right = PlayerInput { right: true, ..PlayerInput::default() };
+        for _ in 0..60 {
+            game.apply_player_input(right);
+            game.update_player();
+        }
+        assert_eq!(game.player_x, rope.x2);
+        assert_eq!(game.player_y, rope.y + HANG_OFFSET);
+
+        game.apply_player_input(PlayerInput { down: true, ..PlayerInput::default() });
+        assert_eq!(game.player_rope, None);
+        assert_eq!(game.player_state, STATE_FALL);
+    }
+}